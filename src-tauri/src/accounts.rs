@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// shadow's "never" for the maximum password age
+const NEVER_EXPIRES: i64 = 99999;
+const NOLOGIN_SHELLS: [&str; 6] = ["nologin", "false", "sync", "shutdown", "halt", "true"];
+
+#[derive(serde::Serialize)]
+pub struct AccountFinding {
+    user: String,
+    uid: u32,
+    /// empty_password, password_expired, account_expired, password_never_expires or
+    /// system_login_shell
+    kind: String,
+    detail: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct AccountAudit {
+    /// /etc/shadow is root-only; without it only the shell check runs
+    shadow_readable: bool,
+    findings: Vec<AccountFinding>,
+}
+
+/// Fields of an /etc/shadow line; ages are in days since the epoch.
+struct Shadow {
+    hash: String,
+    last_change: Option<i64>,
+    max_age: Option<i64>,
+    expires: Option<i64>,
+}
+
+// UID_MIN from login.defs separates system accounts from people
+fn uid_min() -> u32 {
+    fs::read_to_string("/etc/login.defs").unwrap_or_default().lines()
+        .find_map(|l| l.trim().strip_prefix("UID_MIN")?.trim().parse().ok())
+        .unwrap_or(1000)
+}
+
+fn read_shadow() -> Option<HashMap<String, Shadow>> {
+    let text = fs::read_to_string("/etc/shadow").ok()?;
+    let num = |f: Option<&&str>| f.and_then(|v| v.parse::<i64>().ok());
+    Some(text.lines().filter_map(|l| {
+        let f: Vec<&str> = l.split(':').collect();
+        Some((f.first()?.to_string(), Shadow {
+            hash: f.get(1)?.to_string(),
+            last_change: num(f.get(2)),
+            max_age: num(f.get(4)),
+            expires: num(f.get(7)),
+        }))
+    }).collect())
+}
+
+// --- Commands ---
+
+/// Password and shell problems with local accounts, from /etc/passwd and, when readable,
+/// /etc/shadow.
+#[tauri::command]
+pub fn get_account_audit() -> AccountAudit {
+    let today = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64 / 86400).unwrap_or(0);
+    let uid_min = uid_min();
+    let shadow = read_shadow();
+    let mut findings = Vec::new();
+
+    for line in fs::read_to_string("/etc/passwd").unwrap_or_default().lines() {
+        let f: Vec<&str> = line.split(':').collect();
+        let (Some(user), Some(uid), Some(shell)) = (f.first(), f.get(2).and_then(|u| u.parse::<u32>().ok()), f.get(6)) else {
+            continue;
+        };
+        let mut push = |kind: &str, detail: String| findings.push(AccountFinding {
+            user: user.to_string(), uid, kind: kind.to_string(), detail,
+        });
+        // nobody (65534) is a system account despite its high uid
+        let system = uid != 0 && (uid < uid_min || uid == 65534);
+        let shell_name = shell.rsplit('/').next().unwrap_or_default();
+        if system && !shell.is_empty() && !NOLOGIN_SHELLS.contains(&shell_name) {
+            push("system_login_shell", format!("System account has login shell {}", shell));
+        }
+
+        let Some(entry) = shadow.as_ref().and_then(|s| s.get(*user)) else { continue };
+        if entry.hash.is_empty() {
+            push("empty_password", "Logs in without a password".to_string());
+            continue;
+        }
+        // "!" and "*" prefixes lock the password, so its age doesn't matter
+        if entry.hash.starts_with(['!', '*']) {
+            continue;
+        }
+        if entry.expires.is_some_and(|e| e <= today) {
+            push("account_expired", "Account has expired".to_string());
+        }
+        match (entry.last_change, entry.max_age) {
+            // 0 forces a change at the next login
+            (Some(0), _) => push("password_expired", "Must change password at next login".to_string()),
+            (Some(changed), Some(max)) if max < NEVER_EXPIRES && changed + max < today => {
+                push("password_expired", format!("Password expired {} days ago", today - changed - max));
+            }
+            (_, max) if !system && max.is_none_or(|m| m >= NEVER_EXPIRES) => {
+                push("password_never_expires", "Password has no maximum age".to_string());
+            }
+            _ => {}
+        }
+    }
+    AccountAudit { shadow_readable: shadow.is_some(), findings }
+}
+
@@ -0,0 +1,54 @@
+use std::collections::{HashSet, VecDeque};
+use tauri::api::notification::Notification;
+use tauri::{AppHandle, Manager, State};
+
+use crate::history::unix_now;
+use crate::AppState;
+
+const MAX_ALERTS: usize = 100;
+
+#[derive(Clone, serde::Serialize)]
+pub struct Alert {
+    time: u64,
+    key: String,
+    title: String,
+    body: String,
+}
+
+#[derive(Default)]
+pub struct AlertLog {
+    recent: VecDeque<Alert>,
+    // Keys currently firing, so a condition only notifies when it first trips
+    active: HashSet<String>,
+}
+
+/// Raises an alert identified by `key` unless it is already active.
+pub fn raise(app: &AppHandle, key: &str, title: &str, body: &str) {
+    let state = app.state::<AppState>();
+    let mut log = state.alerts.lock().unwrap();
+    if !log.active.insert(key.to_string()) {
+        return;
+    }
+
+    let alert = Alert { time: unix_now(), key: key.to_string(), title: title.to_string(), body: body.to_string() };
+    if log.recent.len() >= MAX_ALERTS {
+        log.recent.pop_front();
+    }
+    log.recent.push_back(alert.clone());
+    drop(log);
+
+    let _ = Notification::new(&app.config().tauri.bundle.identifier).title(title).body(body).show();
+    let _ = app.emit_all("alerts://new", alert);
+}
+
+/// Marks the condition behind `key` as recovered so it can alert again later.
+pub fn clear(app: &AppHandle, key: &str) {
+    app.state::<AppState>().alerts.lock().unwrap().active.remove(key);
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_alerts(state: State<AppState>) -> Vec<Alert> {
+    state.alerts.lock().unwrap().recent.iter().rev().cloned().collect()
+}
@@ -0,0 +1,198 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+use tauri::State;
+
+use crate::AppState;
+
+const NFT_TABLE: &str = "glassview_apps";
+
+// cgroup and uid of one running instance
+type Instance = (Option<String>, Option<u32>);
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct AppRule {
+    /// Matched against the process name or its executable's file name, like nice rules
+    app: String,
+    /// Allowed apps are remembered decisions; only denied ones produce nftables rules
+    allow: bool,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct BlockTarget {
+    app: String,
+    /// nftables match in use, e.g. `socket cgroupv2 level 4 "..."` or `meta skuid 991`
+    matcher: Option<String>,
+    /// Why a running instance could not be isolated
+    problem: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct AppFirewallStatus {
+    rules: Vec<AppRule>,
+    targets: Vec<BlockTarget>,
+    error: Option<String>,
+}
+
+/// Outbound blocking for denied apps through a dedicated nftables table. The rules themselves
+/// live in `Settings::app_firewall_rules`.
+#[derive(Default)]
+pub struct AppFirewall {
+    targets: Vec<BlockTarget>,
+    /// Ruleset last handed to nft, so unchanged sets are not re-applied every tick; None until
+    /// the first pass, which also clears a table left behind by a crash
+    attempted: Option<String>,
+    error: Option<String>,
+}
+
+fn cgroup_of(pid: u32) -> Option<String> {
+    // cgroup v2 has a single "0::/path" line
+    let line = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    let path = line.lines().find_map(|l| l.strip_prefix("0::/"))?.trim();
+    Some(path.to_string()).filter(|p| !p.is_empty())
+}
+
+fn nft(ruleset: &str) -> Result<(), String> {
+    let mut child = Command::new("nft").args(["-f", "-"])
+        .stdin(Stdio::piped()).stderr(Stdio::piped()).spawn()
+        .map_err(|_| "nft is not installed".to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(ruleset.as_bytes());
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+// Declaring the table first makes the delete succeed whether or not it exists
+fn clear_script() -> String {
+    format!("table inet {0}\ndelete table inet {0}\n", NFT_TABLE)
+}
+
+impl AppFirewall {
+    fn matches(rule: &AppRule, name: &str, exe_name: Option<&str>) -> bool {
+        rule.app == name || Some(rule.app.as_str()) == exe_name
+    }
+
+    /// Resolves denied apps to cgroup matches (or their uid when the cgroup is shared) and
+    /// returns the nft script to run when the result changed; the caller runs it through
+    /// `apply` once it has let go of the process data. Expects fresh process data.
+    pub fn plan(&mut self, sys: &System, rules: &[AppRule]) -> Option<String> {
+        let denied: Vec<&AppRule> = rules.iter().filter(|r| !r.allow).collect();
+
+        // Which apps live in each cgroup and run as each uid, to decide what is safe to block
+        let mut cgroup_apps: HashMap<String, HashSet<String>> = HashMap::new();
+        let mut uid_apps: HashMap<u32, HashSet<String>> = HashMap::new();
+        let mut instances: HashMap<String, Vec<Instance>> = HashMap::new();
+        if !denied.is_empty() {
+            for (pid, process) in sys.processes() {
+                let exe_name = process.exe().file_name().map(|n| n.to_string_lossy().to_string());
+                let app = denied.iter()
+                    .find(|r| Self::matches(r, process.name(), exe_name.as_deref()))
+                    .map(|r| r.app.clone())
+                    .unwrap_or_else(|| process.name().to_string());
+                let cgroup = cgroup_of(pid.as_u32());
+                let uid = process.user_id().map(|u| **u);
+                if let Some(c) = &cgroup {
+                    cgroup_apps.entry(c.clone()).or_default().insert(app.clone());
+                }
+                if let Some(u) = uid {
+                    uid_apps.entry(u).or_default().insert(app.clone());
+                }
+                if denied.iter().any(|r| r.app == app) {
+                    instances.entry(app).or_default().push((cgroup, uid));
+                }
+            }
+        }
+
+        let mut targets = Vec::new();
+        for rule in &denied {
+            // Ordered so an unchanged set renders to the same ruleset text
+            let mut matchers = BTreeSet::new();
+            let mut problem = None;
+            for (cgroup, uid) in instances.get(&rule.app).into_iter().flatten() {
+                let exclusive = |apps: Option<&HashSet<String>>| apps.is_some_and(|a| a.len() == 1);
+                if let Some(c) = cgroup.as_ref().filter(|c| exclusive(cgroup_apps.get(*c))) {
+                    matchers.insert(format!("socket cgroupv2 level {} \"{}\"", c.split('/').count(), c));
+                } else if let Some(u) = uid.filter(|u| *u != 0 && exclusive(uid_apps.get(u))) {
+                    matchers.insert(format!("meta skuid {}", u));
+                } else {
+                    problem = Some("Shares its cgroup and user with other programs".to_string());
+                }
+            }
+            if matchers.is_empty() {
+                targets.push(BlockTarget { app: rule.app.clone(), matcher: None, problem: problem.clone() });
+            }
+            for m in matchers {
+                targets.push(BlockTarget { app: rule.app.clone(), matcher: Some(m), problem: problem.clone() });
+            }
+        }
+
+        let mut ruleset = String::new();
+        if targets.iter().any(|t| t.matcher.is_some()) {
+            // Declaring then deleting the table makes the reload atomic and idempotent
+            let _ = writeln!(ruleset, "table inet {0}\ndelete table inet {0}\ntable inet {0} {{", NFT_TABLE);
+            let _ = writeln!(ruleset, "  chain output {{\n    type filter hook output priority 0; policy accept;\n    oif \"lo\" accept");
+            for t in &targets {
+                if let Some(m) = &t.matcher {
+                    let _ = writeln!(ruleset, "    {} drop comment \"{}\"", m, t.app.replace('"', ""));
+                }
+            }
+            let _ = writeln!(ruleset, "  }}\n}}");
+        }
+        self.targets = targets;
+
+        if self.attempted.as_ref() == Some(&ruleset) {
+            return None;
+        }
+        let first = self.attempted.replace(ruleset.clone()).is_none();
+        if !ruleset.is_empty() {
+            return Some(ruleset);
+        }
+        // With nothing left to block the table goes away entirely. Only root could have left
+        // one behind before this run, and only root can remove it
+        (!first || unsafe { libc::geteuid() } == 0).then(clear_script)
+    }
+
+    /// Records the outcome of running a script from `plan`.
+    pub fn applied(&mut self, result: Result<(), String>) {
+        self.error = result.err();
+    }
+
+    /// Removes the table on exit, when this run put one in place.
+    pub fn clear(&mut self) {
+        if self.attempted.take().is_some_and(|r| !r.is_empty()) {
+            let _ = nft(&clear_script());
+        }
+    }
+}
+
+/// Runs a script from `AppFirewall::plan`.
+pub fn apply(script: &str) -> Result<(), String> {
+    nft(script)
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_app_firewall(state: State<AppState>) -> AppFirewallStatus {
+    let rules = state.settings.lock().unwrap().app_firewall_rules.clone();
+    let fw = state.app_firewall.lock().unwrap();
+    AppFirewallStatus { rules, targets: fw.targets.clone(), error: fw.error.clone() }
+}
+
+/// Replaces the allow/deny list in settings; the sampler applies it on its next process refresh.
+#[tauri::command]
+pub fn set_app_firewall_rules(rules: Vec<AppRule>, state: State<AppState>) -> Result<(), String> {
+    let mut settings = state.settings.lock().unwrap();
+    let mut updated = settings.clone();
+    updated.app_firewall_rules = rules;
+    updated.save()?;
+    *settings = updated;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::process::Command;
+
+#[derive(serde::Serialize)]
+pub struct AudioDevice {
+    /// sink (output) or source (input)
+    kind: String,
+    /// Server-side name, e.g. "alsa_output.pci-0000_00_1f.3.analog-stereo"
+    name: String,
+    description: String,
+    /// RUNNING, IDLE or SUSPENDED
+    state: String,
+    default: bool,
+    /// Average over channels, in percent; above 100 is software amplification
+    volume: Option<u32>,
+    muted: bool,
+}
+
+// pactl talks to PulseAudio and to PipeWire's pulse server alike; its labels are translated,
+// so force English
+fn pactl(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("pactl").args(args).env("LC_ALL", "C").output()
+        .map_err(|_| "pactl is not installed".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// "Default Sink: alsa_output..." from `pactl info`
+fn default(info: &str, key: &str) -> Option<String> {
+    info.lines().find_map(|l| l.strip_prefix(key)?.strip_prefix(": ")).map(|v| v.trim().to_string())
+}
+
+// "Sink #52" records with tab-indented "Key: value" lines
+fn devices(kind: &str, text: &str, default: Option<&str>) -> Vec<AudioDevice> {
+    let title = if kind == "sink" { "Sink #" } else { "Source #" };
+    text.split(title).skip(1).filter_map(|record| {
+        let field = |key: &str| record.lines().find_map(|l| l.trim().strip_prefix(key)?.strip_prefix(": ")).map(str::trim);
+        let name = field("Name")?.to_string();
+        // Every sink has a monitor source that records what it plays
+        if name.ends_with(".monitor") {
+            return None;
+        }
+        // "front-left: 42597 /  65% / -11.23 dB,   front-right: ..."
+        let percents: Vec<u32> = field("Volume").unwrap_or_default().split_whitespace()
+            .filter_map(|t| t.strip_suffix('%')?.parse().ok())
+            .collect();
+        Some(AudioDevice {
+            kind: kind.to_string(),
+            description: field("Description").unwrap_or(&name).to_string(),
+            state: field("State").unwrap_or_default().to_string(),
+            default: default == Some(name.as_str()),
+            volume: (!percents.is_empty()).then(|| percents.iter().sum::<u32>() / percents.len() as u32),
+            muted: field("Mute") == Some("yes"),
+            name,
+        })
+    }).collect()
+}
+
+// --- Commands ---
+
+/// Output and input devices of the sound server, with which is default and their volume.
+#[tauri::command]
+pub fn get_audio_devices() -> Result<Vec<AudioDevice>, String> {
+    let info = pactl(&["info"])?;
+    let mut all = devices("sink", &pactl(&["list", "sinks"])?, default(&info, "Default Sink").as_deref());
+    all.extend(devices("source", &pactl(&["list", "sources"])?, default(&info, "Default Source").as_deref()));
+    Ok(all)
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::health::{unix_millis, Sampled};
+use crate::{sockets, AppState};
+
+const SAMPLE_EVERY: Duration = Duration::from_secs(2);
+// Sampling stops this long after the last request, so ss and the /proc fd walk only run
+// while something is showing the rates
+const IDLE_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Clone, serde::Serialize)]
+pub struct ProcessBandwidth {
+    pid: u32,
+    name: String,
+    rx_bytes_per_sec: f64,
+    tx_bytes_per_sec: f64,
+    /// Since glassview started watching
+    rx_total: u64,
+    tx_total: u64,
+}
+
+#[derive(Default)]
+pub struct BandwidthState {
+    sampled_at: u64,
+    processes: Vec<ProcessBandwidth>,
+    requested_at: Option<Instant>,
+}
+
+/// Byte counters of one TCP socket, from the kernel's tcp_info.
+#[derive(Clone, Copy)]
+pub struct Counters {
+    pub received: u64,
+    pub sent: u64,
+}
+
+/// Counters by socket inode. `ss -tinHe` prints each socket as a line with "ino:<inode>"
+/// followed by an indented tcp_info line carrying "bytes_received:" and "bytes_sent:".
+pub fn tcp_counters() -> Result<HashMap<u64, Counters>, String> {
+    let output = Command::new("ss").args(["-t", "-i", "-n", "-H", "-e"]).output()
+        .map_err(|_| "ss is not installed".to_string())?;
+    let mut counters = HashMap::new();
+    let mut inode = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let field = |key: &str| line.split_whitespace().find_map(|w| w.strip_prefix(key)?.parse::<u64>().ok());
+        if !line.starts_with(char::is_whitespace) {
+            inode = field("ino:").filter(|i| *i != 0);
+            continue;
+        }
+        let Some(ino) = inode.take() else { continue };
+        // bytes_sent (4.19+) includes retransmissions; older kernels only have bytes_acked
+        let sent = field("bytes_sent:").or_else(|| field("bytes_acked:")).unwrap_or(0);
+        counters.insert(ino, Counters { received: field("bytes_received:").unwrap_or(0), sent });
+    }
+    Ok(counters)
+}
+
+struct Accounting {
+    previous: HashMap<u64, Counters>,
+    totals: HashMap<u32, (u64, u64)>,
+    last: Instant,
+}
+
+impl Accounting {
+    // Counters from before the first sample are history, not traffic
+    fn start() -> Self {
+        Accounting { previous: tcp_counters().unwrap_or_default(), totals: HashMap::new(), last: Instant::now() }
+    }
+
+    // Traffic of sockets that closed since the last sample is lost; new sockets count in full
+    fn sample(&mut self) -> Result<Vec<ProcessBandwidth>, String> {
+        let current = tcp_counters()?;
+        let owners = sockets::socket_owners();
+        let elapsed = self.last.elapsed().as_secs_f64().max(0.001);
+        self.last = Instant::now();
+        let mut deltas: HashMap<u32, (u64, u64)> = HashMap::new();
+        for (inode, now) in &current {
+            let Some(pid) = owners.get(inode) else { continue };
+            let before = self.previous.get(inode).copied().unwrap_or(Counters { received: 0, sent: 0 });
+            let delta = deltas.entry(*pid).or_default();
+            delta.0 += now.received.saturating_sub(before.received);
+            delta.1 += now.sent.saturating_sub(before.sent);
+        }
+        self.previous = current;
+        let mut processes: Vec<ProcessBandwidth> = deltas.into_iter().filter_map(|(pid, (rx, tx))| {
+            let total = self.totals.entry(pid).or_default();
+            total.0 += rx;
+            total.1 += tx;
+            (total.0 + total.1 > 0).then(|| ProcessBandwidth {
+                pid,
+                name: sockets::process_name(pid).unwrap_or_else(|| pid.to_string()),
+                rx_bytes_per_sec: rx as f64 / elapsed,
+                tx_bytes_per_sec: tx as f64 / elapsed,
+                rx_total: total.0,
+                tx_total: total.1,
+            })
+        }).collect();
+        self.totals.retain(|pid, _| processes.iter().any(|p| p.pid == *pid));
+        processes.sort_by(|a, b| (b.rx_bytes_per_sec + b.tx_bytes_per_sec).total_cmp(&(a.rx_bytes_per_sec + a.tx_bytes_per_sec)));
+        Ok(processes)
+    }
+}
+
+/// Attributes TCP traffic to processes by sampling per-socket kernel counters and matching
+/// socket inodes to /proc/<pid>/fd. UDP sockets carry no byte counters, so they aren't counted.
+/// Runs from the first `get_process_bandwidth` until `IDLE_AFTER` without one.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut accounting: Option<Accounting> = None;
+        loop {
+            std::thread::sleep(SAMPLE_EVERY);
+            let state = app.state::<AppState>();
+            let wanted = state.bandwidth.lock().unwrap().requested_at.is_some_and(|at| at.elapsed() < IDLE_AFTER);
+            if !wanted {
+                if accounting.take().is_some() {
+                    // Idle rather than overdue, and no rates from before the pause
+                    state.health.lock().unwrap().ok("bandwidth", None);
+                    let mut bandwidth = state.bandwidth.lock().unwrap();
+                    bandwidth.sampled_at = 0;
+                    bandwidth.processes.clear();
+                }
+                continue;
+            }
+            let Some(accounting) = accounting.as_mut() else {
+                accounting = Some(Accounting::start());
+                continue;
+            };
+            let result = accounting.sample();
+            state.health.lock().unwrap().record("bandwidth", Some(SAMPLE_EVERY.as_millis() as u64), &result);
+            if let Ok(processes) = result {
+                let mut bandwidth = state.bandwidth.lock().unwrap();
+                bandwidth.sampled_at = unix_millis();
+                bandwidth.processes = processes;
+            }
+        }
+    });
+}
+
+// --- Commands ---
+
+/// Receive and send rates per process over the last sample, busiest first. The first call
+/// starts sampling, so it comes back empty.
+#[tauri::command]
+pub fn get_process_bandwidth(state: State<AppState>) -> Sampled<Vec<ProcessBandwidth>> {
+    let mut bandwidth = state.bandwidth.lock().unwrap();
+    bandwidth.requested_at = Some(Instant::now());
+    Sampled::new(bandwidth.sampled_at, Some(SAMPLE_EVERY.as_millis() as u64), bandwidth.processes.clone())
+}
@@ -0,0 +1,94 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager, State};
+
+use crate::services::Scope;
+use crate::AppState;
+
+#[derive(serde::Deserialize)]
+pub struct BatchCall {
+    /// Name of a read-only command, e.g. "get_security_audit"
+    cmd: String,
+    /// Named arguments, as they would be passed to `invoke`
+    #[serde(default)]
+    args: Value,
+    /// Result key; defaults to `cmd`, so set it when calling the same command twice
+    key: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct BatchResult {
+    value: Option<Value>,
+    error: Option<String>,
+}
+
+fn arg<T: DeserializeOwned>(args: &Value, name: &str) -> Result<T, String> {
+    let v = args.get(name).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(v).map_err(|e| format!("{}: {}", name, e))
+}
+
+fn json<T: Serialize>(value: T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
+
+// Only cheap queries that read state or make a quick D-Bus call; anything that
+// changes the system or runs for seconds keeps its own invoke
+fn dispatch(call: &BatchCall, state: &State<AppState>) -> Result<Value, String> {
+    let a = &call.args;
+    let state = state.clone();
+    match call.cmd.as_str() {
+        "get_system_stats" => json(crate::get_system_stats(state)),
+        "get_processes" => json(crate::get_processes(state)),
+        "get_kernel_threads" => json(crate::get_kernel_threads(state)),
+        "get_security_audit" => json(crate::security_audit(&state)),
+        "get_hardware_info" => json(crate::get_hardware_info(state)),
+        "get_alerts" => json(crate::alerts::get_alerts(state)),
+        "get_settings" => json(crate::settings::get_settings(state)),
+        "get_nice_rules" => json(crate::rules::get_nice_rules(state)),
+        "get_app_firewall" => json(crate::app_firewall::get_app_firewall(state)),
+        "get_file_access_events" => json(crate::file_monitor::get_file_access_events(state)),
+        "get_drift_timeline" => json(crate::drift::get_drift_timeline(arg(a, "since")?, state)),
+        "get_top_offenders" => json(crate::history::get_top_offenders(arg(a, "period")?, state)?),
+        "get_sessions" => json(crate::sessions::get_sessions(state)?),
+        "get_collector_health" => json(crate::health::get_collector_health(state)),
+        "get_watches" => json(crate::watch::get_watches(state)),
+        "get_listening_ports" => json(crate::sockets::get_listening_ports()),
+        "get_kernel_integrity" => json(crate::security::get_kernel_integrity()),
+        "audit_sysctl" => json(crate::security::audit_sysctl()),
+        "get_inode_usage" => json(crate::storage::get_inode_usage()),
+        "get_mount_audit" => json(crate::storage::get_mount_audit()),
+        "get_services" => json(crate::services::get_services(arg::<Option<Scope>>(a, "scope")?, state)),
+        "list_units" => json(crate::services::list_units(arg(a, "filter")?, arg(a, "scope")?)?),
+        "get_failed_units" => json(crate::services::get_failed_units(arg(a, "scope")?)?),
+        "get_timers" => json(crate::services::timers(arg(a, "scope")?)?),
+        other => Err(format!("{} is not available in a batch", other)),
+    }
+}
+
+// --- Commands ---
+
+/// Runs several read-only queries in one round trip. Each call's outcome is keyed by
+/// its `key` (or command name); one failing call does not affect the others.
+#[tauri::command]
+pub async fn batch(commands: Vec<BatchCall>, app: AppHandle) -> HashMap<String, BatchResult> {
+    // Each call gets its own blocking thread, so a slow D-Bus query neither stalls the
+    // async runtime nor holds up the others
+    let pending: Vec<_> = commands.into_iter()
+        .map(|call| {
+            let app = app.clone();
+            let key = call.key.clone().unwrap_or_else(|| call.cmd.clone());
+            (key, tauri::async_runtime::spawn_blocking(move || dispatch(&call, &app.state::<AppState>())))
+        })
+        .collect();
+    let mut results = HashMap::new();
+    for (key, handle) in pending {
+        let result = match handle.await.map_err(|e| e.to_string()).and_then(|r| r) {
+            Ok(v) => BatchResult { value: Some(v), error: None },
+            Err(e) => BatchResult { value: None, error: Some(e) },
+        };
+        results.insert(key, result);
+    }
+    results
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use zbus::blocking::fdo::ObjectManagerProxy;
+use zbus::blocking::Connection;
+use zbus::dbus_proxy;
+use zbus::zvariant::{ObjectPath, OwnedValue};
+
+// Long enough for most devices to answer an inquiry
+const SCAN_TIME: Duration = Duration::from_secs(6);
+
+#[dbus_proxy(interface = "org.bluez.Adapter1", default_service = "org.bluez")]
+trait Adapter {
+    fn start_discovery(&self) -> zbus::Result<()>;
+    fn stop_discovery(&self) -> zbus::Result<()>;
+    fn remove_device(&self, device: &ObjectPath<'_>) -> zbus::Result<()>;
+}
+
+#[dbus_proxy(interface = "org.bluez.Device1", default_service = "org.bluez")]
+trait Device {
+    fn connect(&self) -> zbus::Result<()>;
+    fn disconnect(&self) -> zbus::Result<()>;
+}
+
+#[derive(serde::Serialize)]
+pub struct BluetoothDevice {
+    /// BlueZ object path, e.g. "/org/bluez/hci0/dev_AA_BB_CC_DD_EE_FF"; the commands take it
+    path: String,
+    address: String,
+    /// The user-set alias, else the name the device advertises
+    name: String,
+    /// freedesktop icon name hinting at the kind, e.g. "audio-headset" or "input-mouse"
+    icon: Option<String>,
+    paired: bool,
+    trusted: bool,
+    connected: bool,
+    /// Signal strength in dBm, only while the device is being discovered
+    rssi: Option<i16>,
+    /// Percent, for devices reporting it over the battery service
+    battery: Option<u8>,
+}
+
+type Properties = HashMap<String, OwnedValue>;
+
+fn objects(conn: &Connection) -> Result<HashMap<String, HashMap<String, Properties>>, String> {
+    let manager = ObjectManagerProxy::builder(conn)
+        .destination("org.bluez").and_then(|b| b.path("/"))
+        .and_then(|b| b.build())
+        .map_err(|e| e.to_string())?;
+    let objects = manager.get_managed_objects().map_err(|_| "BlueZ is not running".to_string())?;
+    Ok(objects.into_iter()
+        .map(|(path, ifaces)| (path.to_string(), ifaces.into_iter().map(|(i, p)| (i.to_string(), p)).collect()))
+        .collect())
+}
+
+fn get<'a, T: TryFrom<&'a OwnedValue>>(props: &'a Properties, key: &str) -> Option<T> {
+    props.get(key).and_then(|v| T::try_from(v).ok())
+}
+
+fn string(props: &Properties, key: &str) -> Option<String> {
+    get::<&str>(props, key).map(String::from)
+}
+
+// Only device objects, so nothing else on the bus can be driven through these commands
+fn device_path(path: &str) -> Result<ObjectPath<'_>, String> {
+    if !path.starts_with("/org/bluez/hci") || !path.contains("/dev_") {
+        return Err(format!("Not a Bluetooth device: {}", path));
+    }
+    ObjectPath::try_from(path).map_err(|e| e.to_string())
+}
+
+fn device(conn: &Connection, path: &str) -> Result<DeviceProxyBlocking<'static>, String> {
+    DeviceProxyBlocking::builder(conn)
+        .path(device_path(path)?.into_owned()).and_then(|b| b.build())
+        .map_err(|e| e.to_string())
+}
+
+// Lets adapters pick up devices in pairing mode
+fn scan(conn: &Connection) -> Result<(), String> {
+    let adapters: Vec<AdapterProxyBlocking> = objects(conn)?.into_iter()
+        .filter(|(_, ifaces)| ifaces.get("org.bluez.Adapter1").and_then(|a| get::<bool>(a, "Powered")) == Some(true))
+        .filter_map(|(path, _)| AdapterProxyBlocking::builder(conn).path(path).ok()?.build().ok())
+        .collect();
+    if adapters.is_empty() {
+        return Err("No powered Bluetooth adapter".to_string());
+    }
+    let started: Vec<&AdapterProxyBlocking> = adapters.iter().filter(|a| a.start_discovery().is_ok()).collect();
+    std::thread::sleep(SCAN_TIME);
+    for adapter in started {
+        let _ = adapter.stop_discovery();
+    }
+    Ok(())
+}
+
+fn list(conn: &Connection) -> Result<Vec<BluetoothDevice>, String> {
+    let mut devices: Vec<BluetoothDevice> = objects(conn)?.into_iter().filter_map(|(path, ifaces)| {
+        let dev = ifaces.get("org.bluez.Device1")?;
+        let address = string(dev, "Address")?;
+        Some(BluetoothDevice {
+            name: string(dev, "Alias").or_else(|| string(dev, "Name")).unwrap_or_else(|| address.clone()),
+            icon: string(dev, "Icon"),
+            paired: get(dev, "Paired").unwrap_or(false),
+            trusted: get(dev, "Trusted").unwrap_or(false),
+            connected: get(dev, "Connected").unwrap_or(false),
+            rssi: get(dev, "RSSI"),
+            battery: ifaces.get("org.bluez.Battery1").and_then(|b| get(b, "Percentage")),
+            address,
+            path,
+        })
+    }).collect();
+    // Paired devices first, then nearby ones by signal strength
+    devices.sort_by_key(|d| (!d.connected, !d.paired, std::cmp::Reverse(d.rssi.unwrap_or(i16::MIN)), d.name.to_lowercase()));
+    Ok(devices)
+}
+
+// --- Commands ---
+
+/// Paired devices and the ones nearby; with `scan`, discovers for a few seconds first.
+#[tauri::command]
+pub async fn get_bluetooth_devices(scan: Option<bool>) -> Result<Vec<BluetoothDevice>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = Connection::system().map_err(|e| e.to_string())?;
+        if scan.unwrap_or(false) {
+            self::scan(&conn)?;
+        }
+        list(&conn)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn connect_bluetooth_device(path: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = Connection::system().map_err(|e| e.to_string())?;
+        device(&conn, &path)?.connect().map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn disconnect_bluetooth_device(path: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = Connection::system().map_err(|e| e.to_string())?;
+        device(&conn, &path)?.disconnect().map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Unpairs the device and forgets it.
+#[tauri::command]
+pub async fn remove_bluetooth_device(path: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let device = device_path(&path)?;
+        // The adapter owning "/org/bluez/hci0/dev_..." is "/org/bluez/hci0"
+        let adapter = path.rsplit_once('/').map(|(a, _)| a.to_string()).ok_or("Invalid device path")?;
+        let conn = Connection::system().map_err(|e| e.to_string())?;
+        AdapterProxyBlocking::builder(&conn)
+            .path(adapter).and_then(|b| b.build())
+            .and_then(|a| a.remove_device(&device))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,101 @@
+use std::process::Command;
+
+#[derive(serde::Serialize)]
+pub struct BlameEntry {
+    unit: String,
+    ms: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct ChainLink {
+    unit: String,
+    /// Nesting level in the chain, 0 for the default target
+    depth: usize,
+    /// When the unit became active, relative to boot
+    active_at_ms: u64,
+    /// How long the unit itself took; absent for units that started instantly
+    startup_ms: Option<u64>,
+}
+
+#[derive(serde::Serialize)]
+pub struct BootAnalysis {
+    /// Total from `systemd-analyze time`, when boot has finished
+    total_ms: Option<u64>,
+    blame: Vec<BlameEntry>,
+    critical_chain: Vec<ChainLink>,
+}
+
+// systemd prints spans like "1min 2.345s", "345ms" or "1h 2min"
+fn parse_span(span: &str) -> Option<u64> {
+    let mut total = 0.0;
+    for part in span.split_whitespace() {
+        let split = part.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (value, unit) = part.split_at(split);
+        let value: f64 = value.parse().ok()?;
+        total += value * match unit {
+            "us" | "µs" => 0.001,
+            "ms" => 1.0,
+            "s" => 1000.0,
+            "min" => 60_000.0,
+            "h" => 3_600_000.0,
+            _ => return None,
+        };
+    }
+    Some(total.round() as u64)
+}
+
+fn systemd_analyze(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("systemd-analyze")
+        .args(args)
+        .arg("--no-pager")
+        .output()
+        .map_err(|_| "systemd-analyze is not installed".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn parse_blame(out: &str) -> Vec<BlameEntry> {
+    out.lines()
+        .filter_map(|line| {
+            let (span, unit) = line.trim().rsplit_once(' ')?;
+            Some(BlameEntry { unit: unit.to_string(), ms: parse_span(span)? })
+        })
+        .collect()
+}
+
+// "  └─docker.service @3.100s +2.019s"
+fn parse_chain(out: &str) -> Vec<ChainLink> {
+    out.lines()
+        .filter(|l| l.contains('@'))
+        .filter_map(|line| {
+            let start = line.find(|c: char| c.is_alphanumeric())?;
+            // Tree glyphs are multi-byte, so count characters rather than bytes
+            let depth = line[..start].chars().count() / 2;
+            let (unit, times) = line[start..].split_once(" @")?;
+            let (active, startup) = match times.split_once(" +") {
+                Some((a, s)) => (a, parse_span(s)),
+                None => (times, None),
+            };
+            Some(ChainLink { unit: unit.to_string(), depth, active_at_ms: parse_span(active)?, startup_ms: startup })
+        })
+        .collect()
+}
+
+// --- Commands ---
+
+/// Per-unit boot cost (`systemd-analyze blame`) and the boot's critical chain.
+#[tauri::command]
+pub async fn get_boot_analysis() -> Result<BootAnalysis, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let blame = parse_blame(&systemd_analyze(&["blame"])?);
+        let critical_chain = parse_chain(&systemd_analyze(&["critical-chain"])?);
+        // "Startup finished in 3.1s (kernel) + 12.3s (userspace) = 15.4s"
+        let total_ms = systemd_analyze(&["time"]).ok()
+            .and_then(|t| t.lines().next().and_then(|l| l.rsplit_once("= ")).and_then(|(_, s)| parse_span(s.trim())));
+        Ok(BootAnalysis { total_ms, blame, critical_chain })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use sysinfo::{System, SystemExt};
+
+use crate::gpu;
+
+const SECTOR_SIZE: f64 = 512.0;
+const MAX_DURATION_SECS: u64 = 60;
+
+#[derive(serde::Serialize)]
+pub struct BurstSample {
+    /// Milliseconds since the capture started
+    t_ms: u64,
+    cpu: f32,
+    mem_used: u64,
+    disk_read_bps: f64,
+    disk_write_bps: f64,
+    gpu_busy: Option<f32>,
+}
+
+// (busy, total) jiffies from the aggregate cpu line
+fn read_cpu_times() -> (u64, u64) {
+    let stat = fs::read_to_string("/proc/stat").unwrap_or_default();
+    let fields: Vec<u64> = stat.lines().next().unwrap_or_default()
+        .split_whitespace().skip(1)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    let total: u64 = fields.iter().sum();
+    // idle + iowait
+    let idle = fields.get(3).copied().unwrap_or(0) + fields.get(4).copied().unwrap_or(0);
+    (total - idle, total)
+}
+
+// Total sectors (read, written) across whole disks, partitions would double count
+fn read_disk_sectors() -> (u64, u64) {
+    let stats = fs::read_to_string("/proc/diskstats").unwrap_or_default();
+    stats.lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>())
+        .filter(|f| f.len() > 9 && Path::new(&format!("/sys/block/{}/device", f[2])).exists())
+        .fold((0, 0), |(r, w), f| {
+            (r + f[5].parse::<u64>().unwrap_or(0), w + f[9].parse::<u64>().unwrap_or(0))
+        })
+}
+
+fn capture(duration: Duration, interval: Duration) -> Vec<BurstSample> {
+    let mut sys = System::new();
+    let mut samples = Vec::new();
+    let start = Instant::now();
+
+    let mut last_cpu = read_cpu_times();
+    let mut last_disk = read_disk_sectors();
+    let mut last_at = start;
+
+    while start.elapsed() < duration {
+        std::thread::sleep(interval);
+        let cpu = read_cpu_times();
+        let disk = read_disk_sectors();
+        let now = Instant::now();
+        let secs = now.duration_since(last_at).as_secs_f64();
+        sys.refresh_memory();
+
+        let total = cpu.1.saturating_sub(last_cpu.1);
+        samples.push(BurstSample {
+            t_ms: now.duration_since(start).as_millis() as u64,
+            cpu: if total == 0 { 0.0 } else { cpu.0.saturating_sub(last_cpu.0) as f32 / total as f32 * 100.0 },
+            mem_used: sys.used_memory(),
+            disk_read_bps: disk.0.saturating_sub(last_disk.0) as f64 * SECTOR_SIZE / secs,
+            disk_write_bps: disk.1.saturating_sub(last_disk.1) as f64 * SECTOR_SIZE / secs,
+            gpu_busy: gpu::busy_percent(),
+        });
+
+        last_cpu = cpu;
+        last_disk = disk;
+        last_at = now;
+    }
+    samples
+}
+
+// --- Commands ---
+
+/// Samples at `interval_ms` for `duration` seconds and returns the whole buffer at once.
+#[tauri::command]
+pub async fn start_burst_capture(duration: u64, interval_ms: u64) -> Result<Vec<BurstSample>, String> {
+    let duration = Duration::from_secs(duration.min(MAX_DURATION_SECS));
+    let interval = Duration::from_millis(interval_ms.clamp(50, 1000));
+    tauri::async_runtime::spawn_blocking(move || capture(duration, interval))
+        .await
+        .map_err(|e| e.to_string())
+}
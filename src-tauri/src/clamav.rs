@@ -0,0 +1,186 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::file_monitor::expand_home;
+use crate::AppState;
+
+// Progress events are throttled; a scan touches thousands of files per second
+const PROGRESS_EVERY: Duration = Duration::from_millis(250);
+
+#[derive(Clone, serde::Serialize)]
+pub struct Detection {
+    path: String,
+    /// Signature name, e.g. "Win.Test.EICAR_HDB-1"
+    signature: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ScanProgress {
+    scanned: u64,
+    /// Files under the scan path, counted up front; 0 with clamdscan, which reports no progress
+    total: u64,
+    current: String,
+    detections: usize,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ScanResult {
+    path: String,
+    /// clamscan or clamdscan
+    scanner: String,
+    scanned: u64,
+    detections: Vec<Detection>,
+    /// Stopped with `stop_clamav_scan`
+    cancelled: bool,
+    error: Option<String>,
+}
+
+// The scanner's `Child` stays with its reader thread, which reaps it; this is only enough to
+// stop it. `id` tells a stopped scan's thread from the one started after it.
+struct RunningScan {
+    id: u64,
+    pid: u32,
+    stopped: Arc<AtomicBool>,
+}
+
+#[derive(Default)]
+pub struct ClamavState {
+    scan: Option<RunningScan>,
+    next_id: u64,
+    last: Option<ScanResult>,
+}
+
+fn installed(cmd: &str) -> bool {
+    ["/usr/bin", "/usr/local/bin", "/usr/sbin"].iter().any(|d| Path::new(&format!("{}/{}", d, cmd)).exists())
+}
+
+// The daemon has its signatures loaded already, so it starts instantly
+fn clamd_running() -> bool {
+    ["clamav-daemon", "clamd@scan", "clamd"].iter().any(|unit| {
+        Command::new("systemctl").args(["is-active", "--quiet", unit]).status().is_ok_and(|s| s.success())
+    })
+}
+
+fn count_files(root: &Path) -> u64 {
+    let mut count = 0;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => stack.push(entry.path()),
+                Ok(t) if t.is_file() => count += 1,
+                _ => {}
+            }
+        }
+    }
+    count.max(u64::from(root.is_file()))
+}
+
+// "/home/me/eicar.com: Win.Test.EICAR_HDB-1 FOUND" or "/home/me/notes.txt: OK"
+fn parse_line(line: &str) -> Option<(&str, Option<&str>)> {
+    let (path, verdict) = line.rsplit_once(": ")?;
+    if verdict == "OK" || verdict == "Empty file" || verdict.ends_with("Symbolic link") {
+        Some((path, None))
+    } else {
+        verdict.strip_suffix(" FOUND").map(|sig| (path, Some(sig)))
+    }
+}
+
+// --- Commands ---
+
+/// Scans `path` with clamdscan when the daemon runs, otherwise clamscan. Emits
+/// `clamav://progress` while running and `clamav://done` with the findings.
+#[tauri::command]
+pub fn start_clamav_scan(path: String, app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    let path = expand_home(&path);
+    let root = Path::new(&path);
+    if !root.exists() {
+        return Err(format!("{} does not exist", path));
+    }
+    let mut clamav = state.clamav.lock().unwrap();
+    if clamav.scan.is_some() {
+        return Err("A scan is already running".to_string());
+    }
+    let daemon = installed("clamdscan") && clamd_running();
+    let scanner = if daemon { "clamdscan" } else if installed("clamscan") { "clamscan" } else {
+        return Err("ClamAV is not installed".to_string());
+    };
+    let mut cmd = Command::new(scanner);
+    if daemon {
+        // Passes file descriptors so the daemon can read files only we have access to
+        cmd.args(["--fdpass", "--multiscan", "--no-summary"]);
+    } else {
+        cmd.args(["--recursive", "--no-summary"]);
+    }
+    let mut child = cmd.arg(&path).stdout(Stdio::piped()).stderr(Stdio::null()).spawn().map_err(|e| e.to_string())?;
+    let stdout = child.stdout.take().ok_or("No scanner output")?;
+    let id = clamav.next_id;
+    clamav.next_id += 1;
+    let stopped = Arc::new(AtomicBool::new(false));
+    clamav.scan = Some(RunningScan { id, pid: child.id(), stopped: stopped.clone() });
+    drop(clamav);
+
+    let scanner = scanner.to_string();
+    std::thread::spawn(move || {
+        let total = if daemon { 0 } else { count_files(Path::new(&path)) };
+        let mut result = ScanResult { path, scanner, scanned: 0, detections: Vec::new(), cancelled: false, error: None };
+        let mut last_emit = Instant::now();
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let Some((file, signature)) = parse_line(&line) else { continue };
+            result.scanned += 1;
+            if let Some(sig) = signature {
+                result.detections.push(Detection { path: file.to_string(), signature: sig.to_string() });
+            }
+            if last_emit.elapsed() >= PROGRESS_EVERY || signature.is_some() {
+                let _ = app.emit_all("clamav://progress", ScanProgress {
+                    scanned: result.scanned, total, current: file.to_string(), detections: result.detections.len(),
+                });
+                last_emit = Instant::now();
+            }
+        }
+
+        // Cleared before reaping, so stop_clamav_scan can never signal a reused pid
+        let state = app.state::<AppState>();
+        {
+            let mut clamav = state.clamav.lock().unwrap();
+            if clamav.scan.as_ref().is_some_and(|s| s.id == id) {
+                clamav.scan = None;
+            }
+        }
+
+        // 0: clean, 1: something found, 2: errors such as unreadable files
+        match child.wait().map(|s| s.code()) {
+            _ if stopped.load(Ordering::SeqCst) => result.cancelled = true,
+            Ok(Some(0)) | Ok(Some(1)) => {}
+            Ok(Some(_)) => result.error = Some("Some files could not be scanned".to_string()),
+            Ok(None) => result.cancelled = true,
+            Err(e) => result.error = Some(e.to_string()),
+        }
+
+        state.clamav.lock().unwrap().last = Some(result.clone());
+        let _ = app.emit_all("clamav://done", result);
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_clamav_scan(state: State<AppState>) {
+    // The reader thread takes the scan out of the state before reaping the scanner, so while
+    // it's still here the pid can't have been reused
+    if let Some(scan) = state.clamav.lock().unwrap().scan.take() {
+        scan.stopped.store(true, Ordering::SeqCst);
+        unsafe { libc::kill(scan.pid as i32, libc::SIGKILL) };
+    }
+}
+
+/// Outcome of the last finished scan.
+#[tauri::command]
+pub fn get_clamav_result(state: State<AppState>) -> Option<ScanResult> {
+    state.clamav.lock().unwrap().last.clone()
+}
@@ -0,0 +1,307 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Manager};
+
+use crate::history::unix_now;
+
+const REPORT_LIMIT: usize = 100;
+const MAX_REPORT_LIMIT: usize = 1000;
+const PROGRESS_EVERY: u64 = 1000;
+// Bytes hashed in the cheap first pass over same-sized files
+const PARTIAL_HASH_BYTES: u64 = 16 * 1024;
+
+#[derive(Clone, serde::Serialize)]
+pub struct FileEntry {
+    path: String,
+    size: u64,
+    modified: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct JunkEntry {
+    path: String,
+    size: u64,
+    kind: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct LargeFileReport {
+    largest: Vec<FileEntry>,
+    oldest: Vec<FileEntry>,
+    junk: Vec<JunkEntry>,
+    scanned: u64,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ScanProgress {
+    scanned: u64,
+    current: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct DuplicateGroup {
+    size: u64,
+    paths: Vec<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct DuplicateProgress {
+    stage: String,
+    done: usize,
+    total: usize,
+}
+
+fn junk_dir_kind(name: &str) -> Option<&'static str> {
+    match name {
+        "node_modules" => Some("node_modules"),
+        "__pycache__" => Some("python cache"),
+        _ => None,
+    }
+}
+
+fn junk_file_kind(name: &str) -> Option<&'static str> {
+    if name == "core" || name.strip_prefix("core.").is_some_and(|pid| pid.chars().all(|c| c.is_ascii_digit())) {
+        return Some("core dump");
+    }
+    // Rotated logs: foo.log.1, foo.log.2.gz, foo.log.old
+    if let Some(idx) = name.find(".log.") {
+        let suffix = &name[idx + 5..];
+        if suffix.starts_with(|c: char| c.is_ascii_digit()) || suffix == "old" {
+            return Some("rotated log");
+        }
+    }
+    None
+}
+
+pub fn dir_size(path: &Path) -> u64 {
+    let mut total = 0;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            match entry.metadata() {
+                Ok(m) if m.is_dir() => stack.push(entry.path()),
+                Ok(m) => total += m.len(),
+                Err(_) => {}
+            }
+        }
+    }
+    total
+}
+
+fn scan(root: PathBuf, min_size: u64, age_days: u64, limit: usize, app: &AppHandle) -> LargeFileReport {
+    let cutoff = unix_now().saturating_sub(age_days.saturating_mul(86400));
+    let root_dev = fs::metadata(&root).map(|m| m.dev()).unwrap_or(0);
+    let mut files = Vec::new();
+    let mut junk = Vec::new();
+    let mut scanned = 0u64;
+    let mut stack = vec![root];
+
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            // symlink_metadata so links are never followed out of the tree
+            let Ok(meta) = entry.path().symlink_metadata() else { continue };
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+
+            if meta.is_dir() {
+                // Stay on one filesystem, like `du -x`
+                if meta.dev() != root_dev {
+                    continue;
+                }
+                match junk_dir_kind(&name) {
+                    Some(kind) => junk.push(JunkEntry {
+                        path: path.to_string_lossy().to_string(),
+                        size: dir_size(&path),
+                        kind: kind.to_string(),
+                    }),
+                    None => stack.push(path),
+                }
+                continue;
+            }
+            if !meta.is_file() {
+                continue;
+            }
+
+            scanned += 1;
+            if scanned.is_multiple_of(PROGRESS_EVERY) {
+                let _ = app.emit_all("cleanup://progress", ScanProgress {
+                    scanned,
+                    current: dir.to_string_lossy().to_string(),
+                });
+            }
+
+            let modified = meta.modified().ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            if let Some(kind) = junk_file_kind(&name) {
+                junk.push(JunkEntry { path: path.to_string_lossy().to_string(), size: meta.len(), kind: kind.to_string() });
+            }
+            if meta.len() >= min_size && modified <= cutoff {
+                files.push(FileEntry { path: path.to_string_lossy().to_string(), size: meta.len(), modified });
+            }
+        }
+    }
+
+    files.sort_by_key(|f| std::cmp::Reverse(f.size));
+    let largest = files.iter().take(limit).cloned().collect();
+    files.sort_by_key(|f| f.modified);
+    let oldest = files.into_iter().take(limit).collect();
+    junk.sort_by_key(|j| std::cmp::Reverse(j.size));
+
+    LargeFileReport { largest, oldest, junk, scanned }
+}
+
+/// 64-bit FNV-1a of the file's first `limit` bytes, or all of it. Unlike `DefaultHasher`, the
+/// value is fixed by the algorithm, so hashes stored by drift stay valid across Rust releases.
+pub fn hash_file(path: &Path, limit: Option<u64>) -> Option<u64> {
+    let file = File::open(path).ok()?;
+    let mut reader: Box<dyn Read> = match limit {
+        Some(n) => Box::new(BufReader::new(file).take(n)),
+        None => Box::new(BufReader::new(file)),
+    };
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        for &byte in &buf[..n] {
+            hash = (hash ^ byte as u64).wrapping_mul(0x100_0000_01b3);
+        }
+    }
+    Some(hash)
+}
+
+fn files_identical(a: &Path, b: &Path) -> std::io::Result<bool> {
+    let (mut fa, mut fb) = (BufReader::new(File::open(a)?), BufReader::new(File::open(b)?));
+    let (mut ba, mut bb) = (vec![0u8; 64 * 1024], vec![0u8; 64 * 1024]);
+    loop {
+        let n = fa.read(&mut ba)?;
+        if n == 0 {
+            return Ok(fb.read(&mut bb)? == 0);
+        }
+        // A short read on `b` means it is shorter, which also means different
+        if fb.read_exact(&mut bb[..n]).is_err() || ba[..n] != bb[..n] {
+            return Ok(false);
+        }
+    }
+}
+
+/// Keeps only groups whose members share `key`, dropping singletons.
+fn regroup(groups: Vec<Vec<PathBuf>>, stage: &str, app: &AppHandle, key: impl Fn(&Path) -> Option<u64>) -> Vec<Vec<PathBuf>> {
+    let total = groups.len();
+    let mut out = Vec::new();
+    for (done, group) in groups.into_iter().enumerate() {
+        let _ = app.emit_all("duplicates://progress", DuplicateProgress { stage: stage.to_string(), done, total });
+        let mut by_key: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for path in group {
+            if let Some(k) = key(&path) {
+                by_key.entry(k).or_default().push(path);
+            }
+        }
+        out.extend(by_key.into_values().filter(|g| g.len() > 1));
+    }
+    out
+}
+
+fn scan_duplicates(roots: Vec<PathBuf>, app: &AppHandle) -> Vec<DuplicateGroup> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    // Existing hardlinks share an inode and are not duplicates
+    let mut seen_inodes = HashSet::new();
+    let mut stack = roots;
+    let mut scanned = 0usize;
+
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let Ok(meta) = entry.path().symlink_metadata() else { continue };
+            if meta.is_dir() {
+                stack.push(entry.path());
+            } else if meta.is_file() && meta.len() > 0 && seen_inodes.insert((meta.dev(), meta.ino())) {
+                by_size.entry(meta.len()).or_default().push(entry.path());
+                scanned += 1;
+                if scanned.is_multiple_of(PROGRESS_EVERY as usize) {
+                    let _ = app.emit_all("duplicates://progress", DuplicateProgress { stage: "scan".to_string(), done: scanned, total: 0 });
+                }
+            }
+        }
+    }
+
+    let candidates: Vec<Vec<PathBuf>> = by_size.into_values().filter(|g| g.len() > 1).collect();
+    let candidates = regroup(candidates, "partial", app, |p| hash_file(p, Some(PARTIAL_HASH_BYTES)));
+    let groups = regroup(candidates, "full", app, |p| hash_file(p, None));
+
+    let mut groups: Vec<DuplicateGroup> = groups.into_iter().map(|paths| DuplicateGroup {
+        size: fs::metadata(&paths[0]).map(|m| m.len()).unwrap_or(0),
+        paths: paths.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+    }).collect();
+    // Most reclaimable space first
+    groups.sort_by_key(|g| std::cmp::Reverse(g.size * (g.paths.len() as u64 - 1)));
+    groups
+}
+
+// --- Commands ---
+
+/// Scans `path` for files of at least `min_size` bytes not modified in the last `age` days
+/// (any age by default), returning the `limit` largest and oldest. Progress is reported
+/// through `cleanup://progress` events.
+#[tauri::command]
+pub async fn find_large_files(path: String, min_size: u64, age: Option<u64>, limit: Option<usize>, app: AppHandle) -> Result<LargeFileReport, String> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", path));
+    }
+    let limit = limit.unwrap_or(REPORT_LIMIT).clamp(1, MAX_REPORT_LIMIT);
+    tauri::async_runtime::spawn_blocking(move || scan(root, min_size, age.unwrap_or(0), limit, &app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Finds files with identical content under `paths`, reporting `duplicates://progress` events.
+#[tauri::command]
+pub async fn find_duplicates(paths: Vec<String>, app: AppHandle) -> Result<Vec<DuplicateGroup>, String> {
+    let roots: Vec<PathBuf> = paths.iter().map(PathBuf::from).filter(|p| p.is_dir()).collect();
+    if roots.is_empty() {
+        return Err("No valid directories selected".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || scan_duplicates(roots, &app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces `duplicate` with a hardlink to `keep`, or deletes it. The two files are compared
+/// byte-for-byte first, so a stale scan result can never destroy distinct data.
+#[tauri::command]
+pub fn resolve_duplicate(keep: String, duplicate: String, action: String) -> Result<(), String> {
+    let (keep, duplicate) = (PathBuf::from(keep), PathBuf::from(duplicate));
+    let (km, dm) = (fs::metadata(&keep).map_err(|e| e.to_string())?, fs::metadata(&duplicate).map_err(|e| e.to_string())?);
+    if km.dev() == dm.dev() && km.ino() == dm.ino() {
+        return Err("Files are already the same inode".to_string());
+    }
+    if km.len() != dm.len() || !files_identical(&keep, &duplicate).map_err(|e| e.to_string())? {
+        return Err("Files are no longer identical".to_string());
+    }
+
+    match action.as_str() {
+        "delete" => fs::remove_file(&duplicate).map_err(|e| e.to_string()),
+        "hardlink" => {
+            if km.dev() != dm.dev() {
+                return Err("Cannot hardlink across filesystems".to_string());
+            }
+            // Link beside the duplicate, then rename over it so it is never missing
+            let name = duplicate.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let tmp = duplicate.with_file_name(format!(".{}.glassview-link", name));
+            fs::hard_link(&keep, &tmp).map_err(|e| e.to_string())?;
+            fs::rename(&tmp, &duplicate).map_err(|e| {
+                let _ = fs::remove_file(&tmp);
+                e.to_string()
+            })
+        }
+        other => Err(format!("Unknown action: {}", other)),
+    }
+}
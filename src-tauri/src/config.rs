@@ -0,0 +1,42 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn config_dir() -> PathBuf {
+    let base = std::env::var("XDG_CONFIG_HOME").ok()
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| format!("{}/.config", std::env::var("HOME").unwrap_or_default()));
+    PathBuf::from(base).join("glassview")
+}
+
+/// Reads a TOML file from the config dir, falling back to defaults if it is missing or invalid.
+pub fn load<T: DeserializeOwned + Default>(file: &str) -> T {
+    fs::read_to_string(config_dir().join(file)).ok()
+        .and_then(|s| toml::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save<T: Serialize>(file: &str, value: &T) -> Result<(), String> {
+    let dir = config_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let body = toml::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(dir.join(file), body).map_err(|e| e.to_string())
+}
+
+/// A fresh directory only we can enter, for scratch files handed to other programs. mkdtemp
+/// creates it 0700 and fails rather than reuse an existing path, so nobody sharing /tmp can
+/// plant symlinks in it. Under $XDG_RUNTIME_DIR when there is one.
+pub fn private_temp_dir(prefix: &str) -> Result<PathBuf, String> {
+    let base = std::env::var("XDG_RUNTIME_DIR").ok()
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    let template = format!("{}/{}-XXXXXX", base.display(), prefix);
+    let mut template = std::ffi::CString::new(template).map_err(|e| e.to_string())?.into_bytes_with_nul();
+    if unsafe { libc::mkdtemp(template.as_mut_ptr().cast()) }.is_null() {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    template.pop();
+    String::from_utf8(template).map(PathBuf::from).map_err(|e| e.to_string())
+}
@@ -0,0 +1,134 @@
+use std::process::Command;
+
+use crate::{eol, journal};
+
+#[derive(serde::Serialize)]
+pub struct Coredump {
+    /// Microseconds since the Unix epoch
+    time: u64,
+    pid: u32,
+    uid: u32,
+    signal: String,
+    exe: String,
+    /// present, missing, none, ..., as reported by coredumpctl
+    corefile: String,
+    size: Option<u64>,
+}
+
+#[derive(serde::Serialize)]
+pub struct CoredumpInfo {
+    /// "Key: value" header lines, in order
+    fields: Vec<(String, String)>,
+    /// The message with its stack trace
+    message: Vec<String>,
+}
+
+fn signal_name(sig: i64) -> String {
+    match sig {
+        4 => "SIGILL".to_string(),
+        5 => "SIGTRAP".to_string(),
+        6 => "SIGABRT".to_string(),
+        7 => "SIGBUS".to_string(),
+        8 => "SIGFPE".to_string(),
+        11 => "SIGSEGV".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn from_json(v: &serde_json::Value) -> Option<Coredump> {
+    Some(Coredump {
+        time: v.get("time")?.as_u64()?,
+        pid: v.get("pid")?.as_u64()? as u32,
+        uid: v.get("uid").and_then(|u| u.as_u64()).unwrap_or(0) as u32,
+        signal: signal_name(v.get("sig").and_then(|s| s.as_i64()).unwrap_or(0)),
+        exe: v.get("exe").and_then(|e| e.as_str()).unwrap_or_default().to_string(),
+        corefile: v.get("corefile").and_then(|c| c.as_str()).unwrap_or_default().to_string(),
+        size: v.get("size").and_then(|s| s.as_u64()),
+    })
+}
+
+// Before --json existed: "Mon 2024-05-01 10:00:00 UTC 1234 1000 1000 SIGSEGV present /usr/bin/foo 1.2M".
+// COREFILE and SIZE are missing on older systemd.
+fn from_line(line: &str) -> Option<Coredump> {
+    let f: Vec<&str> = line.split_whitespace().collect();
+    if f.len() < 9 {
+        return None;
+    }
+    let (h, m, s) = {
+        let mut t = f[2].split(':').map(|p| p.parse::<u64>());
+        (t.next()?.ok()?, t.next()?.ok()?, t.next()?.ok()?)
+    };
+    let secs = eol::days_from_civil(f[1])? as u64 * 86400 + h * 3600 + m * 60 + s;
+    let has_corefile = f.len() >= 10;
+    Some(Coredump {
+        time: secs * 1_000_000,
+        pid: f[4].parse().ok()?,
+        uid: f[5].parse().ok()?,
+        signal: f[7].to_string(),
+        corefile: if has_corefile { f[8].to_string() } else { String::new() },
+        exe: f[if has_corefile { 9 } else { 8 }].to_string(),
+        size: f.get(10).and_then(|s| journal::parse_size(s)),
+    })
+}
+
+// --- Commands ---
+
+/// Recorded crashes, newest first.
+#[tauri::command]
+pub async fn get_coredumps() -> Result<Vec<Coredump>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let output = Command::new("coredumpctl").args(["list", "--json=short", "--no-pager", "--reverse"])
+            .output().map_err(|_| "coredumpctl is not installed".to_string())?;
+        if output.status.success() {
+            let list: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap_or_default();
+            return Ok(list.iter().filter_map(from_json).collect());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // "No coredumps found." is reported as a failure
+        if stderr.contains("No coredumps") {
+            return Ok(Vec::new());
+        }
+
+        // Older coredumpctl without --json; UTC so the times can be parsed
+        let output = Command::new("coredumpctl").args(["list", "--no-pager", "--no-legend", "--reverse"])
+            .env("TZ", "UTC")
+            .output().map_err(|e| e.to_string())?;
+        Ok(String::from_utf8_lossy(&output.stdout).lines().filter_map(from_line).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// `coredumpctl info` for the most recent crash of `pid`, including the stack trace if one was captured.
+#[tauri::command]
+pub async fn get_coredump_info(pid: u32) -> Result<CoredumpInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = Command::new("coredumpctl").args(["info", "--no-pager"]).arg(pid.to_string())
+            .output().map_err(|_| "coredumpctl is not installed".to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut info = CoredumpInfo { fields: Vec::new(), message: Vec::new() };
+        for line in text.lines() {
+            // Everything from the message on is free text
+            if info.message.is_empty() {
+                if let Some((key, value)) = line.split_once(": ") {
+                    if key.trim() == "Message" {
+                        info.message.push(value.to_string());
+                    } else {
+                        info.fields.push((key.trim().to_string(), value.trim().to_string()));
+                    }
+                    continue;
+                }
+            }
+            if !info.message.is_empty() {
+                info.message.push(line.trim().to_string());
+            }
+        }
+        Ok(info)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{ProcessExt, System, SystemExt};
+use tauri::{AppHandle, Manager, State};
+
+use crate::history::unix_now;
+use crate::services::{self, Scope};
+use crate::{config, startup, AppState};
+
+const DEBLOAT_FILE: &str = "debloat.toml";
+
+#[derive(Clone, Copy)]
+enum Target {
+    Service(&'static str),
+    UserService(&'static str),
+    /// XDG autostart entry, by .desktop file name without the extension
+    Autostart(&'static str),
+}
+
+/// Extra condition a suggestion needs to be safe, beyond the target being active.
+#[derive(Clone, Copy)]
+enum Condition {
+    Always,
+    NoPrinters,
+    NoBluetooth,
+    NoModem,
+}
+
+struct Rule {
+    id: &'static str,
+    title: &'static str,
+    reason: &'static str,
+    target: Target,
+    condition: Condition,
+    /// Process names whose CPU and memory use is what disabling saves
+    processes: &'static [&'static str],
+}
+
+const RULES: &[Rule] = &[
+    Rule { id: "tracker-miners", title: "Tracker file indexer",
+        reason: "Heavy indexing I/O and memory; only needed for GNOME's full-text file search",
+        target: Target::UserService("tracker-miner-fs-3.service"), condition: Condition::Always,
+        processes: &["tracker-miner-f", "tracker-extract"] },
+    Rule { id: "baloo", title: "Baloo file indexer",
+        reason: "KDE's file indexer; only needed for content search in Dolphin and KRunner",
+        target: Target::Autostart("baloo_file"), condition: Condition::Always,
+        processes: &["baloo_file", "baloo_file_extr"] },
+    Rule { id: "evolution-alarm", title: "Evolution calendar reminders",
+        reason: "Keeps Evolution's data server running for calendar alarms nobody set",
+        target: Target::Autostart("org.gnome.Evolution-alarm-notify"), condition: Condition::Always,
+        processes: &["evolution-alarm", "evolution-sourc", "evolution-calen"] },
+    Rule { id: "cups", title: "CUPS print server",
+        reason: "No printers are configured",
+        target: Target::Service("cups.service"), condition: Condition::NoPrinters,
+        processes: &["cupsd"] },
+    Rule { id: "cups-browsed", title: "CUPS network printer discovery",
+        reason: "No printers are configured; it also polls the network for new ones",
+        target: Target::Service("cups-browsed.service"), condition: Condition::NoPrinters,
+        processes: &["cups-browsed"] },
+    Rule { id: "bluetooth", title: "Bluetooth daemon",
+        reason: "This machine has no Bluetooth adapter",
+        target: Target::Service("bluetooth.service"), condition: Condition::NoBluetooth,
+        processes: &["bluetoothd"] },
+    Rule { id: "modemmanager", title: "ModemManager",
+        reason: "No mobile broadband modem is present, yet it probes serial devices",
+        target: Target::Service("ModemManager.service"), condition: Condition::NoModem,
+        processes: &["ModemManager"] },
+    Rule { id: "whoopsie", title: "Ubuntu error reporter",
+        reason: "Uploads crash reports to Canonical; crashes are still recorded locally",
+        target: Target::Service("whoopsie.service"), condition: Condition::Always,
+        processes: &["whoopsie"] },
+    Rule { id: "kerneloops", title: "Kernel oops reporter",
+        reason: "Uploads kernel oops reports; they remain in the kernel log",
+        target: Target::Service("kerneloops.service"), condition: Condition::Always,
+        processes: &["kerneloops"] },
+];
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct AppliedFix {
+    id: String,
+    time: u64,
+    /// State to restore on undo
+    was_enabled: bool,
+    was_active: bool,
+    /// Autostart fixes: the Hidden=true override we wrote, or the user entry we disabled in
+    /// place (older versions renamed it to .desktop.bak)
+    override_path: Option<String>,
+    renamed: bool,
+    /// Usage measured right before disabling
+    mem_before: u64,
+    cpu_before: f32,
+    /// Socket and path units that would start the service again, masked alongside it
+    #[serde(default)]
+    companions: Vec<CompanionUnit>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct CompanionUnit {
+    unit: String,
+    was_enabled: bool,
+    was_active: bool,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct DebloatFile {
+    applied: Vec<AppliedFix>,
+}
+
+#[derive(serde::Serialize)]
+pub struct Savings {
+    since: u64,
+    mem_bytes: u64,
+    cpu_percent: f32,
+}
+
+#[derive(serde::Serialize)]
+pub struct Advice {
+    id: String,
+    title: String,
+    reason: String,
+    /// service, user_service or autostart
+    kind: String,
+    target: String,
+    applied: bool,
+    /// Current usage of the rule's processes
+    mem_bytes: u64,
+    cpu_percent: f32,
+    /// Measured before/after difference, once applied
+    savings: Option<Savings>,
+}
+
+fn condition_holds(c: Condition) -> bool {
+    match c {
+        Condition::Always => true,
+        Condition::NoPrinters => !fs::read_to_string("/etc/cups/printers.conf").unwrap_or_default().contains("<Printer"),
+        Condition::NoBluetooth => fs::read_dir("/sys/class/bluetooth").map(|d| d.count() == 0).unwrap_or(true),
+        Condition::NoModem => {
+            // Modems show up as QMI/MBIM control devices or USB serial ports
+            !fs::read_dir("/dev").into_iter().flatten().flatten().any(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                name.starts_with("cdc-wdm") || name.starts_with("ttyUSB")
+            })
+        }
+    }
+}
+
+fn user_autostart(name: &str) -> PathBuf {
+    PathBuf::from(format!("{}/.config/autostart/{}.desktop", std::env::var("HOME").unwrap_or_default(), name))
+}
+
+fn system_autostart(name: &str) -> PathBuf {
+    PathBuf::from(format!("/etc/xdg/autostart/{}.desktop", name))
+}
+
+fn is_hidden(path: &Path) -> bool {
+    fs::read_to_string(path).unwrap_or_default().lines().any(|l| l.trim() == "Hidden=true")
+}
+
+type UnitStates = HashMap<String, (String, bool)>;
+
+// The states of every rule's services, one connection per bus rather than one per rule
+fn rule_unit_states() -> (UnitStates, UnitStates) {
+    let units = |user: bool| -> Vec<&str> {
+        RULES.iter().filter_map(|r| match r.target {
+            Target::Service(u) if !user => Some(u),
+            Target::UserService(u) if user => Some(u),
+            _ => None,
+        }).collect()
+    };
+    (services::unit_states(&units(false), Scope::System), services::unit_states(&units(true), Scope::User))
+}
+
+/// Whether the target is installed and currently set to run.
+fn target_enabled(target: Target, system: &UnitStates, user: &UnitStates) -> Option<(bool, bool)> {
+    match target {
+        Target::Service(unit) | Target::UserService(unit) => {
+            let states = if matches!(target, Target::UserService(_)) { user } else { system };
+            let (file_state, active) = states.get(unit)?;
+            Some((file_state == "enabled", *active))
+        }
+        Target::Autostart(name) => {
+            // A user entry overrides the system-wide one of the same name
+            let user = user_autostart(name);
+            let path = if user.exists() { user } else { system_autostart(name) };
+            path.exists().then(|| !is_hidden(&path)).map(|on| (on, on))
+        }
+    }
+}
+
+// cups.socket and cups.path start cups.service on demand, so disabling the service alone
+// lasts until the next print job or boot
+fn installed_companions(unit: &str, scope: Scope) -> Vec<CompanionUnit> {
+    let Some(base) = unit.strip_suffix(".service") else { return Vec::new() };
+    let names = [format!("{}.socket", base), format!("{}.path", base)];
+    let states = services::unit_states(&names.iter().map(String::as_str).collect::<Vec<_>>(), scope);
+    names.into_iter().filter_map(|name| {
+        let (file_state, active) = states.get(&name)?.clone();
+        Some(CompanionUnit { was_enabled: file_state == "enabled", was_active: active, unit: name })
+    }).collect()
+}
+
+fn usage(sys: &System, names: &[&str]) -> (u64, f32) {
+    sys.processes().values()
+        .filter(|p| names.contains(&p.name()))
+        .fold((0, 0.0), |(m, c), p| (m + p.memory(), c + p.cpu_usage()))
+}
+
+fn mask_companions(companions: &[CompanionUnit], scope: Scope) -> Result<(), String> {
+    for c in companions {
+        services::unit_action(&c.unit, "stop", scope)?;
+        services::mask_unit(&c.unit, true, scope)?;
+    }
+    Ok(())
+}
+
+fn restore_companions(companions: &[CompanionUnit], scope: Scope) -> Result<(), String> {
+    for c in companions {
+        services::mask_unit(&c.unit, false, scope)?;
+        if c.was_enabled {
+            services::enable_unit(&c.unit, true, scope)?;
+        }
+        if c.was_active {
+            services::unit_action(&c.unit, "start", scope)?;
+        }
+    }
+    Ok(())
+}
+
+fn set_service(unit: &str, scope: Scope, enabled: bool, active: bool) -> Result<(), String> {
+    services::enable_unit(unit, enabled, scope)?;
+    services::unit_action(unit, if active { "start" } else { "stop" }, scope)
+}
+
+fn apply(id: String, state: &AppState) -> Result<(), String> {
+    let rule = RULES.iter().find(|r| r.id == id).ok_or_else(|| format!("Unknown suggestion: {}", id))?;
+    let mut file: DebloatFile = config::load(DEBLOAT_FILE);
+    if file.applied.iter().any(|f| f.id == id) {
+        return Ok(());
+    }
+    let (system, user) = rule_unit_states();
+    let (was_enabled, was_active) = target_enabled(rule.target, &system, &user).ok_or("Not installed")?;
+    let (mem_before, cpu_before) = usage(&state.sys.lock().unwrap(), rule.processes);
+
+    let (mut override_path, mut renamed, mut companions) = (None, false, Vec::new());
+    match rule.target {
+        Target::Service(unit) | Target::UserService(unit) => {
+            let scope = if matches!(rule.target, Target::UserService(_)) { Scope::User } else { Scope::System };
+            // Sockets first, so stopping them can't have them start the service back up
+            companions = installed_companions(unit, scope);
+            mask_companions(&companions, scope)?;
+            set_service(unit, scope, false, false)?;
+        }
+        Target::Autostart(name) => {
+            let user = user_autostart(name);
+            if user.exists() {
+                // The user's own entry is switched off the same way the startup panel does it
+                startup::set_enabled(&user, false)?;
+                renamed = true;
+            } else {
+                if let Some(dir) = user.parent() {
+                    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+                }
+                fs::write(&user, "[Desktop Entry]\nType=Application\nHidden=true\n").map_err(|e| e.to_string())?;
+                override_path = Some(user.to_string_lossy().to_string());
+            }
+            // Autostart entries only stop at logout, so stop the running copy now
+            for p in state.sys.lock().unwrap().processes().values().filter(|p| rule.processes.contains(&p.name())) {
+                p.kill();
+            }
+        }
+    }
+
+    file.applied.push(AppliedFix {
+        id, time: unix_now(), was_enabled, was_active, override_path, renamed, mem_before, cpu_before, companions,
+    });
+    config::save(DEBLOAT_FILE, &file)
+}
+
+fn undo(id: String) -> Result<(), String> {
+    let rule = RULES.iter().find(|r| r.id == id).ok_or_else(|| format!("Unknown suggestion: {}", id))?;
+    let mut file: DebloatFile = config::load(DEBLOAT_FILE);
+    let Some(pos) = file.applied.iter().position(|f| f.id == id) else { return Ok(()) };
+    let fix = file.applied[pos].clone();
+
+    match rule.target {
+        Target::Service(unit) | Target::UserService(unit) => {
+            let scope = if matches!(rule.target, Target::UserService(_)) { Scope::User } else { Scope::System };
+            set_service(unit, scope, fix.was_enabled, fix.was_active)?;
+            restore_companions(&fix.companions, scope)?;
+        }
+        Target::Autostart(name) => {
+            let user = user_autostart(name);
+            if fix.renamed {
+                let legacy = user.with_extension("desktop.bak");
+                if legacy.exists() {
+                    fs::rename(&legacy, &user).map_err(|e| e.to_string())?;
+                }
+                startup::set_enabled(&user, true)?;
+            } else if let Some(path) = &fix.override_path {
+                // Only remove the file if it is still our override
+                if is_hidden(Path::new(path)) {
+                    fs::remove_file(path).map_err(|e| e.to_string())?;
+                }
+            }
+        }
+    }
+
+    file.applied.remove(pos);
+    config::save(DEBLOAT_FILE, &file)
+}
+
+// --- Commands ---
+
+/// Suggestions whose target is installed and running and whose condition holds, plus the fixes
+/// already applied with the savings measured since.
+#[tauri::command]
+pub fn get_debloat_advice(state: State<AppState>) -> Vec<Advice> {
+    let applied: DebloatFile = config::load(DEBLOAT_FILE);
+    // Measured up front, so `sys` is not held across the D-Bus calls below
+    let usages: Vec<(u64, f32)> = {
+        let sys = state.sys.lock().unwrap();
+        RULES.iter().map(|r| usage(&sys, r.processes)).collect()
+    };
+    let (system, user) = rule_unit_states();
+
+    RULES.iter().zip(usages).filter_map(|(rule, (mem_bytes, cpu_percent))| {
+        let fix = applied.applied.iter().find(|f| f.id == rule.id);
+        if fix.is_none() {
+            let (enabled, active) = target_enabled(rule.target, &system, &user)?;
+            if !(enabled || active) || !condition_holds(rule.condition) {
+                return None;
+            }
+        }
+        let (kind, target) = match rule.target {
+            Target::Service(u) => ("service", u),
+            Target::UserService(u) => ("user_service", u),
+            Target::Autostart(n) => ("autostart", n),
+        };
+        Some(Advice {
+            id: rule.id.to_string(),
+            title: rule.title.to_string(),
+            reason: rule.reason.to_string(),
+            kind: kind.to_string(),
+            target: target.to_string(),
+            applied: fix.is_some(),
+            mem_bytes,
+            cpu_percent,
+            savings: fix.map(|f| Savings {
+                since: f.time,
+                mem_bytes: f.mem_before.saturating_sub(mem_bytes),
+                cpu_percent: (f.cpu_before - cpu_percent).max(0.0),
+            }),
+        })
+    }).collect()
+}
+
+/// Disables the suggestion's target, remembering how to undo it.
+#[tauri::command]
+pub async fn apply_debloat(id: String, app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || apply(id, &app.state::<AppState>()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Restores the target to how it was before `apply_debloat`.
+#[tauri::command]
+pub async fn undo_debloat(id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || undo(id))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,184 @@
+use std::io::{BufRead, BufReader};
+use std::net::IpAddr;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Manager};
+
+use crate::network::valid_hostname;
+
+const MAX_PINGS: u32 = 100;
+const MAX_HOPS: &str = "30";
+
+#[derive(Clone, serde::Serialize)]
+pub struct PingReply {
+    seq: u32,
+    /// None when the reply didn't arrive in time
+    time_ms: Option<f64>,
+    ttl: Option<u32>,
+}
+
+#[derive(serde::Serialize)]
+pub struct PingSummary {
+    host: String,
+    sent: u32,
+    received: u32,
+    loss_percent: f64,
+    min_ms: Option<f64>,
+    avg_ms: Option<f64>,
+    max_ms: Option<f64>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct TraceHop {
+    hop: u32,
+    /// None when the router didn't answer
+    address: Option<String>,
+    /// One entry per probe; None for probes that timed out
+    times_ms: Vec<Option<f64>>,
+}
+
+fn valid_target(host: &str) -> bool {
+    host.parse::<IpAddr>().is_ok() || (valid_hostname(host) && !host.starts_with('-'))
+}
+
+// "key=value" field of a ping reply, e.g. "time=12.3"
+fn ping_field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.split_whitespace().find_map(|w| w.strip_prefix(key)?.strip_prefix('='))
+}
+
+// "64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=12.3 ms", or with -O
+// "no answer yet for icmp_seq=2"
+fn parse_ping(line: &str) -> Option<PingReply> {
+    let seq = ping_field(line, "icmp_seq")?.parse().ok()?;
+    Some(PingReply {
+        seq,
+        time_ms: ping_field(line, "time").and_then(|t| t.parse().ok()),
+        ttl: ping_field(line, "ttl").and_then(|t| t.parse().ok()),
+    })
+}
+
+// " 3  10.0.0.1  8.123 ms  7.990 ms *" from traceroute -n
+fn parse_traceroute(line: &str) -> Option<TraceHop> {
+    let mut words = line.split_whitespace().peekable();
+    let hop = words.next()?.parse().ok()?;
+    let mut address = None;
+    let mut times_ms = Vec::new();
+    while let Some(w) = words.next() {
+        if w == "*" {
+            times_ms.push(None);
+        } else if words.peek() == Some(&"ms") {
+            times_ms.push(w.parse().ok());
+            words.next();
+        } else if address.is_none() && w.parse::<IpAddr>().is_ok() {
+            // Later probes can be answered by other routers; the first is shown
+            address = Some(w.to_string());
+        }
+    }
+    Some(TraceHop { hop, address, times_ms })
+}
+
+// " 3:  10.0.0.1   8.123ms" or " 4:  no reply" from tracepath -n; "1?:" lines are MTU probes
+fn parse_tracepath(line: &str) -> Option<TraceHop> {
+    let (hop, rest) = line.trim().split_once(": ")?;
+    let hop = hop.parse().ok()?;
+    let mut words = rest.split_whitespace();
+    let first = words.next()?;
+    if first == "no" {
+        return Some(TraceHop { hop, address: None, times_ms: vec![None] });
+    }
+    let time = words.find_map(|w| w.strip_suffix("ms")?.parse().ok());
+    Some(TraceHop { hop, address: first.parse::<IpAddr>().is_ok().then(|| first.to_string()), times_ms: vec![time] })
+}
+
+fn ping(app: &AppHandle, host: &str, count: u32) -> Result<PingSummary, String> {
+    let mut child = Command::new("ping")
+        .args(["-n", "-O", "-c", &count.to_string(), host])
+        .env("LC_ALL", "C")
+        .stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn().map_err(|_| "ping is not installed".to_string())?;
+    let stdout = child.stdout.take().ok_or("No ping output")?;
+    let mut times: Vec<f64> = Vec::new();
+    let mut lines = 0;
+    for reply in BufReader::new(stdout).lines().map_while(Result::ok).filter_map(|l| parse_ping(&l)) {
+        lines += 1;
+        times.extend(reply.time_ms);
+        let _ = app.emit_all("ping://reply", reply);
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    // Exit 1 just means some pings went unanswered
+    if lines == 0 && output.status.code() != Some(1) {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    // -O reports a lost ping when its successor is sent, so the last one can go unreported
+    let sent = count;
+    let received = (times.len() as u32).min(sent);
+    Ok(PingSummary {
+        host: host.to_string(),
+        sent,
+        received,
+        loss_percent: (sent - received) as f64 / sent as f64 * 100.0,
+        min_ms: times.iter().copied().reduce(f64::min),
+        avg_ms: (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64),
+        max_ms: times.iter().copied().reduce(f64::max),
+    })
+}
+
+// traceroute is often missing where iputils' tracepath is not
+fn trace(app: &AppHandle, host: &str) -> Result<Vec<TraceHop>, String> {
+    let (mut cmd, tracepath) = match Command::new("traceroute").arg("--version").output() {
+        Ok(_) => {
+            let mut c = Command::new("traceroute");
+            c.args(["-n", "-q", "3", "-w", "2", "-m", MAX_HOPS, host]);
+            (c, false)
+        }
+        Err(_) => {
+            let mut c = Command::new("tracepath");
+            c.args(["-n", "-m", MAX_HOPS, host]);
+            (c, true)
+        }
+    };
+    let mut child = cmd.env("LC_ALL", "C").stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn().map_err(|_| "Neither traceroute nor tracepath is installed".to_string())?;
+    let stdout = child.stdout.take().ok_or("No traceroute output")?;
+    let mut hops: Vec<TraceHop> = Vec::new();
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        let Some(hop) = (if tracepath { parse_tracepath(&line) } else { parse_traceroute(&line) }) else { continue };
+        // tracepath prints a hop again when its MTU changes
+        if hops.last().is_some_and(|h| h.hop == hop.hop) {
+            continue;
+        }
+        let _ = app.emit_all("traceroute://hop", hop.clone());
+        hops.push(hop);
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if hops.is_empty() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(hops)
+}
+
+// --- Commands ---
+
+/// Pings `host` `count` times, emitting `ping://reply` for each reply or timeout.
+#[tauri::command]
+pub async fn ping_host(app: AppHandle, host: String, count: Option<u32>) -> Result<PingSummary, String> {
+    let host = host.trim().to_string();
+    if !valid_target(&host) {
+        return Err(format!("Invalid host: {}", host));
+    }
+    let count = count.unwrap_or(4).clamp(1, MAX_PINGS);
+    tauri::async_runtime::spawn_blocking(move || ping(&app, &host, count))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Traces the route to `host`, emitting `traceroute://hop` as each hop answers.
+#[tauri::command]
+pub async fn trace_route(app: AppHandle, host: String) -> Result<Vec<TraceHop>, String> {
+    let host = host.trim().to_string();
+    if !valid_target(&host) {
+        return Err(format!("Invalid host: {}", host));
+    }
+    tauri::async_runtime::spawn_blocking(move || trace(&app, &host))
+        .await
+        .map_err(|e| e.to_string())?
+}
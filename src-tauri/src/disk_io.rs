@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const SAMPLE_WINDOW: Duration = Duration::from_secs(1);
+const REPORT_LIMIT: usize = 30;
+
+#[derive(Clone, Copy, Default)]
+struct IoCounters {
+    rbytes: u64,
+    wbytes: u64,
+    rios: u64,
+    wios: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct CgroupIo {
+    /// Path relative to the cgroup root, e.g. "system.slice/docker.service"
+    cgroup: String,
+    /// service, container, vm, session or other
+    kind: String,
+    read_bps: f64,
+    write_bps: f64,
+    read_iops: f64,
+    write_iops: f64,
+}
+
+fn classify(cgroup: &str) -> &'static str {
+    let leaf = cgroup.rsplit('/').next().unwrap_or(cgroup);
+    if leaf.starts_with("docker-") || leaf.starts_with("libpod-") || leaf.starts_with("cri-containerd-") || cgroup.contains("/docker/") {
+        "container"
+    } else if cgroup.starts_with("machine.slice/") {
+        "vm"
+    } else if leaf.ends_with(".service") {
+        "service"
+    } else if leaf.starts_with("session-") {
+        "session"
+    } else {
+        "other"
+    }
+}
+
+// "8:0 rbytes=1 wbytes=2 rios=3 wios=4 dbytes=0 dios=0", one line per device
+fn read_io_stat(path: &Path) -> Option<IoCounters> {
+    let stat = fs::read_to_string(path.join("io.stat")).ok()?;
+    let mut total = IoCounters::default();
+    for (key, value) in stat.split_whitespace().filter_map(|f| f.split_once('=')) {
+        let value: u64 = value.parse().unwrap_or(0);
+        match key {
+            "rbytes" => total.rbytes += value,
+            "wbytes" => total.wbytes += value,
+            "rios" => total.rios += value,
+            "wios" => total.wios += value,
+            _ => {}
+        }
+    }
+    Some(total)
+}
+
+// Parents already include their children in io.stat, so only leaves are counted
+fn leaf_cgroups() -> Vec<PathBuf> {
+    let mut leaves = Vec::new();
+    let mut stack = vec![PathBuf::from(CGROUP_ROOT)];
+    while let Some(dir) = stack.pop() {
+        let children: Vec<PathBuf> = fs::read_dir(&dir).into_iter().flatten().flatten()
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+            .map(|e| e.path())
+            .collect();
+        if children.is_empty() {
+            leaves.push(dir);
+        } else {
+            stack.extend(children);
+        }
+    }
+    leaves
+}
+
+fn snapshot(cgroups: &[PathBuf]) -> HashMap<PathBuf, IoCounters> {
+    cgroups.iter().filter_map(|c| Some((c.clone(), read_io_stat(c)?))).collect()
+}
+
+fn sample() -> Result<Vec<CgroupIo>, String> {
+    if !Path::new(CGROUP_ROOT).join("cgroup.controllers").exists() {
+        return Err("cgroup v2 is not mounted at /sys/fs/cgroup".to_string());
+    }
+    let cgroups = leaf_cgroups();
+    let before = snapshot(&cgroups);
+    let start = Instant::now();
+    std::thread::sleep(SAMPLE_WINDOW);
+    let after = snapshot(&cgroups);
+    let secs = start.elapsed().as_secs_f64();
+
+    let mut usage: Vec<CgroupIo> = after.into_iter()
+        .filter_map(|(path, now)| {
+            let was = before.get(&path)?;
+            let rate = |a: u64, b: u64| a.saturating_sub(b) as f64 / secs;
+            let cgroup = path.strip_prefix(CGROUP_ROOT).ok()?.to_string_lossy().to_string();
+            Some(CgroupIo {
+                kind: classify(&cgroup).to_string(),
+                cgroup,
+                read_bps: rate(now.rbytes, was.rbytes),
+                write_bps: rate(now.wbytes, was.wbytes),
+                read_iops: rate(now.rios, was.rios),
+                write_iops: rate(now.wios, was.wios),
+            })
+        })
+        .filter(|c| c.read_bps + c.write_bps > 0.0)
+        .collect();
+    usage.sort_by(|a, b| (b.read_bps + b.write_bps).total_cmp(&(a.read_bps + a.write_bps)));
+    usage.truncate(REPORT_LIMIT);
+    Ok(usage)
+}
+
+// --- Commands ---
+
+/// Disk throughput per cgroup over a one second window, busiest first.
+#[tauri::command]
+pub async fn get_cgroup_io() -> Result<Vec<CgroupIo>, String> {
+    tauri::async_runtime::spawn_blocking(sample)
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,276 @@
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+const PROGRESS_EVERY: Duration = Duration::from_millis(250);
+const DEFAULT_DEPTH: u32 = 3;
+const MAX_DEPTH: u32 = 10;
+// Per directory in the returned tree; the rest still count towards the parent's size
+const MAX_CHILDREN: usize = 100;
+// Directories this close to the root go through the shared queue, so one huge subtree is
+// split between walkers; deeper ones are walked serially by whoever reaches them
+const SHARED_LEVELS: u32 = 4;
+
+#[derive(serde::Serialize)]
+pub struct DirUsage {
+    name: String,
+    path: String,
+    /// Allocated on disk, like `du`; sparse files count for what they use
+    size: u64,
+    /// Files anywhere below
+    files: u64,
+    /// Subdirectories, largest first, down to the requested depth
+    children: Vec<DirUsage>,
+    /// Subdirectories left out of `children` past `MAX_CHILDREN`
+    omitted: usize,
+}
+
+#[derive(serde::Serialize)]
+pub struct DiskUsage {
+    tree: DirUsage,
+    /// Directories that couldn't be read, usually for lack of permission
+    unreadable: u64,
+    elapsed_ms: u64,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct UsageProgress {
+    files: u64,
+    bytes: u64,
+    current: String,
+}
+
+impl DirUsage {
+    fn new(path: &Path) -> Self {
+        DirUsage {
+            name: path.file_name().map_or_else(|| path.to_string_lossy().to_string(), |n| n.to_string_lossy().to_string()),
+            path: path.to_string_lossy().to_string(),
+            size: 0,
+            files: 0,
+            children: Vec::new(),
+            omitted: 0,
+        }
+    }
+}
+
+// A directory waiting in the shared queue
+struct Job {
+    path: PathBuf,
+    /// Levels below the root
+    level: u32,
+    /// Index of the parent in `nodes`
+    parent: usize,
+}
+
+struct Queue {
+    jobs: Vec<Job>,
+    /// Jobs taken and not yet finished, which may still queue more
+    busy: usize,
+}
+
+// A directory that went through the queue; its children are attached once every walker is done
+struct Node {
+    usage: DirUsage,
+    parent: Option<usize>,
+    depth: u32,
+}
+
+// Shared by the walker threads
+struct Walk {
+    root_dev: u64,
+    files: AtomicU64,
+    bytes: AtomicU64,
+    unreadable: AtomicU64,
+    current: Mutex<String>,
+    /// Hard-linked files by (device, inode), so each counts once
+    linked: Mutex<HashSet<(u64, u64)>>,
+}
+
+impl Walk {
+    fn file(&self, meta: &fs::Metadata) -> u64 {
+        if meta.nlink() > 1 && !self.linked.lock().unwrap().insert((meta.dev(), meta.ino())) {
+            return 0;
+        }
+        let size = meta.blocks() * 512;
+        self.files.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(size, Ordering::Relaxed);
+        size
+    }
+
+    fn show(&self, path: &Path) {
+        if let Ok(mut current) = self.current.try_lock() {
+            *current = path.to_string_lossy().to_string();
+        }
+    }
+
+    // The files directly in `path`, and the subdirectories on the same filesystem
+    fn entries(&self, path: &Path, usage: &mut DirUsage) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(path) else {
+            self.unreadable.fetch_add(1, Ordering::Relaxed);
+            return Vec::new();
+        };
+        let mut dirs = Vec::new();
+        for entry in entries.flatten() {
+            // symlink_metadata so links are never followed out of the tree
+            let Ok(meta) = entry.path().symlink_metadata() else { continue };
+            if meta.is_dir() {
+                // Stay on one filesystem, like `du -x`, so /proc and other mounts stay out
+                if meta.dev() == self.root_dev {
+                    usage.size += meta.blocks() * 512;
+                    dirs.push(entry.path());
+                }
+            } else {
+                usage.size += self.file(&meta);
+                usage.files += 1;
+            }
+        }
+        dirs
+    }
+
+    fn dir(&self, path: &Path, depth: u32) -> DirUsage {
+        self.show(path);
+        let mut usage = DirUsage::new(path);
+        for dir in self.entries(path, &mut usage) {
+            let child = self.dir(&dir, depth.saturating_sub(1));
+            usage.size += child.size;
+            usage.files += child.files;
+            usage.children.push(child);
+        }
+        prune(&mut usage, depth);
+        usage
+    }
+}
+
+fn prune(usage: &mut DirUsage, depth: u32) {
+    if depth == 0 {
+        usage.omitted = 0;
+        usage.children.clear();
+        return;
+    }
+    usage.children.sort_by_key(|c| std::cmp::Reverse(c.size));
+    usage.omitted = usage.children.len().saturating_sub(MAX_CHILDREN);
+    usage.children.truncate(MAX_CHILDREN);
+}
+
+// Takes the next job, waiting while other walkers may still queue some; None once all is done
+fn next_job(queue: &Mutex<Queue>, wake: &Condvar) -> Option<Job> {
+    let mut q = queue.lock().unwrap();
+    loop {
+        if let Some(job) = q.jobs.pop() {
+            q.busy += 1;
+            return Some(job);
+        }
+        if q.busy == 0 {
+            return None;
+        }
+        q = wake.wait(q).unwrap();
+    }
+}
+
+// One walker per core takes directories off a shared queue, and queues the subdirectories of
+// those near the root again, so a single large subtree doesn't leave the others idle
+fn analyze(root: PathBuf, depth: u32, app: &AppHandle) -> Result<DiskUsage, String> {
+    let start = Instant::now();
+    let meta = fs::metadata(&root).map_err(|e| format!("{}: {}", root.display(), e))?;
+    // Below the root an unreadable directory is only counted; the root itself is an error
+    fs::read_dir(&root).map_err(|e| format!("{}: {}", root.display(), e))?;
+    let walk = Walk {
+        root_dev: meta.dev(),
+        files: AtomicU64::new(0),
+        bytes: AtomicU64::new(0),
+        unreadable: AtomicU64::new(0),
+        current: Mutex::new(String::new()),
+        linked: Mutex::new(HashSet::new()),
+    };
+    let mut tree = DirUsage::new(&root);
+    tree.name = root.to_string_lossy().to_string();
+    tree.size = meta.blocks() * 512;
+    let dirs = walk.entries(&root, &mut tree);
+
+    let nodes = Mutex::new(vec![Node { usage: tree, parent: None, depth }]);
+    let queue = Mutex::new(Queue {
+        jobs: dirs.into_iter().map(|path| Job { path, level: 1, parent: 0 }).collect(),
+        busy: 0,
+    });
+    let wake = Condvar::new();
+    let done = AtomicBool::new(false);
+    let workers = std::thread::available_parallelism().map_or(4, |n| n.get());
+    std::thread::scope(|s| {
+        let handles: Vec<_> = (0..workers).map(|_| s.spawn(|| {
+            while let Some(job) = next_job(&queue, &wake) {
+                let remaining = depth.saturating_sub(job.level);
+                let mut more = Vec::new();
+                if job.level < SHARED_LEVELS {
+                    walk.show(&job.path);
+                    let mut usage = DirUsage::new(&job.path);
+                    let dirs = walk.entries(&job.path, &mut usage);
+                    let mut nodes = nodes.lock().unwrap();
+                    nodes.push(Node { usage, parent: Some(job.parent), depth: remaining });
+                    let index = nodes.len() - 1;
+                    more.extend(dirs.into_iter().map(|path| Job { path, level: job.level + 1, parent: index }));
+                } else {
+                    let child = walk.dir(&job.path, remaining);
+                    let parent = &mut nodes.lock().unwrap()[job.parent].usage;
+                    parent.size += child.size;
+                    parent.files += child.files;
+                    parent.children.push(child);
+                }
+                let mut q = queue.lock().unwrap();
+                q.jobs.extend(more);
+                q.busy -= 1;
+                wake.notify_all();
+            }
+        })).collect();
+        s.spawn(|| while !done.load(Ordering::Relaxed) {
+            let _ = app.emit_all("disk_usage://progress", UsageProgress {
+                files: walk.files.load(Ordering::Relaxed),
+                bytes: walk.bytes.load(Ordering::Relaxed),
+                current: walk.current.lock().unwrap().clone(),
+            });
+            std::thread::sleep(PROGRESS_EVERY);
+        });
+        for handle in handles {
+            let _ = handle.join();
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+
+    // Children always come after their parent, so folding from the end sees every subtree whole
+    let mut nodes = nodes.into_inner().unwrap();
+    while let Some(mut node) = nodes.pop() {
+        prune(&mut node.usage, node.depth);
+        let Some(parent) = node.parent else {
+            return Ok(DiskUsage {
+                tree: node.usage,
+                unreadable: walk.unreadable.load(Ordering::Relaxed),
+                elapsed_ms: start.elapsed().as_millis() as u64,
+            });
+        };
+        let parent = &mut nodes[parent].usage;
+        parent.size += node.usage.size;
+        parent.files += node.usage.files;
+        parent.children.push(node.usage);
+    }
+    Err("Nothing was walked".to_string())
+}
+
+// --- Commands ---
+
+/// Sizes every directory under `path`, ncdu style, and returns them largest first down to
+/// `depth` levels. Progress is reported through `disk_usage://progress` events.
+#[tauri::command]
+pub async fn analyze_disk_usage(path: String, depth: Option<u32>, app: AppHandle) -> Result<DiskUsage, String> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", path));
+    }
+    let depth = depth.unwrap_or(DEFAULT_DEPTH).clamp(1, MAX_DEPTH);
+    tauri::async_runtime::spawn_blocking(move || analyze(root, depth, &app))
+        .await
+        .map_err(|e| e.to_string())?
+}
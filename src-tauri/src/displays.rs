@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Clone, serde::Serialize)]
+pub struct DisplayMode {
+    width: u32,
+    height: u32,
+    /// Hz
+    refresh: f32,
+}
+
+#[derive(serde::Serialize)]
+pub struct Display {
+    /// DRM connector, e.g. "DP-1" or "eDP-1"
+    connector: String,
+    /// DRM card driving it, e.g. "card1"
+    card: String,
+    /// Monitor name from its EDID
+    name: Option<String>,
+    /// Three-letter PNP vendor code, e.g. "DEL"
+    manufacturer: Option<String>,
+    width_mm: Option<u32>,
+    height_mm: Option<u32>,
+    /// The monitor's native mode
+    preferred: Option<DisplayMode>,
+    /// What the session is running; None when neither xrandr nor wlr-randr can tell
+    current: Option<DisplayMode>,
+    /// Supported resolutions, preferred first
+    resolutions: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct DisplayInfo {
+    /// x11, wayland or tty
+    session_type: String,
+    displays: Vec<Display>,
+}
+
+// Monitor name from the 0xFC display descriptor
+fn edid_name(edid: &[u8]) -> Option<String> {
+    (0..4).map(|i| &edid[54 + i * 18..72 + i * 18])
+        .find(|d| d[..3] == [0, 0, 0] && d[3] == 0xFC)
+        .map(|d| String::from_utf8_lossy(&d[5..]).split('\n').next().unwrap_or_default().trim().to_string())
+}
+
+// Bytes 8-9 pack three letters in five bits each, 'A' = 1
+fn edid_manufacturer(edid: &[u8]) -> String {
+    let id = u16::from_be_bytes([edid[8], edid[9]]);
+    [10, 5, 0].iter().map(|shift| (b'@' + ((id >> shift) & 0x1F) as u8) as char).collect()
+}
+
+// The first detailed timing descriptor is the preferred mode
+fn edid_preferred(edid: &[u8]) -> Option<DisplayMode> {
+    let d = &edid[54..72];
+    let clock = u16::from_le_bytes([d[0], d[1]]) as f32 * 10_000.0;
+    if clock == 0.0 {
+        return None;
+    }
+    let h_active = d[2] as u32 | ((d[4] as u32 >> 4) << 8);
+    let h_blank = d[3] as u32 | ((d[4] as u32 & 0xF) << 8);
+    let v_active = d[5] as u32 | ((d[7] as u32 >> 4) << 8);
+    let v_blank = d[6] as u32 | ((d[7] as u32 & 0xF) << 8);
+    let total = (h_active + h_blank) * (v_active + v_blank);
+    (total > 0).then(|| DisplayMode { width: h_active, height: v_active, refresh: clock / total as f32 })
+}
+
+fn parse_resolution(text: &str) -> Option<(u32, u32)> {
+    let (w, h) = text.split_once('x')?;
+    Some((w.parse().ok()?, h.trim_end_matches(|c: char| !c.is_ascii_digit()).parse().ok()?))
+}
+
+// X.Org's modesetting driver calls "HDMI-A-1" just "HDMI-1"
+fn normalize(connector: &str) -> String {
+    connector.replace("-A-", "-")
+}
+
+// "DP-1 connected primary 2560x1440+0+0 ..." then "   2560x1440     59.95*+  74.97"
+fn xrandr_modes() -> HashMap<String, DisplayMode> {
+    let Ok(output) = Command::new("xrandr").arg("--query").output() else { return HashMap::new() };
+    let mut modes = HashMap::new();
+    let mut output_name: Option<String> = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if !line.starts_with(' ') {
+            output_name = line.contains(" connected").then(|| line.split_whitespace().next().unwrap_or_default().to_string());
+            continue;
+        }
+        let Some(name) = &output_name else { continue };
+        let mut fields = line.split_whitespace();
+        let Some((width, height)) = fields.next().and_then(parse_resolution) else { continue };
+        if let Some(rate) = fields.find(|f| f.contains('*')) {
+            if let Ok(refresh) = rate.trim_end_matches(['*', '+']).parse() {
+                modes.insert(normalize(name), DisplayMode { width, height, refresh });
+            }
+        }
+    }
+    modes
+}
+
+// wlroots compositors: "DP-1 \"Dell ...\"" then "    2560x1440 px, 59.951000 Hz (preferred, current)"
+fn wlr_randr_modes() -> HashMap<String, DisplayMode> {
+    let Ok(output) = Command::new("wlr-randr").output() else { return HashMap::new() };
+    let mut modes = HashMap::new();
+    let mut output_name: Option<String> = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if !line.starts_with(' ') {
+            output_name = line.split_whitespace().next().map(String::from);
+        } else if let (Some(name), true) = (&output_name, line.contains("current)")) {
+            let mut fields = line.split_whitespace();
+            let Some((width, height)) = fields.next().and_then(parse_resolution) else { continue };
+            if let Some(refresh) = fields.nth(1).and_then(|r| r.parse().ok()) {
+                modes.insert(normalize(name), DisplayMode { width, height, refresh });
+            }
+        }
+    }
+    modes
+}
+
+fn session_type() -> String {
+    std::env::var("XDG_SESSION_TYPE").ok().filter(|t| !t.is_empty())
+        .or_else(|| std::env::var("WAYLAND_DISPLAY").is_ok().then(|| "wayland".to_string()))
+        .or_else(|| std::env::var("DISPLAY").is_ok().then(|| "x11".to_string()))
+        .unwrap_or_else(|| "tty".to_string())
+}
+
+fn read(path: &Path, name: &str) -> String {
+    fs::read_to_string(path.join(name)).unwrap_or_default().trim().to_string()
+}
+
+// --- Commands ---
+
+/// Connected monitors from DRM sysfs and their EDID, with the mode the session runs them at.
+#[tauri::command]
+pub fn get_displays() -> DisplayInfo {
+    let session_type = session_type();
+    let current = if session_type == "wayland" {
+        // Under Wayland xrandr only sees XWayland's virtual outputs
+        wlr_randr_modes()
+    } else {
+        xrandr_modes()
+    };
+    let mut displays: Vec<Display> = fs::read_dir("/sys/class/drm").into_iter().flatten().flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let (card, connector) = entry.file_name().to_string_lossy().split_once('-')
+                .map(|(c, n)| (c.to_string(), n.to_string()))?;
+            if read(&dir, "status") != "connected" {
+                return None;
+            }
+            let edid = fs::read(dir.join("edid")).ok().filter(|e| e.len() >= 128);
+            // One line per mode, repeated for each refresh rate
+            let mut resolutions: Vec<String> = Vec::new();
+            for mode in read(&dir, "modes").lines() {
+                if !resolutions.iter().any(|r| r == mode) {
+                    resolutions.push(mode.to_string());
+                }
+            }
+            Some(Display {
+                name: edid.as_deref().and_then(edid_name),
+                manufacturer: edid.as_deref().map(edid_manufacturer),
+                // Bytes 21-22 are the image size in cm; 0 for projectors
+                width_mm: edid.as_ref().map(|e| e[21] as u32 * 10).filter(|w| *w > 0),
+                height_mm: edid.as_ref().map(|e| e[22] as u32 * 10).filter(|h| *h > 0),
+                preferred: edid.as_deref().and_then(edid_preferred),
+                current: current.get(&normalize(&connector)).cloned(),
+                resolutions,
+                card,
+                connector,
+            })
+        })
+        .collect();
+    displays.sort_by(|a, b| (&a.card, &a.connector).cmp(&(&b.card, &b.connector)));
+    DisplayInfo { session_type, displays }
+}
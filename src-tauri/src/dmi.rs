@@ -0,0 +1,173 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const DMI_ID: &str = "/sys/class/dmi/id";
+
+/// Firmware-reported identity of the machine. Serial numbers are root-only in sysfs and left out.
+#[derive(serde::Serialize)]
+pub struct DmiInfo {
+    /// The machine as sold, e.g. "LENOVO" / "20XW0055GE"
+    system_vendor: Option<String>,
+    product_name: Option<String>,
+    /// Often the marketing name on laptops, e.g. "ThinkPad X1 Carbon Gen 9"
+    product_version: Option<String>,
+    board_vendor: Option<String>,
+    board_name: Option<String>,
+    bios_vendor: Option<String>,
+    bios_version: Option<String>,
+    bios_date: Option<String>,
+    /// SMBIOS chassis type by name, e.g. "Notebook"
+    chassis_type: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct MemoryModule {
+    /// Slot label on the board, e.g. "DIMM A1" or "ChannelA-DIMM0"
+    slot: String,
+    bank: Option<String>,
+    /// Bytes; None for an empty slot
+    size: Option<u64>,
+    /// e.g. "DDR4"
+    kind: Option<String>,
+    /// e.g. "SODIMM"
+    form_factor: Option<String>,
+    /// Rated and configured speed in MT/s
+    speed: Option<u32>,
+    configured_speed: Option<u32>,
+    manufacturer: Option<String>,
+    part_number: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct MemoryModules {
+    /// Most the board accepts in total, in bytes
+    max_capacity: Option<u64>,
+    /// Every slot, empty ones included
+    modules: Vec<MemoryModule>,
+}
+
+// Placeholders vendors leave in unused fields
+fn dmi(name: &str) -> Option<String> {
+    let value = fs::read_to_string(format!("{}/{}", DMI_ID, name)).ok()?.trim().to_string();
+    let placeholder = ["", "To Be Filled By O.E.M.", "Default string", "System Product Name", "Not Applicable", "None", "0123456789"];
+    (!placeholder.contains(&value.as_str())).then_some(value)
+}
+
+// System enclosure types from the SMBIOS spec, section 7.4.1
+fn chassis_name(code: &str) -> Option<&'static str> {
+    Some(match code.parse::<u8>().ok()? {
+        1 => "Other",
+        3 => "Desktop",
+        4 => "Low Profile Desktop",
+        5 => "Pizza Box",
+        6 => "Mini Tower",
+        7 => "Tower",
+        8 => "Portable",
+        9 => "Laptop",
+        10 => "Notebook",
+        11 => "Hand Held",
+        12 => "Docking Station",
+        13 => "All in One",
+        14 => "Sub Notebook",
+        15 => "Space-saving",
+        16 => "Lunch Box",
+        17 => "Main Server Chassis",
+        23 => "Rack Mount Chassis",
+        24 => "Sealed-case PC",
+        30 => "Tablet",
+        31 => "Convertible",
+        32 => "Detachable",
+        33 => "IoT Gateway",
+        34 => "Embedded PC",
+        35 => "Mini PC",
+        36 => "Stick PC",
+        _ => return None,
+    })
+}
+
+pub fn read() -> DmiInfo {
+    DmiInfo {
+        system_vendor: dmi("sys_vendor"),
+        product_name: dmi("product_name"),
+        product_version: dmi("product_version"),
+        board_vendor: dmi("board_vendor"),
+        board_name: dmi("board_name"),
+        bios_vendor: dmi("bios_vendor"),
+        bios_version: dmi("bios_version"),
+        bios_date: dmi("bios_date"),
+        chassis_type: dmi("chassis_type").and_then(|c| chassis_name(&c)).map(String::from),
+    }
+}
+
+// "16 GB", "8192 MB"
+fn parse_size(value: &str) -> Option<u64> {
+    let (num, unit) = value.split_once(' ')?;
+    let mult: u64 = match unit {
+        "kB" | "KB" => 1 << 10,
+        "MB" => 1 << 20,
+        "GB" => 1 << 30,
+        "TB" => 1 << 40,
+        _ => return None,
+    };
+    Some(num.parse::<u64>().ok()? * mult)
+}
+
+// dmidecode needs root to read the SMBIOS tables; try directly first in case we are root
+fn dmidecode(args: &[&str]) -> Result<String, String> {
+    let path = ["/usr/sbin/dmidecode", "/usr/bin/dmidecode", "/sbin/dmidecode"].into_iter()
+        .find(|p| Path::new(p).exists())
+        .ok_or("dmidecode is not installed")?;
+    let mut output = Command::new(path).args(args).output().map_err(|e| e.to_string())?;
+    if !output.status.success() && unsafe { libc::geteuid() } != 0 {
+        output = Command::new("pkexec").arg(path).args(args).output().map_err(|e| e.to_string())?;
+        if matches!(output.status.code(), Some(126) | Some(127)) {
+            return Err("Authentication cancelled".to_string());
+        }
+    }
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Records are blank-line separated, each a title line followed by tab-indented "Key: Value"
+fn memory_modules(text: &str) -> MemoryModules {
+    let mut layout = MemoryModules { max_capacity: None, modules: Vec::new() };
+    for record in text.split("\n\n") {
+        let field = |key: &str| record.lines()
+            .find_map(|l| l.trim().strip_prefix(key)?.strip_prefix(": "))
+            .map(str::trim)
+            .filter(|v| !["Unknown", "Not Specified", "None", ""].contains(v));
+        if record.contains("Physical Memory Array") {
+            // Boards with several arrays accept the sum
+            if let Some(max) = field("Maximum Capacity").and_then(parse_size) {
+                *layout.max_capacity.get_or_insert(0) += max;
+            }
+        } else if record.contains("\nMemory Device") {
+            let mt = |key: &str| field(key)?.split_whitespace().next()?.parse().ok();
+            layout.modules.push(MemoryModule {
+                slot: field("Locator").unwrap_or("Unknown").to_string(),
+                bank: field("Bank Locator").map(String::from),
+                size: field("Size").and_then(parse_size),
+                kind: field("Type").map(String::from),
+                form_factor: field("Form Factor").map(String::from),
+                speed: mt("Speed"),
+                configured_speed: mt("Configured Memory Speed").or_else(|| mt("Configured Clock Speed")),
+                manufacturer: field("Manufacturer").map(String::from),
+                part_number: field("Part Number").map(String::from),
+            });
+        }
+    }
+    layout
+}
+
+// --- Commands ---
+
+/// Every memory slot with the module in it, if any. Asks for authentication unless run as root.
+#[tauri::command]
+pub async fn get_memory_modules() -> Result<MemoryModules, String> {
+    tauri::async_runtime::spawn_blocking(|| dmidecode(&["-t", "16,17"]).map(|text| memory_modules(&text)))
+        .await
+        .map_err(|e| e.to_string())?
+}
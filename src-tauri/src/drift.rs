@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::cleanup::hash_file;
+use crate::history::unix_now;
+use crate::{config, AppState};
+
+const DRIFT_FILE: &str = "drift.toml";
+const MAX_EVENTS: usize = 5000;
+// Text files up to this size keep their content so changes can be shown as a diff
+const DIFF_MAX_BYTES: u64 = 16 * 1024;
+// Checked periodically so a changed interval applies without a restart
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct FileState {
+    /// FNV-1a as hex, since TOML integers stop at i64::MAX; empty for a baseline taken with the
+    /// old per-build hash, which can't be compared
+    #[serde(deserialize_with = "stored_hash")]
+    hash: String,
+    size: u64,
+    content: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct DriftEvent {
+    time: u64,
+    path: String,
+    /// added, removed or modified
+    change: String,
+    /// Changed lines prefixed with "+" or "-", when the old and new content are known
+    diff: Vec<String>,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+pub struct DriftStore {
+    last_scan: u64,
+    baseline: HashMap<String, FileState>,
+    timeline: Vec<DriftEvent>,
+}
+
+impl DriftStore {
+    pub fn load() -> Self {
+        config::load(DRIFT_FILE)
+    }
+}
+
+fn stored_hash<'de, D: serde::Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    let stored: toml::Value = serde::Deserialize::deserialize(d)?;
+    Ok(stored.as_str().unwrap_or_default().to_string())
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix('~') {
+        Some(rest) => PathBuf::from(format!("{}{}", std::env::var("HOME").unwrap_or_default(), rest)),
+        None => PathBuf::from(path),
+    }
+}
+
+fn read_state(path: &Path, meta: &fs::Metadata) -> Option<FileState> {
+    let hash = format!("{:016x}", hash_file(path, None)?);
+    // Never copy files other users cannot read (shadow, keys) into our own config dir
+    let world_readable = meta.permissions().mode() & 0o004 != 0;
+    let content = if world_readable && meta.len() <= DIFF_MAX_BYTES {
+        fs::read(path).ok().and_then(|b| String::from_utf8(b).ok())
+    } else {
+        None
+    };
+    Some(FileState { hash, size: meta.len(), content })
+}
+
+fn snapshot(roots: &[String]) -> HashMap<String, FileState> {
+    let mut files = HashMap::new();
+    let mut stack: Vec<PathBuf> = roots.iter().map(|r| expand_home(r)).collect();
+    while let Some(path) = stack.pop() {
+        let Ok(meta) = path.symlink_metadata() else { continue };
+        if meta.is_dir() {
+            stack.extend(fs::read_dir(&path).into_iter().flatten().flatten().map(|e| e.path()));
+        } else if meta.is_file() {
+            if let Some(state) = read_state(&path, &meta) {
+                files.insert(path.to_string_lossy().to_string(), state);
+            }
+        }
+    }
+    files
+}
+
+// Classic LCS line diff; inputs are capped by DIFF_MAX_BYTES so the table stays small
+fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let (a, b): (Vec<&str>, Vec<&str>) = (old.lines().collect(), new.lines().collect());
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let (mut i, mut j, mut out) = (0, 0, Vec::new());
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(format!("+{}", b[j]));
+            j += 1;
+        } else {
+            out.push(format!("-{}", a[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Compares the watched paths against the stored baseline and records what changed.
+fn scan(app: &AppHandle) -> Result<usize, String> {
+    let state = app.state::<AppState>();
+    let paths = state.settings.lock().unwrap().drift_paths.clone();
+    let current = snapshot(&paths);
+
+    let mut store = state.drift.lock().unwrap();
+    let now = unix_now();
+    let mut events = Vec::new();
+    // A newly watched path only establishes its baseline instead of reporting every file as added
+    let roots: Vec<PathBuf> = paths.iter().map(|r| expand_home(r)).collect();
+    let baselined: Vec<&PathBuf> = roots.iter()
+        .filter(|r| store.baseline.keys().any(|p| Path::new(p).starts_with(r)))
+        .collect();
+    for (path, new) in &current {
+        match store.baseline.get(path) {
+            Some(old) if old.hash == new.hash => {}
+            // Re-baselined silently; only a content diff can still tell a real change apart
+            Some(old) if old.hash.is_empty() && old.content.is_none() => {}
+            Some(old) if old.hash.is_empty() && old.content == new.content => {}
+            Some(old) => events.push(DriftEvent {
+                time: now,
+                path: path.clone(),
+                change: "modified".to_string(),
+                diff: match (&old.content, &new.content) {
+                    (Some(o), Some(n)) => line_diff(o, n),
+                    _ => vec![format!("size {} -> {} bytes", old.size, new.size)],
+                },
+            }),
+            None if baselined.iter().any(|r| Path::new(path).starts_with(r)) => {
+                events.push(DriftEvent { time: now, path: path.clone(), change: "added".to_string(), diff: Vec::new() });
+            }
+            None => {}
+        }
+    }
+    for path in store.baseline.keys().filter(|p| !current.contains_key(*p)) {
+        // Paths dropped from the watch list are forgotten, not reported as deleted
+        if roots.iter().any(|r| Path::new(path).starts_with(r)) {
+            events.push(DriftEvent { time: now, path: path.clone(), change: "removed".to_string(), diff: Vec::new() });
+        }
+    }
+
+    let changes = events.len();
+    store.timeline.extend(events);
+    let overflow = store.timeline.len().saturating_sub(MAX_EVENTS);
+    store.timeline.drain(..overflow);
+    store.baseline = current;
+    store.last_scan = now;
+    config::save(DRIFT_FILE, &*store)?;
+    Ok(changes)
+}
+
+/// Rescans in the background every `drift_interval_hours`.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        let (enabled, interval) = {
+            let state = app.state::<AppState>();
+            let settings = state.settings.lock().unwrap();
+            (!settings.drift_paths.is_empty(), settings.drift_interval_hours * 3600)
+        };
+        let last_scan = app.state::<AppState>().drift.lock().unwrap().last_scan;
+        if enabled && unix_now().saturating_sub(last_scan) >= interval {
+            let result = scan(&app);
+            app.state::<AppState>().health.lock().unwrap().record("drift", Some(interval * 1000), &result);
+        }
+        std::thread::sleep(SCHEDULER_TICK);
+    });
+}
+
+// --- Commands ---
+
+/// Recorded changes newest first, optionally only those after `since` (Unix seconds).
+#[tauri::command]
+pub fn get_drift_timeline(since: Option<u64>, state: State<AppState>) -> Vec<DriftEvent> {
+    let store = state.drift.lock().unwrap();
+    store.timeline.iter().rev()
+        .filter(|e| e.time >= since.unwrap_or(0))
+        .cloned()
+        .collect()
+}
+
+/// Scans now instead of waiting for the schedule; returns the number of changes found.
+#[tauri::command]
+pub async fn run_drift_scan(app: AppHandle) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || scan(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
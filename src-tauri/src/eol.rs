@@ -0,0 +1,180 @@
+use std::fs;
+use std::process::Command;
+
+use crate::history::unix_now;
+
+// Warn this long before support ends
+const ENDING_SOON_DAYS: i64 = 180;
+
+// Security support end dates (YYYY-MM-DD). Update as new releases ship; unknown versions
+// are reported as such rather than guessed.
+const DISTRO_EOL: &[(&str, &str, &str)] = &[
+    ("ubuntu", "20.04", "2025-05-31"),
+    ("ubuntu", "22.04", "2027-06-01"),
+    ("ubuntu", "24.04", "2029-05-31"),
+    ("ubuntu", "24.10", "2025-07-10"),
+    ("ubuntu", "25.04", "2026-01-15"),
+    ("debian", "10", "2022-09-10"),
+    ("debian", "11", "2024-08-14"),
+    ("debian", "12", "2026-06-10"),
+    ("debian", "13", "2028-08-09"),
+    ("fedora", "39", "2024-11-26"),
+    ("fedora", "40", "2025-05-13"),
+    ("fedora", "41", "2025-12-15"),
+    ("fedora", "42", "2026-05-13"),
+    ("rhel", "8", "2029-05-31"),
+    ("rhel", "9", "2032-05-31"),
+    ("rocky", "8", "2029-05-31"),
+    ("rocky", "9", "2032-05-31"),
+    ("almalinux", "8", "2029-05-31"),
+    ("almalinux", "9", "2032-05-31"),
+];
+
+// Longterm kernels from kernel.org; any other series is a short-lived stable branch
+const KERNEL_LTS_EOL: &[(&str, &str)] = &[
+    ("5.4", "2025-12-31"),
+    ("5.10", "2026-12-31"),
+    ("5.15", "2026-12-31"),
+    ("6.1", "2027-12-31"),
+    ("6.6", "2026-12-31"),
+    ("6.12", "2026-12-31"),
+];
+
+const PYTHON_EOL: &[(&str, &str)] = &[
+    ("3.8", "2024-10-07"),
+    ("3.9", "2025-10-31"),
+    ("3.10", "2026-10-31"),
+    ("3.11", "2027-10-31"),
+    ("3.12", "2028-10-31"),
+    ("3.13", "2029-10-31"),
+];
+
+const NODE_EOL: &[(&str, &str)] = &[
+    ("16", "2023-09-11"),
+    ("18", "2025-04-30"),
+    ("20", "2026-04-30"),
+    ("22", "2027-04-30"),
+    ("24", "2028-04-30"),
+];
+
+// Eclipse Temurin support windows
+const JAVA_EOL: &[(&str, &str)] = &[
+    ("8", "2026-11-30"),
+    ("11", "2027-10-31"),
+    ("17", "2027-10-31"),
+    ("21", "2029-12-31"),
+];
+
+#[derive(serde::Serialize)]
+pub struct SupportStatus {
+    component: String,
+    version: String,
+    /// End of security support, when known
+    eol: Option<String>,
+    /// supported, ending_soon, eol or unknown
+    status: String,
+    /// Whole days until `eol`; negative once passed
+    days_left: Option<i64>,
+}
+
+// Days since the Unix epoch for a civil date (Howard Hinnant's algorithm)
+pub fn days_from_civil(date: &str) -> Option<i64> {
+    let mut parts = date.split('-').map(|p| p.parse::<i64>());
+    let (y, m, d) = (parts.next()?.ok()?, parts.next()?.ok()?, parts.next()?.ok()?);
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146097 + doe - 719468)
+}
+
+fn status(component: &str, version: &str, eol: Option<&str>) -> SupportStatus {
+    let today = (unix_now() / 86400) as i64;
+    let days_left = eol.and_then(days_from_civil).map(|d| d - today);
+    let status = match days_left {
+        None => "unknown",
+        Some(d) if d < 0 => "eol",
+        Some(d) if d < ENDING_SOON_DAYS => "ending_soon",
+        Some(_) => "supported",
+    };
+    SupportStatus {
+        component: component.to_string(),
+        version: version.to_string(),
+        eol: eol.map(String::from),
+        status: status.to_string(),
+        days_left,
+    }
+}
+
+fn lookup<'a>(table: &[(&str, &'a str)], version: &str) -> Option<&'a str> {
+    table.iter().find(|(v, _)| *v == version).map(|(_, eol)| *eol)
+}
+
+fn os_release_field(release: &str, key: &str) -> String {
+    release.lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix('='))
+        .unwrap_or_default()
+        .trim_matches('"')
+        .to_string()
+}
+
+/// First "x.y.z"-looking token of a tool's version output, checking stderr too (java prints there).
+fn tool_version(cmd: &str, arg: &str) -> Option<String> {
+    let out = Command::new(cmd).arg(arg).output().ok()?;
+    let text = format!("{}{}", String::from_utf8_lossy(&out.stdout), String::from_utf8_lossy(&out.stderr));
+    text.split(|c: char| c.is_whitespace() || c == '"')
+        .map(|t| t.trim_start_matches('v'))
+        .find(|t| t.starts_with(|c: char| c.is_ascii_digit()) && t.contains('.'))
+        .map(String::from)
+}
+
+fn major_minor(version: &str, parts: usize) -> String {
+    version.split(['.', '_', '-']).take(parts).collect::<Vec<_>>().join(".")
+}
+
+// --- Commands ---
+
+/// Support status of the distro release, kernel series and detected language runtimes.
+#[tauri::command]
+pub async fn get_support_status() -> Result<Vec<SupportStatus>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut out = Vec::new();
+
+        let release = fs::read_to_string("/etc/os-release").unwrap_or_default();
+        let (id, version) = (os_release_field(&release, "ID"), os_release_field(&release, "VERSION_ID"));
+        let name = os_release_field(&release, "NAME");
+        if version.is_empty() {
+            // Rolling releases (Arch, Tumbleweed) have no release to age out
+            out.push(SupportStatus { status: "supported".to_string(), ..status(&name, "rolling", None) });
+        } else {
+            let major = version.split('.').next().unwrap_or_default();
+            let eol = DISTRO_EOL.iter()
+                .find(|(d, v, _)| *d == id && (*v == version || *v == major))
+                .map(|(_, _, eol)| *eol);
+            out.push(status(&name, &version, eol));
+        }
+
+        let kernel = fs::read_to_string("/proc/sys/kernel/osrelease").unwrap_or_default();
+        let series = major_minor(kernel.trim(), 2);
+        match lookup(KERNEL_LTS_EOL, &series) {
+            Some(eol) => out.push(status("Linux kernel (LTS)", &series, Some(eol))),
+            None => out.push(status("Linux kernel (non-LTS)", &series, None)),
+        }
+
+        if let Some(v) = tool_version("python3", "--version") {
+            out.push(status("Python", &v, lookup(PYTHON_EOL, &major_minor(&v, 2))));
+        }
+        if let Some(v) = tool_version("node", "--version") {
+            out.push(status("Node.js", &v, lookup(NODE_EOL, &major_minor(&v, 1))));
+        }
+        if let Some(v) = tool_version("java", "-version") {
+            // Java 8 reports itself as 1.8
+            let major = v.strip_prefix("1.").map(|r| major_minor(r, 1)).unwrap_or_else(|| major_minor(&v, 1));
+            out.push(status("Java", &v, lookup(JAVA_EOL, &major)));
+        }
+        out
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,156 @@
+use std::net::IpAddr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::process::Command;
+use tauri::{AppHandle, Manager};
+
+use crate::history::unix_now;
+use crate::AppState;
+
+const SOCKET: &str = "/var/run/fail2ban/fail2ban.sock";
+
+#[derive(Clone, serde::Serialize)]
+pub struct Jail {
+    name: String,
+    currently_failed: u32,
+    total_failed: u32,
+    currently_banned: u32,
+    total_banned: u32,
+    banned: Vec<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct Fail2banStatus {
+    running: bool,
+    jails: Vec<Jail>,
+    /// Why the status couldn't be read; the server socket is root-only
+    error: Option<String>,
+    /// Unix seconds of the authenticated read being shown again, when it isn't fresh
+    cached_at: Option<u64>,
+}
+
+// Prints the overview, then each jail's status after an "@@jail <name>" marker, so one
+// password prompt covers every jail
+const STATUS_SCRIPT: &str = r#"fail2ban-client status || exit 1
+for j in $(fail2ban-client status | sed -n 's/.*Jail list:[[:space:]]*//p' | tr ',' ' '); do
+    echo "@@jail $j"
+    fail2ban-client status "$j"
+done"#;
+
+// Root can always talk to the server, and some setups open the socket to a group
+fn socket_accessible() -> bool {
+    if unsafe { libc::geteuid() } == 0 {
+        return true;
+    }
+    let Ok(path) = std::ffi::CString::new(Path::new(SOCKET).as_os_str().as_bytes()) else { return false };
+    unsafe { libc::access(path.as_ptr(), libc::R_OK | libc::W_OK) == 0 }
+}
+
+// Runs directly when the socket lets us, otherwise through pkexec
+fn as_root(program: &str, args: &[&str]) -> Result<String, String> {
+    let mut cmd = if socket_accessible() {
+        Command::new(program)
+    } else {
+        let mut c = Command::new("pkexec");
+        c.arg(program);
+        c
+    };
+    let output = cmd.args(args).output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if err.is_empty() { "fail2ban is not running".to_string() } else { err });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// "|  `- Total failed:\t12" -> ("Total failed", "12")
+fn field<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    text.lines().find_map(|l| {
+        let (key, value) = l.split_once(':')?;
+        (key.trim_start_matches(['|', '`', '-', ' ']).trim() == name).then(|| value.trim())
+    })
+}
+
+fn parse_jail(name: &str, out: &str) -> Jail {
+    let num = |f: &str| field(out, f).and_then(|v| v.parse().ok()).unwrap_or(0);
+    Jail {
+        name: name.to_string(),
+        currently_failed: num("Currently failed"),
+        total_failed: num("Total failed"),
+        currently_banned: num("Currently banned"),
+        total_banned: num("Total banned"),
+        banned: field(out, "Banned IP list").unwrap_or_default().split_whitespace().map(String::from).collect(),
+    }
+}
+
+fn failed(error: &str) -> Fail2banStatus {
+    Fail2banStatus { running: false, jails: Vec::new(), error: Some(error.to_string()), cached_at: None }
+}
+
+fn status() -> Fail2banStatus {
+    let out = match as_root("sh", &["-c", STATUS_SCRIPT]) {
+        Ok(o) => o,
+        Err(e) => return failed(&e),
+    };
+    let jails = out.split("@@jail ").skip(1)
+        .filter_map(|section| {
+            let (name, body) = section.split_once('\n')?;
+            Some(parse_jail(name.trim(), body))
+        })
+        .collect();
+    Fail2banStatus { running: true, jails, error: None, cached_at: None }
+}
+
+// Reads straight from the socket when allowed. Otherwise only an explicit `authenticate`
+// prompts for a password, and later refreshes show that result again instead of prompting
+fn current(authenticate: bool, app: &AppHandle) -> Fail2banStatus {
+    if !["/usr/bin/fail2ban-client", "/usr/local/bin/fail2ban-client"].iter().any(|p| Path::new(p).exists()) {
+        return failed("fail2ban is not installed");
+    }
+    let state = app.state::<AppState>();
+    if socket_accessible() {
+        return status();
+    }
+    if !authenticate {
+        let cached = state.fail2ban.lock().unwrap().clone();
+        return cached.unwrap_or_else(|| failed("Reading fail2ban needs root; authenticate to load it"));
+    }
+    let fresh = status();
+    if fresh.running {
+        *state.fail2ban.lock().unwrap() = Some(Fail2banStatus { cached_at: Some(unix_now()), ..fresh.clone() });
+    }
+    fresh
+}
+
+// --- Commands ---
+
+/// Jails with their failure and ban counters and the addresses banned right now. Without
+/// access to the server socket, asks for authentication only when `authenticate` is set and
+/// returns the last authenticated read otherwise.
+#[tauri::command]
+pub async fn get_fail2ban_status(authenticate: Option<bool>, app: AppHandle) -> Result<Fail2banStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || current(authenticate.unwrap_or(false), &app))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unban_ip(jail: String, ip: String, app: AppHandle) -> Result<(), String> {
+    ip.parse::<IpAddr>().map_err(|_| format!("Invalid address: {}", ip))?;
+    if !jail.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) {
+        return Err(format!("Invalid jail: {}", jail));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        as_root("fail2ban-client", &["set", &jail, "unbanip", &ip])?;
+        // So the cached list doesn't show the address until the next authenticated read
+        if let Some(cached) = app.state::<AppState>().fail2ban.lock().unwrap().as_mut() {
+            for j in cached.jails.iter_mut().filter(|j| j.name == jail) {
+                j.banned.retain(|b| *b != ip);
+                j.currently_banned = j.banned.len() as u32;
+            }
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,216 @@
+use std::collections::{HashSet, VecDeque};
+use std::io::{BufRead, BufReader};
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::api::notification::Notification;
+use tauri::{AppHandle, Manager, State};
+
+use crate::history::unix_now;
+use crate::AppState;
+
+const FEED_CAPACITY: usize = 200;
+// bpftrace compiles the program before attaching; exiting within this time is a failure to start
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, serde::Serialize)]
+pub struct FileAccessEvent {
+    time: u64,
+    pid: u32,
+    process: String,
+    /// opened, created, renamed or deleted
+    action: String,
+    path: String,
+    watched_dir: String,
+}
+
+#[derive(Default)]
+pub struct FileMonitorState {
+    tracer: Option<Child>,
+    feed: VecDeque<FileAccessEvent>,
+}
+
+pub fn expand_home(path: &str) -> String {
+    let home = std::env::var("HOME").unwrap_or_default();
+    match path.strip_prefix('~') {
+        Some(rest) => format!("{}{}", home, rest),
+        None => path.to_string(),
+    }
+}
+
+// Directories become string literals in the bpftrace program, so anything that could end the
+// literal or change its meaning is refused rather than escaped
+fn check_dir(dir: &str) -> Result<(), String> {
+    if dir.contains(['"', '\\']) || dir.chars().any(char::is_control) {
+        return Err(format!("{} can't be watched: quotes, backslashes and control characters aren't supported", dir));
+    }
+    Ok(())
+}
+
+// bpftrace refuses the whole program over one missing tracepoint. open, creat, rename and
+// unlink only exist on older ABIs like x86_64, and openat2 needs 5.6; when tracefs isn't
+// readable the tracepoint is assumed to be there
+#[cfg(feature = "ebpf")]
+fn has_tracepoint(name: &str, legacy: bool) -> bool {
+    if legacy && !cfg!(target_arch = "x86_64") {
+        return false;
+    }
+    std::fs::metadata(format!("/sys/kernel/tracing/events/syscalls/{}", name))
+        .map_or_else(|e| e.kind() != std::io::ErrorKind::NotFound, |_| true)
+}
+
+#[cfg(feature = "ebpf")]
+fn spawn_tracer(dirs: &[String]) -> Result<Child, String> {
+    use std::process::{Command, Stdio};
+
+    // (tracepoint, path argument, action, legacy); renames are matched on either name
+    const PROBES: [(&str, &str, &str, bool); 12] = [
+        ("sys_enter_openat", "filename", "opened", false),
+        ("sys_enter_openat2", "filename", "opened", false),
+        ("sys_enter_open", "filename", "opened", true),
+        ("sys_enter_creat", "pathname", "created", true),
+        ("sys_enter_renameat", "oldname", "renamed", false),
+        ("sys_enter_renameat", "newname", "renamed", false),
+        ("sys_enter_renameat2", "oldname", "renamed", false),
+        ("sys_enter_renameat2", "newname", "renamed", false),
+        ("sys_enter_rename", "oldname", "renamed", true),
+        ("sys_enter_rename", "newname", "renamed", true),
+        ("sys_enter_unlinkat", "pathname", "deleted", false),
+        ("sys_enter_unlink", "pathname", "deleted", true),
+    ];
+    // Filter in-kernel so only paths under the watched directories reach us
+    let filter = |arg: &str| dirs.iter()
+        .map(|d| format!("strncmp(str(args->{}), \"{}\", {}) == 0", arg, d, d.len()))
+        .collect::<Vec<_>>()
+        .join(" || ");
+    let script = PROBES.iter()
+        .filter(|(tracepoint, _, _, legacy)| has_tracepoint(tracepoint, *legacy))
+        .map(|(tracepoint, arg, action, _)| format!(
+            "tracepoint:syscalls:{} /pid != {} && ({})/ {{ printf(\"%d\\t%s\\t{}\\t%s\\n\", pid, comm, str(args->{})); }}",
+            tracepoint, std::process::id(), filter(arg), action, arg
+        ))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Command::new("bpftrace").arg("-e").arg(script)
+        .stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start bpftrace: {}", e))
+}
+
+#[cfg(not(feature = "ebpf"))]
+fn spawn_tracer(_dirs: &[String]) -> Result<Child, String> {
+    Err("glassview was built without the `ebpf` feature".to_string())
+}
+
+fn parse_event(line: &str, dirs: &[String]) -> Option<FileAccessEvent> {
+    let mut parts = line.splitn(4, '\t');
+    let pid = parts.next()?.trim().parse().ok()?;
+    let process = parts.next()?.to_string();
+    let action = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+    let watched_dir = dirs.iter().find(|d| path.starts_with(d.as_str()))?.clone();
+    Some(FileAccessEvent { time: unix_now(), pid, process, action, path, watched_dir })
+}
+
+// bpftrace prints "Attaching N probes..." once the program loaded, on stdout or stderr
+// depending on the version; until then, everything on stderr is the reason it failed
+fn start(dirs: Vec<String>, app: AppHandle) -> Result<(), String> {
+    let mut child = spawn_tracer(&dirs)?;
+    let stdout = child.stdout.take().ok_or("bpftrace has no stdout")?;
+    let stderr = child.stderr.take().ok_or("bpftrace has no stderr")?;
+    let attached = Arc::new(AtomicBool::new(false));
+    let errors = Arc::new(Mutex::new(String::new()));
+
+    // Kept draining for as long as bpftrace runs, so its warnings can't fill the pipe
+    let stderr_reader = {
+        let (attached, errors) = (attached.clone(), errors.clone());
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                if line.starts_with("Attaching") {
+                    attached.store(true, Ordering::Relaxed);
+                } else if !attached.load(Ordering::Relaxed) {
+                    errors.lock().unwrap().push_str(&format!("{}\n", line));
+                }
+            }
+        })
+    };
+
+    let seen = attached.clone();
+    let monitored = dirs.clone();
+    let events = app.clone();
+    std::thread::spawn(move || {
+        let identifier = events.config().tauri.bundle.identifier.clone();
+        // Notify once per process/directory pair, the feed has the full detail
+        let mut notified = HashSet::new();
+
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            seen.store(true, Ordering::Relaxed);
+            let Some(event) = parse_event(&line, &monitored) else { continue };
+
+            if notified.insert((event.process.clone(), event.watched_dir.clone())) {
+                let _ = Notification::new(&identifier)
+                    .title("Sensitive file access")
+                    .body(format!("{} (PID {}) {} {}", event.process, event.pid, event.action, event.path))
+                    .show();
+            }
+            let _ = events.emit_all("files://access", event.clone());
+
+            let state = events.state::<AppState>();
+            let mut monitor = state.file_monitor.lock().unwrap();
+            if monitor.feed.len() >= FEED_CAPACITY {
+                monitor.feed.pop_front();
+            }
+            monitor.feed.push_back(event);
+        }
+    });
+
+    let started = Instant::now();
+    while !attached.load(Ordering::Relaxed) && started.elapsed() < STARTUP_TIMEOUT {
+        if child.try_wait().map_err(|e| e.to_string())?.is_some() {
+            // The pipe closed with the process, so the reader has everything
+            let _ = stderr_reader.join();
+            let message = errors.lock().unwrap().trim().to_string();
+            return Err(if message.is_empty() { "bpftrace exited without starting".to_string() } else { message });
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let state = app.state::<AppState>();
+    let mut monitor = state.file_monitor.lock().unwrap();
+    if let Some(mut old) = monitor.tracer.replace(child) {
+        let _ = old.kill();
+        let _ = old.wait();
+    }
+    Ok(())
+}
+
+// --- Commands ---
+
+/// Traces opens, creates, renames and deletes under `dirs` until stopped. Fails with bpftrace's
+/// own message when it can't load the program.
+#[tauri::command]
+pub async fn start_file_monitor(dirs: Vec<String>, app: AppHandle) -> Result<(), String> {
+    let dirs: Vec<String> = dirs.iter().map(|d| expand_home(d)).collect();
+    if dirs.is_empty() {
+        return Err("No directories selected".to_string());
+    }
+    dirs.iter().try_for_each(|d| check_dir(d))?;
+    tauri::async_runtime::spawn_blocking(move || start(dirs, app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn stop_file_monitor(state: State<AppState>) {
+    if let Some(mut child) = state.file_monitor.lock().unwrap().tracer.take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+#[tauri::command]
+pub fn get_file_access_events(state: State<AppState>) -> Vec<FileAccessEvent> {
+    state.file_monitor.lock().unwrap().feed.iter().cloned().collect()
+}
@@ -0,0 +1,332 @@
+use std::fs;
+use std::net::IpAddr;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(serde::Serialize)]
+pub struct FirewallRule {
+    /// allow, deny, reject or limit
+    action: String,
+    /// in, out or empty when the rule applies both ways
+    direction: String,
+    /// Port, service or match the rule applies to
+    target: String,
+    source: String,
+    /// The rule as the backend prints it; for ufw the spec `ufw delete` takes
+    raw: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct FirewallStatus {
+    /// ufw, firewalld, nftables or none
+    backend: String,
+    enabled: bool,
+    /// Policy for incoming traffic no rule matched
+    default_policy: Option<String>,
+    rules: Vec<FirewallRule>,
+    /// Why the rule list could not be read, typically missing privileges
+    error: Option<String>,
+}
+
+fn run(cmd: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(cmd).args(args).output().map_err(|_| format!("{} is not installed", cmd))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn installed(cmd: &str) -> bool {
+    ["/usr/sbin", "/usr/bin", "/sbin", "/bin"].iter().any(|d| Path::new(&format!("{}/{}", d, cmd)).exists())
+}
+
+// ufw prints comments quoted: "allow 22/tcp comment 'ssh from work'"
+fn split_spec(spec: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote = None;
+    for c in spec.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => quote = Some(c),
+            (None, c) if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            (None, c) => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+// `ufw show added` lists each rule as the command that adds it, e.g.
+// "ufw allow 22/tcp" or "ufw deny in from 10.0.0.0/8 to any port 80 proto tcp"
+fn parse_ufw_rule(line: &str) -> Option<FirewallRule> {
+    let spec = line.trim().strip_prefix("ufw ")?;
+    let words = split_spec(spec);
+    let action = words.first().filter(|a| ["allow", "deny", "reject", "limit"].contains(&a.as_str()))?;
+    let direction = words.get(1).filter(|d| *d == "in" || *d == "out").cloned().unwrap_or_default();
+    let after = |key: &str| words.iter().position(|w| w == key).and_then(|i| words.get(i + 1)).cloned();
+    // The short form names the port or service right after the action and direction
+    let simple = words.get(if direction.is_empty() { 1 } else { 2 })
+        .filter(|w| !["from", "to", "on", "proto", "log", "log-all", "comment"].contains(&w.as_str()))
+        .cloned();
+    Some(FirewallRule {
+        action: action.clone(),
+        direction,
+        target: after("port").or(simple).or_else(|| after("app")).unwrap_or_default(),
+        source: after("from").filter(|s| s != "any").unwrap_or_else(|| "Anywhere".to_string()),
+        raw: spec.to_string(),
+    })
+}
+
+// Listing rules needs root like changing them; as root it just runs, otherwise only when the
+// user asked to authenticate, so a refresh never pops up a password dialog
+fn ufw_read(args: &[&str], authenticate: bool) -> Result<String, String> {
+    if unsafe { libc::geteuid() } == 0 {
+        return run("ufw", args);
+    }
+    if !authenticate {
+        return Err("Listing ufw rules needs root; authenticate to show them".to_string());
+    }
+    let mut pkexec_args = vec!["ufw"];
+    pkexec_args.extend(args);
+    run("pkexec", &pkexec_args)
+}
+
+fn ufw_status(authenticate: bool) -> FirewallStatus {
+    // The config files are world-readable; the rule list needs root
+    let enabled = fs::read_to_string("/etc/ufw/ufw.conf").unwrap_or_default().lines()
+        .any(|l| l.trim() == "ENABLED=yes");
+    let default_policy = fs::read_to_string("/etc/default/ufw").unwrap_or_default().lines()
+        .find_map(|l| l.strip_prefix("DEFAULT_INPUT_POLICY="))
+        .map(|p| p.trim_matches('"').to_lowercase());
+    let (rules, error) = match ufw_read(&["show", "added"], authenticate) {
+        Ok(out) => (out.lines().filter_map(parse_ufw_rule).collect(), None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+    FirewallStatus { backend: "ufw".to_string(), enabled, default_policy, rules, error }
+}
+
+// "  services: dhcpv6-client ssh" and "rule family="ipv4" source address="10.0.0.0/8" accept"
+fn firewalld_status() -> FirewallStatus {
+    let enabled = run("firewall-cmd", &["--state"]).is_ok_and(|s| s.trim() == "running");
+    let mut status = FirewallStatus {
+        backend: "firewalld".to_string(), enabled, default_policy: None, rules: Vec::new(), error: None,
+    };
+    if !enabled {
+        return status;
+    }
+    let listing = match run("firewall-cmd", &["--list-all"]) {
+        Ok(l) => l,
+        Err(e) => {
+            status.error = Some(e);
+            return status;
+        }
+    };
+    let allow = |target: &str, raw: &str| FirewallRule {
+        action: "allow".to_string(), direction: "in".to_string(),
+        target: target.to_string(), source: "Anywhere".to_string(), raw: raw.to_string(),
+    };
+    let mut in_rich = false;
+    for line in listing.lines() {
+        let line = line.trim();
+        if let Some(target) = line.strip_prefix("target:") {
+            status.default_policy = Some(target.trim().to_lowercase());
+        } else if let Some(list) = line.strip_prefix("services:").or_else(|| line.strip_prefix("ports:")) {
+            status.rules.extend(list.split_whitespace().map(|t| allow(t, line)));
+        } else if line.starts_with("rich rules:") {
+            in_rich = true;
+        } else if in_rich && line.starts_with("rule ") {
+            let source = line.split_once("source address=\"")
+                .and_then(|(_, r)| r.split('"').next()).unwrap_or("Anywhere");
+            let target = line.split_once("service name=\"").or_else(|| line.split_once("port port=\""))
+                .and_then(|(_, r)| r.split('"').next()).unwrap_or("");
+            status.rules.push(FirewallRule {
+                action: line.split_whitespace().last().unwrap_or_default().to_string(),
+                direction: "in".to_string(),
+                target: target.to_string(),
+                source: source.to_string(),
+                raw: line.to_string(),
+            });
+        } else if line.ends_with(':') || line.contains(": ") {
+            in_rich = false;
+        }
+    }
+    status
+}
+
+// Rules are every statement inside a chain that ends in a verdict
+fn nftables_status() -> FirewallStatus {
+    let mut status = FirewallStatus {
+        backend: "nftables".to_string(), enabled: false, default_policy: None, rules: Vec::new(), error: None,
+    };
+    let ruleset = match run("nft", &["list", "ruleset"]) {
+        Ok(r) => r,
+        Err(e) => {
+            // Without root nft can't list anything; the service state still says whether rules load at boot
+            status.enabled = Command::new("systemctl").args(["is-active", "--quiet", "nftables"])
+                .status().is_ok_and(|s| s.success());
+            status.error = Some(e);
+            return status;
+        }
+    };
+    let mut direction = "";
+    for line in ruleset.lines().map(str::trim) {
+        if line.starts_with("chain ") {
+            direction = "";
+        } else if line.starts_with("type filter hook") {
+            direction = if line.contains("hook input") { "in" } else if line.contains("hook output") { "out" } else { "" };
+            if direction == "in" {
+                status.default_policy = line.split_once("policy ")
+                    .map(|(_, p)| p.trim_end_matches(';').trim().to_string());
+            }
+        } else if let Some(verdict) = ["accept", "drop", "reject"].iter().find(|v| line.split_whitespace().any(|w| w == **v)) {
+            let source = line.split_once("saddr ").and_then(|(_, r)| r.split_whitespace().next()).unwrap_or("Anywhere");
+            let target = line.split_once("dport ").and_then(|(_, r)| r.split_whitespace().next()).unwrap_or("");
+            status.rules.push(FirewallRule {
+                action: if *verdict == "accept" { "allow" } else if *verdict == "drop" { "deny" } else { "reject" }.to_string(),
+                direction: direction.to_string(),
+                target: target.to_string(),
+                source: source.to_string(),
+                raw: line.to_string(),
+            });
+        }
+    }
+    status.enabled = !status.rules.is_empty() || status.default_policy.is_some();
+    status
+}
+
+fn detect(authenticate: bool) -> FirewallStatus {
+    let ufw = installed("ufw").then(|| ufw_status(authenticate));
+    let firewalld = installed("firewall-cmd").then(firewalld_status);
+    let nftables = installed("nft").then(nftables_status);
+    match (ufw, firewalld, nftables) {
+        (Some(u), _, _) if u.enabled => u,
+        (_, Some(f), _) if f.enabled => f,
+        (_, _, Some(n)) if n.enabled => n,
+        // Installed but switched off
+        (Some(u), _, _) => u,
+        (_, Some(f), _) => f,
+        (_, _, Some(n)) => n,
+        _ => FirewallStatus { backend: "none".to_string(), enabled: false, default_policy: None, rules: Vec::new(), error: None },
+    }
+}
+
+// ufw needs root, so it goes through pkexec; firewalld asks polkit itself
+fn ufw(args: &[String]) -> Result<(), String> {
+    let mut cmd = Command::new("pkexec");
+    cmd.arg("ufw").args(args);
+    let output = cmd.output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+// Both the running and the permanent configuration, so the change takes effect now and survives a reload
+fn firewall_cmd(arg: &str) -> Result<(), String> {
+    run("firewall-cmd", &[arg])?;
+    run("firewall-cmd", &["--permanent", arg]).map(|_| ())
+}
+
+// --- Commands ---
+
+/// Detects the active firewall frontend (ufw, then firewalld, then plain nftables) and lists its
+/// rules. ufw's rules are only listed through pkexec when `authenticate` is set.
+#[tauri::command]
+pub async fn get_firewall(authenticate: Option<bool>) -> Result<FirewallStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || detect(authenticate.unwrap_or(false)))
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Allows or denies incoming traffic to `port`, optionally only for one `protocol` (tcp/udp)
+/// and `source` address or CIDR range.
+#[tauri::command]
+pub async fn add_firewall_rule(action: String, port: u16, protocol: Option<String>, source: Option<String>) -> Result<(), String> {
+    if action != "allow" && action != "deny" {
+        return Err(format!("Unknown action: {}", action));
+    }
+    let protocol = protocol.filter(|p| !p.is_empty());
+    if let Some(p) = protocol.as_deref().filter(|p| *p != "tcp" && *p != "udp") {
+        return Err(format!("Unknown protocol: {}", p));
+    }
+    let source = source.filter(|s| !s.is_empty());
+    let family = match &source {
+        Some(s) => Some(s.split('/').next().unwrap_or_default().parse::<IpAddr>()
+            .map_err(|_| format!("Invalid source address: {}", s))?),
+        None => None,
+    };
+
+    tauri::async_runtime::spawn_blocking(move || match detect(false).backend.as_str() {
+        "ufw" => {
+            // ufw <allow|deny> [from SRC] to any port P [proto X]
+            let mut args = vec![action];
+            if let Some(s) = source {
+                args.extend(["from".to_string(), s]);
+            }
+            args.extend(["to".to_string(), "any".to_string(), "port".to_string(), port.to_string()]);
+            if let Some(p) = protocol {
+                args.extend(["proto".to_string(), p]);
+            }
+            ufw(&args)
+        }
+        "firewalld" => {
+            let protocols = protocol.map(|p| vec![p]).unwrap_or_else(|| vec!["tcp".to_string(), "udp".to_string()]);
+            for proto in protocols {
+                if action == "allow" && source.is_none() {
+                    firewall_cmd(&format!("--add-port={}/{}", port, proto))?;
+                } else {
+                    // Denies and source matches need a rich rule
+                    let mut rule = String::from("rule");
+                    if let (Some(s), Some(ip)) = (&source, family) {
+                        rule += &format!(" family=\"{}\" source address=\"{}\"", if ip.is_ipv4() { "ipv4" } else { "ipv6" }, s);
+                    }
+                    rule += &format!(" port port=\"{}\" protocol=\"{}\" {}", port, proto, if action == "allow" { "accept" } else { "reject" });
+                    firewall_cmd(&format!("--add-rich-rule={}", rule))?;
+                }
+            }
+            Ok(())
+        }
+        other => Err(format!("Managing rules is not supported for {}", other)),
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Removes a rule from `get_firewall` by its `raw` spec, and for firewalld its `target`.
+#[tauri::command]
+pub async fn delete_firewall_rule(target: String, raw: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || match detect(false).backend.as_str() {
+        // By spec rather than number, which shifts whenever another rule comes or goes
+        "ufw" => {
+            let spec = split_spec(&raw);
+            if spec.is_empty() || parse_ufw_rule(&format!("ufw {}", raw)).is_none() {
+                return Err("Unrecognised ufw rule".to_string());
+            }
+            let mut args = vec!["delete".to_string()];
+            args.extend(spec);
+            ufw(&args)
+        }
+        "firewalld" => {
+            if raw.starts_with("rule ") {
+                firewall_cmd(&format!("--remove-rich-rule={}", raw))
+            } else if raw.starts_with("services:") {
+                firewall_cmd(&format!("--remove-service={}", target))
+            } else if raw.starts_with("ports:") {
+                firewall_cmd(&format!("--remove-port={}", target))
+            } else {
+                Err("Unrecognised firewalld rule".to_string())
+            }
+        }
+        other => Err(format!("Managing rules is not supported for {}", other)),
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
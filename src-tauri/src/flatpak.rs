@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::process::Command;
+
+#[derive(serde::Serialize)]
+pub struct PortalGrant {
+    /// Permission store table, e.g. "devices", "background", "notifications"
+    table: String,
+    /// Object within the table, e.g. "camera"
+    object: String,
+    permissions: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct FlatpakPermissions {
+    app: String,
+    name: String,
+    filesystems: Vec<String>,
+    devices: Vec<String>,
+    /// "network" and "ipc"
+    shared: Vec<String>,
+    sockets: Vec<String>,
+    portals: Vec<PortalGrant>,
+}
+
+#[derive(serde::Serialize)]
+pub struct FlatpakApp {
+    app: String,
+    name: String,
+    version: String,
+    branch: String,
+    /// system or user
+    installation: String,
+    origin: String,
+    /// Installed size in bytes, excluding shared runtimes
+    size: Option<u64>,
+    /// Version the remote offers, when an update is available; can be empty when the
+    /// app doesn't publish versions
+    update: Option<String>,
+}
+
+fn flatpak(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("flatpak").args(args).output().map_err(|_| "flatpak is not installed".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// `flatpak info --show-permissions` prints a keyfile; values are ';'-separated lists
+fn context_list(keyfile: &str, key: &str) -> Vec<String> {
+    keyfile.lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix('='))
+        .map(|v| v.split(';').filter(|s| !s.is_empty()).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+// "Table\tObject\tApp\tPermissions\tData", one row per grant
+fn portal_grants() -> HashMap<String, Vec<PortalGrant>> {
+    let mut grants: HashMap<String, Vec<PortalGrant>> = HashMap::new();
+    for line in flatpak(&["permissions"]).unwrap_or_default().lines() {
+        let cols: Vec<&str> = line.split('\t').map(str::trim).collect();
+        if cols.len() < 4 || cols[2].is_empty() {
+            continue;
+        }
+        grants.entry(cols[2].to_string()).or_default().push(PortalGrant {
+            table: cols[0].to_string(),
+            object: cols[1].to_string(),
+            permissions: cols[3].to_string(),
+        });
+    }
+    grants
+}
+
+// flatpak prints sizes with GLib's decimal units: "1.2 GB", "940.5 kB"
+fn parse_size(text: &str) -> Option<u64> {
+    let (num, unit) = text.trim().split_once(|c: char| c.is_whitespace())?;
+    let mult = match unit.trim() {
+        "bytes" | "B" => 1.0,
+        "kB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+    Some((num.parse::<f64>().ok()? * mult) as u64)
+}
+
+fn valid_app_id(app: &str) -> bool {
+    app.contains('.') && app.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+}
+
+fn installation_flag(installation: &str) -> Result<&'static str, String> {
+    match installation {
+        "system" => Ok("--system"),
+        "user" => Ok("--user"),
+        other => Err(format!("Unknown installation: {}", other)),
+    }
+}
+
+// --- Commands ---
+
+/// Installed Flatpak apps with their size and any pending update.
+#[tauri::command]
+pub async fn get_flatpaks() -> Result<Vec<FlatpakApp>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let list = flatpak(&["list", "--app", "--columns=application,name,version,branch,installation,origin,size"])?;
+        // Uses the cached remote metadata; `flatpak update` refreshes it
+        let updates: HashMap<(String, String), String> = flatpak(&["remote-ls", "--updates", "--app", "--columns=application,branch,version"])
+            .unwrap_or_default().lines()
+            .filter_map(|l| {
+                let f: Vec<&str> = l.split('\t').collect();
+                Some(((f.first()?.to_string(), f.get(1)?.to_string()), f.get(2).unwrap_or(&"").to_string()))
+            })
+            .collect();
+        let mut apps: Vec<FlatpakApp> = list.lines().filter_map(|l| {
+            let f: Vec<&str> = l.split('\t').map(str::trim).collect();
+            let [app, name, version, branch, installation, origin, size] = f.as_slice() else { return None };
+            Some(FlatpakApp {
+                update: updates.get(&(app.to_string(), branch.to_string())).cloned(),
+                app: app.to_string(), name: name.to_string(), version: version.to_string(), branch: branch.to_string(),
+                installation: installation.to_string(), origin: origin.to_string(), size: parse_size(size),
+            })
+        }).collect();
+        apps.sort_by_key(|a| a.name.to_lowercase());
+        Ok(apps)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Updates one app. System installations authenticate through flatpak's own polkit rules.
+#[tauri::command]
+pub async fn update_flatpak(app: String, installation: String) -> Result<(), String> {
+    if !valid_app_id(&app) {
+        return Err(format!("Invalid app id: {}", app));
+    }
+    let flag = installation_flag(&installation)?;
+    tauri::async_runtime::spawn_blocking(move || flatpak(&["update", "-y", "--noninteractive", flag, &app]).map(|_| ()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Uninstalls an app, and with `delete_data` its files under ~/.var/app too.
+#[tauri::command]
+pub async fn remove_flatpak(app: String, installation: String, delete_data: bool) -> Result<(), String> {
+    if !valid_app_id(&app) {
+        return Err(format!("Invalid app id: {}", app));
+    }
+    let flag = installation_flag(&installation)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut args = vec!["uninstall", "-y", "--noninteractive", flag];
+        if delete_data {
+            args.push("--delete-data");
+        }
+        args.push(&app);
+        flatpak(&args).map(|_| ())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Static sandbox holes and runtime portal grants for each installed Flatpak app.
+#[tauri::command]
+pub async fn get_flatpak_permissions() -> Result<Vec<FlatpakPermissions>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let apps = flatpak(&["list", "--app", "--columns=application,name"])?;
+        let mut grants = portal_grants();
+        Ok(apps.lines()
+            .filter_map(|l| l.split_once('\t'))
+            .map(|(app, name)| {
+                let info = flatpak(&["info", "--show-permissions", app]).unwrap_or_default();
+                FlatpakPermissions {
+                    app: app.to_string(),
+                    name: name.trim().to_string(),
+                    filesystems: context_list(&info, "filesystems"),
+                    devices: context_list(&info, "devices"),
+                    shared: context_list(&info, "shared"),
+                    sockets: context_list(&info, "sockets"),
+                    portals: grants.remove(app).unwrap_or_default(),
+                }
+            })
+            .collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Revokes one permission. `kind` is filesystem, device, shared or socket (applied as a per-user
+/// `flatpak override`), or portal, where `value` is "table/object" in the permission store.
+#[tauri::command]
+pub async fn revoke_flatpak_permission(app: String, kind: String, value: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let flag = match kind.as_str() {
+            "filesystem" => "--nofilesystem",
+            "device" => "--nodevice",
+            "shared" => "--unshare",
+            "socket" => "--nosocket",
+            "portal" => {
+                let (table, object) = value.split_once('/').ok_or("Portal permission must be table/object")?;
+                return flatpak(&["permission-remove", table, object, &app]).map(|_| ());
+            }
+            other => return Err(format!("Unknown permission kind: {}", other)),
+        };
+        // Grants read "home:ro"; --nofilesystem takes the bare location and refuses a mode
+        let value = match kind.as_str() {
+            "filesystem" => ["ro", "rw", "create"].iter()
+                .find_map(|mode| value.strip_suffix(&format!(":{}", mode)))
+                .unwrap_or(&value),
+            _ => &value,
+        };
+        flatpak(&["override", "--user", &format!("{}={}", flag, value), &app]).map(|_| ())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,180 @@
+use std::ffi::{CString, OsStr};
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+use crate::history::unix_now;
+use crate::AppState;
+
+const POLL_TIMEOUT_MS: i32 = 200;
+const INOTIFY_MASK: u32 = libc::IN_CREATE | libc::IN_CLOSE_WRITE | libc::IN_DELETE
+    | libc::IN_MOVED_FROM | libc::IN_MOVED_TO;
+
+#[derive(Clone, serde::Serialize)]
+pub struct FsEvent {
+    time: u64,
+    kind: String,
+    path: String,
+    /// Only known when fanotify is permitted (needs CAP_SYS_ADMIN)
+    pid: Option<u32>,
+    process: Option<String>,
+}
+
+struct Watcher {
+    inotify: i32,
+    /// -1 when fanotify is unavailable
+    fanotify: i32,
+}
+
+impl Watcher {
+    fn open(dir: &Path) -> Result<Self, String> {
+        let c_dir = CString::new(dir.as_os_str().as_bytes()).map_err(|e| e.to_string())?;
+
+        let inotify = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if inotify < 0 || unsafe { libc::inotify_add_watch(inotify, c_dir.as_ptr(), INOTIFY_MASK) } < 0 {
+            let err = std::io::Error::last_os_error().to_string();
+            unsafe { libc::close(inotify) };
+            return Err(err);
+        }
+
+        // fanotify reports the writer's PID, but only for privileged callers
+        let mut fanotify = unsafe {
+            libc::fanotify_init(libc::FAN_CLASS_NOTIF | libc::FAN_CLOEXEC | libc::FAN_NONBLOCK,
+                (libc::O_RDONLY | libc::O_LARGEFILE) as u32)
+        };
+        if fanotify >= 0 {
+            let marked = unsafe {
+                libc::fanotify_mark(fanotify, libc::FAN_MARK_ADD,
+                    libc::FAN_CLOSE_WRITE | libc::FAN_EVENT_ON_CHILD, libc::AT_FDCWD, c_dir.as_ptr())
+            };
+            if marked < 0 {
+                unsafe { libc::close(fanotify) };
+                fanotify = -1;
+            }
+        }
+
+        Ok(Watcher { inotify, fanotify })
+    }
+
+    fn read_inotify(&self, dir: &Path, buf: &mut [u8], out: &mut Vec<FsEvent>) {
+        let n = unsafe { libc::read(self.inotify, buf.as_mut_ptr().cast(), buf.len()) };
+        let mut offset = 0;
+        while n > 0 && offset + std::mem::size_of::<libc::inotify_event>() <= n as usize {
+            let event: libc::inotify_event = unsafe { std::ptr::read_unaligned(buf[offset..].as_ptr().cast()) };
+            let name_start = offset + std::mem::size_of::<libc::inotify_event>();
+            let name = &buf[name_start..name_start + event.len as usize];
+            let name = OsStr::from_bytes(name.split(|b| *b == 0).next().unwrap_or_default());
+            offset = name_start + event.len as usize;
+
+            let kind = if event.mask & libc::IN_CREATE != 0 {
+                "create"
+            } else if event.mask & (libc::IN_DELETE | libc::IN_MOVED_FROM) != 0 {
+                "delete"
+            } else if event.mask & libc::IN_MOVED_TO != 0 {
+                "rename"
+            } else if self.fanotify < 0 {
+                "modify"
+            } else {
+                // fanotify reports the same write with a PID attached
+                continue;
+            };
+            out.push(FsEvent {
+                time: unix_now(),
+                kind: kind.to_string(),
+                path: dir.join(name).to_string_lossy().to_string(),
+                pid: None,
+                process: None,
+            });
+        }
+    }
+
+    fn read_fanotify(&self, buf: &mut [u8], out: &mut Vec<FsEvent>) {
+        let n = unsafe { libc::read(self.fanotify, buf.as_mut_ptr().cast(), buf.len()) };
+        let mut offset = 0;
+        while n > 0 && offset + std::mem::size_of::<libc::fanotify_event_metadata>() <= n as usize {
+            let meta: libc::fanotify_event_metadata = unsafe { std::ptr::read_unaligned(buf[offset..].as_ptr().cast()) };
+            offset += meta.event_len as usize;
+            if meta.fd < 0 {
+                continue;
+            }
+            let path = fs::read_link(format!("/proc/self/fd/{}", meta.fd))
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            unsafe { libc::close(meta.fd) };
+
+            let pid = meta.pid as u32;
+            out.push(FsEvent {
+                time: unix_now(),
+                kind: "modify".to_string(),
+                path,
+                pid: Some(pid),
+                process: fs::read_to_string(format!("/proc/{}/comm", pid)).ok().map(|c| c.trim().to_string()),
+            });
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.inotify);
+            if self.fanotify >= 0 {
+                libc::close(self.fanotify);
+            }
+        }
+    }
+}
+
+// --- Commands ---
+
+/// Streams changes to the direct children of `path` as `fs://event` events.
+#[tauri::command]
+pub fn watch_path(path: String, app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    let dir = Path::new(&path).to_path_buf();
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", path));
+    }
+    let watcher = Watcher::open(&dir)?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Some(prev) = state.fs_watch.lock().unwrap().replace(stop.clone()) {
+        prev.store(true, Ordering::Relaxed);
+    }
+
+    std::thread::spawn(move || {
+        let mut buf = vec![0u8; 64 * 1024];
+        let mut fds = vec![libc::pollfd { fd: watcher.inotify, events: libc::POLLIN, revents: 0 }];
+        if watcher.fanotify >= 0 {
+            fds.push(libc::pollfd { fd: watcher.fanotify, events: libc::POLLIN, revents: 0 });
+        }
+
+        while !stop.load(Ordering::Relaxed) {
+            let ready = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, POLL_TIMEOUT_MS) };
+            if ready <= 0 {
+                continue;
+            }
+            let mut events = Vec::new();
+            if fds[0].revents & libc::POLLIN != 0 {
+                watcher.read_inotify(&dir, &mut buf, &mut events);
+            }
+            if fds.len() > 1 && fds[1].revents & libc::POLLIN != 0 {
+                watcher.read_fanotify(&mut buf, &mut events);
+            }
+            for event in events {
+                let _ = app.emit_all("fs://event", event);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unwatch_path(state: State<AppState>) {
+    if let Some(stop) = state.fs_watch.lock().unwrap().take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::settings::{aggregate, Aggregation};
+
+const SAMPLE_WINDOW: Duration = Duration::from_millis(500);
+// How long a relaunched process gets to shut down cleanly
+const EXIT_TIMEOUT: Duration = Duration::from_secs(10);
+const EXIT_POLL: Duration = Duration::from_millis(100);
+
+/// Counters of one GPU; only amdgpu exposes cheap busy/VRAM counters, other drivers report None.
+pub struct GpuReading {
+    /// DRM card name, e.g. "card1"
+    pub name: String,
+    pub busy: Option<f32>,
+    /// (used, total) bytes
+    pub vram: Option<(u64, u64)>,
+}
+
+fn drm_devices() -> Vec<(String, PathBuf)> {
+    fs::read_dir("/sys/class/drm").into_iter().flatten().flatten()
+        .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+        // card0, card1, ... but not connectors like card0-DP-1
+        .filter(|(n, _)| n.starts_with("card") && !n.contains('-'))
+        .map(|(n, p)| (n, p.join("device")))
+        .collect()
+}
+
+fn read_u64(path: PathBuf) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// Reading a runtime-suspended GPU's counters wakes it up, which on hybrid laptops costs
+// watts for nothing; reporting it as idle is just as true
+fn suspended(device: &Path) -> bool {
+    fs::read_to_string(device.join("power/runtime_status")).is_ok_and(|s| s.trim() == "suspended")
+}
+
+/// Every card by name, with counters for those that are awake.
+pub fn readings() -> Vec<GpuReading> {
+    let mut gpus: Vec<GpuReading> = drm_devices().into_iter().map(|(name, d)| {
+        if suspended(&d) {
+            return GpuReading { name, busy: None, vram: None };
+        }
+        GpuReading {
+            name,
+            busy: read_u64(d.join("gpu_busy_percent")).map(|v| v as f32),
+            vram: read_u64(d.join("mem_info_vram_used")).zip(read_u64(d.join("mem_info_vram_total"))),
+        }
+    }).collect();
+    gpus.sort_by(|a, b| a.name.cmp(&b.name));
+    gpus
+}
+
+/// DRM card names, without touching the cards.
+pub fn card_names() -> Vec<String> {
+    let mut names: Vec<String> = drm_devices().into_iter().map(|(name, _)| name).collect();
+    names.sort();
+    names
+}
+
+/// Busy percentage across GPUs, folded by `mode`.
+pub fn busy(gpus: &[GpuReading], mode: &Aggregation, device: Option<&str>) -> Option<f32> {
+    let values: Vec<(String, f32)> = gpus.iter().filter_map(|g| Some((g.name.clone(), g.busy?))).collect();
+    aggregate(mode, device, &values)
+}
+
+/// (used, total) VRAM: the fullest GPU for max, the combined pool for avg.
+pub fn vram(gpus: &[GpuReading], mode: &Aggregation, device: Option<&str>) -> Option<(u64, u64)> {
+    let with_vram: Vec<(&str, (u64, u64))> = gpus.iter().filter_map(|g| Some((g.name.as_str(), g.vram?))).collect();
+    let fullest = || with_vram.iter()
+        .max_by(|a, b| (a.1 .0 as f64 / a.1 .1.max(1) as f64).total_cmp(&(b.1 .0 as f64 / b.1 .1.max(1) as f64)))
+        .map(|g| g.1);
+    match mode {
+        Aggregation::Max => fullest(),
+        Aggregation::Avg => (!with_vram.is_empty())
+            .then(|| with_vram.iter().fold((0, 0), |(u, t), g| (u + g.1 .0, t + g.1 .1))),
+        Aggregation::Device => with_vram.iter().find(|g| Some(g.0) == device).map(|g| g.1).or_else(fullest),
+    }
+}
+
+/// Busiest GPU, for callers without settings at hand.
+pub fn busy_percent() -> Option<f32> {
+    busy(&readings(), &Aggregation::Max, None)
+}
+
+#[derive(serde::Serialize)]
+pub struct GpuProcess {
+    pid: u32,
+    name: String,
+    /// Kernel driver, e.g. amdgpu or i915
+    driver: String,
+    /// PCI address of the GPU, which tells integrated and discrete apart
+    device: String,
+    /// Busiest engine over the sample window
+    busy_percent: f32,
+    vram_bytes: u64,
+}
+
+#[derive(Default)]
+struct DrmClient {
+    pid: u32,
+    driver: String,
+    device: String,
+    /// Nanoseconds of GPU time per engine
+    engines: HashMap<String, u64>,
+    vram_bytes: u64,
+}
+
+// DRM fdinfo (Documentation/gpu/drm-usage-stats.rst): every open DRM fd reports its client's
+// "drm-engine-<name>: <ns> ns". Several fds can share one client, so clients are keyed by id.
+fn drm_clients() -> HashMap<(u32, String), DrmClient> {
+    let mut clients = HashMap::new();
+    for proc_entry in fs::read_dir("/proc").into_iter().flatten().flatten() {
+        let Ok(pid) = proc_entry.file_name().to_string_lossy().parse::<u32>() else { continue };
+        for fd in fs::read_dir(proc_entry.path().join("fdinfo")).into_iter().flatten().flatten() {
+            let Ok(info) = fs::read_to_string(fd.path()) else { continue };
+            if !info.contains("drm-client-id:") {
+                continue;
+            }
+            let mut client = DrmClient { pid, ..Default::default() };
+            let mut id = String::new();
+            for line in info.lines() {
+                let Some((key, value)) = line.split_once(':') else { continue };
+                let value = value.trim();
+                match key {
+                    "drm-client-id" => id = value.to_string(),
+                    "drm-driver" => client.driver = value.to_string(),
+                    "drm-pdev" => client.device = value.to_string(),
+                    "drm-memory-vram" => {
+                        client.vram_bytes = value.trim_end_matches(" KiB").parse::<u64>().unwrap_or(0) * 1024;
+                    }
+                    _ => {
+                        if let Some(engine) = key.strip_prefix("drm-engine-") {
+                            let ns = value.trim_end_matches(" ns").parse().unwrap_or(0);
+                            client.engines.insert(engine.to_string(), ns);
+                        }
+                    }
+                }
+            }
+            clients.insert((pid, format!("{}:{}", client.device, id)), client);
+        }
+    }
+    clients
+}
+
+fn gpu_processes() -> Vec<GpuProcess> {
+    let before = drm_clients();
+    let start = Instant::now();
+    std::thread::sleep(SAMPLE_WINDOW);
+    let after = drm_clients();
+    let window_ns = start.elapsed().as_nanos() as f64;
+
+    let mut by_pid: HashMap<u32, (GpuProcess, HashMap<String, u64>)> = HashMap::new();
+    for (key, client) in after {
+        let entry = by_pid.entry(client.pid).or_insert_with(|| (GpuProcess {
+            pid: client.pid,
+            name: crate::sockets::process_name(client.pid).unwrap_or_default(),
+            driver: client.driver.clone(),
+            device: client.device.clone(),
+            busy_percent: 0.0,
+            vram_bytes: 0,
+        }, HashMap::new()));
+        entry.0.vram_bytes += client.vram_bytes;
+        let was = before.get(&key);
+        for (engine, ns) in &client.engines {
+            let prev = was.and_then(|w| w.engines.get(engine)).copied().unwrap_or(*ns);
+            *entry.1.entry(engine.clone()).or_default() += ns.saturating_sub(prev);
+        }
+    }
+
+    let mut procs: Vec<GpuProcess> = by_pid.into_values().map(|(mut p, engines)| {
+        let busiest = engines.values().copied().max().unwrap_or(0);
+        p.busy_percent = (busiest as f64 / window_ns * 100.0).min(100.0) as f32;
+        p
+    }).collect();
+    procs.sort_by(|a, b| b.busy_percent.total_cmp(&a.busy_percent).then(b.vram_bytes.cmp(&a.vram_bytes)));
+    procs
+}
+
+// Environment that routes rendering to one GPU on a hybrid system
+fn offload_env(target: &str) -> Result<&'static [(&'static str, &'static str)], String> {
+    match target {
+        "integrated" => Ok(&[("DRI_PRIME", "0")]),
+        "discrete" => Ok(&[("DRI_PRIME", "1")]),
+        "nvidia" => Ok(&[
+            ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+            ("__GLX_VENDOR_LIBRARY_NAME", "nvidia"),
+            ("__VK_LAYER_NV_optimus", "NVIDIA_only"),
+        ]),
+        other => Err(format!("Unknown GPU target: {}", other)),
+    }
+}
+
+const OFFLOAD_VARS: [&str; 4] = ["DRI_PRIME", "__NV_PRIME_RENDER_OFFLOAD", "__GLX_VENDOR_LIBRARY_NAME", "__VK_LAYER_NV_optimus"];
+
+// --- Commands ---
+
+/// GPU time and VRAM per process over a short window, from DRM fdinfo. Drivers without
+/// fdinfo support (e.g. the proprietary NVIDIA driver) don't show up.
+#[tauri::command]
+pub async fn get_gpu_processes() -> Result<Vec<GpuProcess>, String> {
+    tauri::async_runtime::spawn_blocking(gpu_processes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Field 22 of /proc/<pid>/stat, after the parenthesised name that may contain spaces;
+// None once the process is gone or a zombie
+fn start_time(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    if fields.first() == Some(&"Z") {
+        return None;
+    }
+    fields.get(19)?.parse().ok()
+}
+
+fn relaunch(pid: u32, target: &str) -> Result<u32, String> {
+    let env = offload_env(target)?;
+    let split = |bytes: Vec<u8>| -> Vec<String> {
+        bytes.split(|b| *b == 0).filter(|a| !a.is_empty()).map(|a| String::from_utf8_lossy(a).to_string()).collect()
+    };
+    let args = split(fs::read(format!("/proc/{}/cmdline", pid)).map_err(|_| "Process has exited".to_string())?);
+    // environ is only readable for our own processes, which is also all we should restart
+    let environ = split(fs::read(format!("/proc/{}/environ", pid)).map_err(|_| "Can only relaunch your own processes".to_string())?);
+    let cwd = fs::read_link(format!("/proc/{}/cwd", pid)).map_err(|e| e.to_string())?;
+    let (program, rest) = args.split_first().ok_or("Kernel threads can't be relaunched")?;
+    let started = start_time(pid).ok_or("Process has exited")?;
+
+    let mut cmd = Command::new(program);
+    cmd.args(rest).current_dir(cwd).env_clear();
+    for var in &environ {
+        if let Some((k, v)) = var.split_once('=') {
+            if !OFFLOAD_VARS.contains(&k) {
+                cmd.env(k, v);
+            }
+        }
+    }
+    cmd.envs(env.iter().copied());
+    // Its own session with nothing of ours attached, so it outlives glassview
+    cmd.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+    unsafe {
+        cmd.pre_exec(|| {
+            libc::setsid();
+            Ok(())
+        });
+    }
+
+    if unsafe { libc::kill(pid as i32, libc::SIGTERM) } != 0 {
+        return Err("Failed to stop the process".to_string());
+    }
+    // Single-instance apps refuse to start while the old copy is still around; a changed
+    // start time means the pid now belongs to someone else
+    let stopping = Instant::now();
+    while start_time(pid) == Some(started) {
+        if stopping.elapsed() >= EXIT_TIMEOUT {
+            return Err("The process did not exit; it was left running".to_string());
+        }
+        std::thread::sleep(EXIT_POLL);
+    }
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let new_pid = child.id();
+    // Reaped when it exits, so it never lingers as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(new_pid)
+}
+
+/// Restarts one of the user's processes with its rendering offloaded to `target`
+/// ("integrated", "discrete" or "nvidia"); GPU placement can't change while it runs.
+/// Returns the new pid.
+#[tauri::command]
+pub async fn relaunch_on_gpu(pid: u32, target: String) -> Result<u32, String> {
+    tauri::async_runtime::spawn_blocking(move || relaunch(pid, &target))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Sets an NVIDIA GPU's compute mode ("default", "exclusive_process" or "prohibited"), e.g. to keep
+/// background CUDA jobs off a GPU. Device-wide; needs root, so it asks through pkexec.
+#[tauri::command]
+pub async fn set_nvidia_compute_mode(gpu: u32, mode: String) -> Result<(), String> {
+    let mode = match mode.as_str() {
+        "default" => "DEFAULT",
+        "exclusive_process" => "EXCLUSIVE_PROCESS",
+        "prohibited" => "PROHIBITED",
+        other => return Err(format!("Unknown compute mode: {}", other)),
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = Command::new("pkexec").arg("nvidia-smi").arg("-i").arg(gpu.to_string()).arg("-c").arg(mode)
+            .output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            // nvidia-smi reports its own errors on stdout
+            return Err(match output.status.code() {
+                Some(126) | Some(127) => "Authentication was cancelled".to_string(),
+                _ => String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            });
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::State;
+
+use crate::AppState;
+
+// A collector that has not succeeded within this many intervals is reported stale
+const STALE_AFTER_INTERVALS: u64 = 3;
+
+pub fn unix_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0)
+}
+
+/// A payload with the time it was collected, so the UI can tell fresh data from a frozen panel.
+#[derive(Clone, serde::Serialize)]
+pub struct Sampled<T> {
+    /// Milliseconds since the Unix epoch
+    sampled_at: u64,
+    /// How often the collector refreshes; absent for on-demand queries
+    interval_ms: Option<u64>,
+    data: T,
+}
+
+impl<T> Sampled<T> {
+    pub fn new(sampled_at: u64, interval_ms: Option<u64>, data: T) -> Self {
+        Sampled { sampled_at, interval_ms, data }
+    }
+
+    /// Stamped with the current time, for data collected on request.
+    pub fn now(data: T) -> Self {
+        Sampled::new(unix_millis(), None, data)
+    }
+}
+
+#[derive(Clone, Default, serde::Serialize)]
+pub struct CollectorHealth {
+    name: String,
+    last_success: Option<u64>,
+    last_error: Option<String>,
+    last_error_at: Option<u64>,
+    interval_ms: Option<u64>,
+    /// No success within a few intervals, or the latest attempt failed
+    stale: bool,
+}
+
+/// Last outcome of each background collector and query, keyed by collector name.
+#[derive(Default)]
+pub struct Health {
+    collectors: BTreeMap<String, CollectorHealth>,
+}
+
+impl Health {
+    fn entry(&mut self, name: &str) -> &mut CollectorHealth {
+        self.collectors.entry(name.to_string())
+            .or_insert_with(|| CollectorHealth { name: name.to_string(), ..Default::default() })
+    }
+
+    pub fn ok(&mut self, name: &str, interval_ms: Option<u64>) {
+        let c = self.entry(name);
+        c.last_success = Some(unix_millis());
+        c.interval_ms = interval_ms;
+    }
+
+    pub fn fail(&mut self, name: &str, error: &str) {
+        let c = self.entry(name);
+        c.last_error = Some(error.to_string());
+        c.last_error_at = Some(unix_millis());
+    }
+
+    pub fn record<T>(&mut self, name: &str, interval_ms: Option<u64>, result: &Result<T, String>) {
+        match result {
+            Ok(_) => self.ok(name, interval_ms),
+            Err(e) => self.fail(name, e),
+        }
+    }
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_collector_health(state: State<AppState>) -> Vec<CollectorHealth> {
+    let now = unix_millis();
+    state.health.lock().unwrap().collectors.values().cloned().map(|mut c| {
+        let failing = c.last_error_at > c.last_success;
+        let overdue = match (c.last_success, c.interval_ms) {
+            (Some(at), Some(interval)) => now.saturating_sub(at) > interval * STALE_AFTER_INTERVALS,
+            _ => false,
+        };
+        c.stale = failing || overdue;
+        c
+    }).collect()
+}
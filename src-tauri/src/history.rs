@@ -0,0 +1,188 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::{ProcessExt, System, SystemExt};
+use tauri::State;
+
+use crate::{config, AppState, ProcInfo, SystemStats};
+
+// One hour at the default one-second resolution
+const HISTORY_CAPACITY: usize = 3600;
+const LEDGER_FILE: &str = "offenders.toml";
+const LEDGER_DAYS: u64 = 7;
+const LEDGER_SAVE_INTERVAL_SECS: u64 = 300;
+const OFFENDER_LIMIT: usize = 20;
+
+#[derive(Clone, serde::Serialize)]
+pub struct Sample {
+    timestamp: u64,
+    cpu_util: f32,
+    mem_used: u64,
+    mem_total: u64,
+    net_in: u64,
+}
+
+#[derive(serde::Serialize)]
+struct MetricsExport<'a> {
+    history: Vec<&'a Sample>,
+    processes: Vec<ProcInfo>,
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+struct ProcessTotals {
+    cpu_secs: f64,
+    peak_mem: u64,
+    disk_bytes: u64,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct DayTotals {
+    /// Days since the Unix epoch
+    day: u64,
+    processes: HashMap<String, ProcessTotals>,
+}
+
+/// Per-process resource totals, bucketed by day and kept for a week across restarts.
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+pub struct Ledger {
+    days: Vec<DayTotals>,
+    #[serde(skip)]
+    last_saved: u64,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct Offender {
+    name: String,
+    cpu_secs: f64,
+    peak_mem: u64,
+    disk_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct TopOffenders {
+    by_cpu: Vec<Offender>,
+    by_memory: Vec<Offender>,
+    by_disk: Vec<Offender>,
+}
+
+impl Ledger {
+    pub fn load() -> Self {
+        config::load(LEDGER_FILE)
+    }
+
+    /// Adds the work each process did over the last `elapsed` seconds, keyed by process name
+    /// so restarts of the same program accumulate. Returns whether the totals are due to be
+    /// written out with `save`.
+    pub fn record(&mut self, sys: &System, elapsed: f64) -> bool {
+        let now = unix_now();
+        let today = now / 86400;
+        self.days.retain(|d| d.day + LEDGER_DAYS > today);
+        if self.days.last().is_none_or(|d| d.day != today) {
+            self.days.push(DayTotals { day: today, processes: HashMap::new() });
+        }
+        let Some(day) = self.days.last_mut() else { return false };
+
+        for process in sys.processes().values() {
+            let disk = process.disk_usage();
+            let totals = day.processes.entry(process.name().to_string()).or_default();
+            totals.cpu_secs += process.cpu_usage() as f64 / 100.0 * elapsed;
+            totals.peak_mem = totals.peak_mem.max(process.memory());
+            totals.disk_bytes += disk.read_bytes + disk.written_bytes;
+        }
+
+        now.saturating_sub(self.last_saved) >= LEDGER_SAVE_INTERVAL_SECS
+    }
+
+    pub fn save(&mut self) {
+        if config::save(LEDGER_FILE, self).is_ok() {
+            self.last_saved = unix_now();
+        }
+    }
+
+    fn totals_since(&self, since_day: u64) -> Vec<Offender> {
+        let mut merged: HashMap<&str, ProcessTotals> = HashMap::new();
+        for day in self.days.iter().filter(|d| d.day >= since_day) {
+            for (name, t) in &day.processes {
+                let m = merged.entry(name).or_default();
+                m.cpu_secs += t.cpu_secs;
+                m.peak_mem = m.peak_mem.max(t.peak_mem);
+                m.disk_bytes += t.disk_bytes;
+            }
+        }
+        merged.into_iter()
+            .map(|(name, t)| Offender { name: name.to_string(), cpu_secs: t.cpu_secs, peak_mem: t.peak_mem, disk_bytes: t.disk_bytes })
+            .collect()
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+pub fn record(history: &mut VecDeque<Sample>, stats: &SystemStats) {
+    if history.len() >= HISTORY_CAPACITY {
+        history.pop_front();
+    }
+    history.push_back(Sample {
+        timestamp: unix_now(),
+        cpu_util: stats.cpu_util,
+        mem_used: stats.mem_used,
+        mem_total: stats.mem_total,
+        net_in: stats.net_in,
+    });
+}
+
+fn to_csv(history: &[&Sample], procs: &[ProcInfo]) -> String {
+    let mut out = String::from("timestamp,cpu_util,mem_used,mem_total,net_in\n");
+    for s in history {
+        let _ = writeln!(out, "{},{:.2},{},{},{}", s.timestamp, s.cpu_util, s.mem_used, s.mem_total, s.net_in);
+    }
+    out.push_str("\npid,name,user,status,cpu,mem\n");
+    for p in procs {
+        // Process names may contain commas; quote them
+        let _ = writeln!(out, "{},\"{}\",{},{},{:.2},{}", p.id, p.name.replace('"', "\"\""), p.user, p.status, p.cpu, p.mem);
+    }
+    out
+}
+
+// --- Commands ---
+
+/// `range` is the number of seconds of history to include, counting back from now.
+#[tauri::command]
+pub fn export_metrics(range: u64, format: String, path: String, state: State<AppState>) -> Result<(), String> {
+    let procs = state.snapshot.lock().unwrap().processes.clone();
+    let history = state.history.lock().unwrap();
+    let since = unix_now().saturating_sub(range);
+    let samples: Vec<&Sample> = history.iter().filter(|s| s.timestamp >= since).collect();
+
+    let body = match format.as_str() {
+        "csv" => to_csv(&samples, &procs),
+        "json" => serde_json::to_string_pretty(&MetricsExport { history: samples, processes: procs })
+            .map_err(|e| e.to_string())?,
+        other => return Err(format!("Unsupported export format: {}", other)),
+    };
+    fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Processes that used the most CPU time, memory and disk I/O over the last `period` ("day" or "week").
+#[tauri::command]
+pub fn get_top_offenders(period: String, state: State<AppState>) -> Result<TopOffenders, String> {
+    let days = match period.as_str() {
+        "day" => 1,
+        "week" => LEDGER_DAYS,
+        other => return Err(format!("Unsupported period: {}", other)),
+    };
+    let since_day = (unix_now() / 86400 + 1).saturating_sub(days);
+    let ledger = state.offenders.lock().unwrap();
+
+    let mut all = ledger.totals_since(since_day);
+    let take = |all: &[Offender]| all.iter().take(OFFENDER_LIMIT).cloned().collect::<Vec<_>>();
+    all.sort_by(|a, b| b.cpu_secs.total_cmp(&a.cpu_secs));
+    let by_cpu = take(&all);
+    all.sort_by_key(|o| std::cmp::Reverse(o.peak_mem));
+    let by_memory = take(&all);
+    all.sort_by_key(|o| std::cmp::Reverse(o.disk_bytes));
+    let by_disk = take(&all);
+    Ok(TopOffenders { by_cpu, by_memory, by_disk })
+}
@@ -0,0 +1,120 @@
+use std::fs;
+use zbus::blocking::Connection;
+use zbus::dbus_proxy;
+
+use crate::network::valid_hostname;
+
+// The kernel's limit, and so systemd's, for the static hostname
+const MAX_HOSTNAME: usize = 64;
+
+#[dbus_proxy(
+    interface = "org.freedesktop.hostname1",
+    default_service = "org.freedesktop.hostname1",
+    default_path = "/org/freedesktop/hostname1"
+)]
+trait Hostname1 {
+    /// `interactive` lets polkit ask for a password instead of refusing outright
+    fn set_static_hostname(&self, hostname: &str, interactive: bool) -> zbus::Result<()>;
+    fn set_pretty_hostname(&self, hostname: &str, interactive: bool) -> zbus::Result<()>;
+    #[dbus_proxy(property)]
+    fn hostname(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn static_hostname(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn pretty_hostname(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn chassis(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn icon_name(&self) -> zbus::Result<String>;
+}
+
+#[derive(serde::Serialize)]
+pub struct MachineIdentity {
+    /// The kernel's current hostname, which DHCP or a container may have set
+    hostname: String,
+    /// From /etc/hostname
+    static_hostname: Option<String>,
+    /// Free-form name shown to people, e.g. "Anna's Laptop"
+    pretty_hostname: Option<String>,
+    machine_id: Option<String>,
+    /// desktop, laptop, server, vm, container, ...
+    chassis: Option<String>,
+    icon_name: Option<String>,
+}
+
+fn read(path: &str) -> Option<String> {
+    fs::read_to_string(path).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn hostnamed(conn: &Connection) -> Result<Hostname1ProxyBlocking<'_>, String> {
+    Hostname1ProxyBlocking::new(conn).map_err(|e| e.to_string())
+}
+
+// Without hostnamed (minimal systems, some containers) the files still tell most of it
+fn identity() -> MachineIdentity {
+    let non_empty = |v: zbus::Result<String>| v.ok().filter(|v| !v.is_empty());
+    let conn = Connection::system().ok();
+    let proxy = conn.as_ref().and_then(|c| hostnamed(c).ok());
+    MachineIdentity {
+        hostname: proxy.as_ref().and_then(|p| non_empty(p.hostname()))
+            .or_else(|| read("/proc/sys/kernel/hostname"))
+            .unwrap_or_default(),
+        static_hostname: proxy.as_ref().and_then(|p| non_empty(p.static_hostname())).or_else(|| read("/etc/hostname")),
+        pretty_hostname: proxy.as_ref().and_then(|p| non_empty(p.pretty_hostname())),
+        machine_id: read("/etc/machine-id"),
+        chassis: proxy.as_ref().and_then(|p| non_empty(p.chassis())),
+        icon_name: proxy.as_ref().and_then(|p| non_empty(p.icon_name())),
+    }
+}
+
+// "Anna's Laptop" becomes "annas-laptop", as hostnamectl does
+fn static_from_pretty(pretty: &str) -> String {
+    let mut name = String::new();
+    for c in pretty.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if (c.is_whitespace() || c == '-' || c == '_' || c == '.') && !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    name.truncate(MAX_HOSTNAME);
+    name.trim_end_matches('-').to_string()
+}
+
+fn valid_static(name: &str) -> bool {
+    name.len() <= MAX_HOSTNAME && valid_hostname(name)
+        && name.split('.').all(|label| !label.is_empty() && !label.starts_with('-') && !label.ends_with('-'))
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub async fn get_machine_identity() -> MachineIdentity {
+    tauri::async_runtime::spawn_blocking(identity)
+        .await
+        .unwrap_or(MachineIdentity { hostname: String::new(), static_hostname: None, pretty_hostname: None, machine_id: None, chassis: None, icon_name: None })
+}
+
+/// Sets `name` as the static hostname if it is a valid one, clearing the pretty name; anything
+/// else becomes the pretty name with a static hostname derived from it. hostnamed asks polkit.
+#[tauri::command]
+pub async fn set_hostname(name: String) -> Result<MachineIdentity, String> {
+    let name = name.trim().to_string();
+    let (static_name, pretty) = if valid_static(&name) {
+        (name, String::new())
+    } else {
+        (static_from_pretty(&name), name)
+    };
+    if static_name.is_empty() {
+        return Err("The name needs at least one letter or digit".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = Connection::system().map_err(|e| e.to_string())?;
+        let proxy = hostnamed(&conn)?;
+        proxy.set_static_hostname(&static_name, true).map_err(|e| e.to_string())?;
+        proxy.set_pretty_hostname(&pretty, true).map_err(|e| e.to_string())?;
+        Ok(identity())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+// Where a writable or ownerless file is a privilege escalation or tampering risk
+const SENSITIVE_PATHS: [&str; 10] = ["/etc", "/bin", "/sbin", "/usr", "/lib", "/lib64", "/boot", "/opt", "/root", "/var/spool/cron"];
+// Enough to act on; a broken system would otherwise return millions
+const MAX_FINDINGS: usize = 500;
+
+#[derive(serde::Serialize)]
+pub struct HygieneFinding {
+    path: String,
+    /// world_writable, orphaned_uid or orphaned_gid
+    kind: String,
+    /// Permission bits, e.g. 0o777
+    mode: u32,
+    uid: u32,
+    gid: u32,
+    is_dir: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct HygieneReport {
+    findings: Vec<HygieneFinding>,
+    /// Files and directories looked at
+    scanned: u64,
+    /// Stopped at MAX_FINDINGS
+    truncated: bool,
+}
+
+/// Resolves ids through NSS, so LDAP or systemd-homed users aren't reported as orphans.
+struct Ids {
+    known_users: HashSet<u32>,
+    known_groups: HashSet<u32>,
+    checked: HashMap<(&'static str, u32), bool>,
+}
+
+fn ids_in(file: &str) -> HashSet<u32> {
+    fs::read_to_string(file).unwrap_or_default().lines()
+        .filter_map(|l| l.split(':').nth(2)?.parse().ok())
+        .collect()
+}
+
+impl Ids {
+    fn new() -> Self {
+        Ids { known_users: ids_in("/etc/passwd"), known_groups: ids_in("/etc/group"), checked: HashMap::new() }
+    }
+
+    fn exists(&mut self, db: &'static str, id: u32) -> bool {
+        let local = if db == "passwd" { &self.known_users } else { &self.known_groups };
+        if local.contains(&id) {
+            return true;
+        }
+        *self.checked.entry((db, id)).or_insert_with(|| {
+            // getent exits 2 when the id is unknown; treat a missing getent as known to avoid false alarms
+            Command::new("getent").arg(db).arg(id.to_string()).output().map(|o| o.status.code() != Some(2)).unwrap_or(true)
+        })
+    }
+}
+
+fn scan() -> HygieneReport {
+    let mut ids = Ids::new();
+    let mut report = HygieneReport { findings: Vec::new(), scanned: 0, truncated: false };
+    let mut stack: Vec<(PathBuf, u64)> = SENSITIVE_PATHS.iter()
+        .filter_map(|p| fs::symlink_metadata(p).ok().filter(|m| m.is_dir()).map(|m| (PathBuf::from(p), m.dev())))
+        .collect();
+
+    while let Some((dir, dev)) = stack.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            // lstat: a symlink's own mode is always 777 and says nothing
+            let Ok(meta) = entry.metadata() else { continue };
+            if meta.file_type().is_symlink() {
+                continue;
+            }
+            report.scanned += 1;
+            let path = entry.path();
+            let mode = meta.mode() & 0o7777;
+            let mut push = |kind: &str| report.findings.push(HygieneFinding {
+                path: path.to_string_lossy().to_string(), kind: kind.to_string(),
+                mode, uid: meta.uid(), gid: meta.gid(), is_dir: meta.is_dir(),
+            });
+            // Sticky directories like /var/tmp are meant to be shared
+            if mode & 0o002 != 0 && !(meta.is_dir() && mode & 0o1000 != 0) {
+                push("world_writable");
+            }
+            if !ids.exists("passwd", meta.uid()) {
+                push("orphaned_uid");
+            } else if !ids.exists("group", meta.gid()) {
+                push("orphaned_gid");
+            }
+            if report.findings.len() >= MAX_FINDINGS {
+                report.truncated = true;
+                return report;
+            }
+            // Stay on the starting filesystem, which keeps /proc-like and network mounts out
+            if meta.is_dir() && meta.dev() == dev {
+                stack.push((path, dev));
+            }
+        }
+    }
+    report
+}
+
+// --- Commands ---
+
+/// World-writable files and directories, and files owned by users or groups that no longer
+/// exist, under system paths such as /etc and /usr.
+#[tauri::command]
+pub async fn get_file_hygiene() -> Result<HygieneReport, String> {
+    tauri::async_runtime::spawn_blocking(scan)
+        .await
+        .map_err(|e| e.to_string())
+}
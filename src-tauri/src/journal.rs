@@ -0,0 +1,350 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError, TrySendError};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::services::Scope;
+use crate::AppState;
+
+const MAX_QUERY_LIMIT: u32 = 1000;
+// Entries queued between the reader and the emitter; beyond this they are dropped
+const STREAM_QUEUE: usize = 2000;
+const STREAM_BATCH: usize = 200;
+const STREAM_FLUSH: Duration = Duration::from_millis(250);
+
+#[derive(Clone, serde::Serialize)]
+pub struct LogEntry {
+    time: String,
+    msg: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct JournalBatch {
+    entries: Vec<LogEntry>,
+    /// Entries discarded since the previous batch because the window fell behind
+    dropped: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct JournalEntry {
+    /// Microseconds since the Unix epoch
+    timestamp: u64,
+    unit: String,
+    /// syslog level, 0 (emerg) to 7 (debug)
+    priority: u8,
+    message: String,
+    cursor: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct JournalPage {
+    entries: Vec<JournalEntry>,
+    /// Pass back as `cursor` to fetch the next (older) page; absent at the end
+    next_cursor: Option<String>,
+}
+
+// MESSAGE is a byte array instead of a string when it is not valid UTF-8
+pub fn field_text(v: &serde_json::Value) -> String {
+    match v {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().filter_map(|b| b.as_u64().map(|b| b as u8)).collect();
+            String::from_utf8_lossy(&bytes).to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+fn parse_json_entry(line: &str) -> Option<JournalEntry> {
+    let v: serde_json::Value = serde_json::from_str(line).ok()?;
+    let text = |key: &str| v.get(key).map(field_text).filter(|s| !s.is_empty());
+    Some(JournalEntry {
+        timestamp: text("__REALTIME_TIMESTAMP")?.parse().ok()?,
+        unit: text("_SYSTEMD_UNIT").or_else(|| text("SYSLOG_IDENTIFIER")).or_else(|| text("_COMM")).unwrap_or_default(),
+        priority: text("PRIORITY").and_then(|p| p.parse().ok()).unwrap_or(6),
+        message: text("MESSAGE").unwrap_or_default(),
+        cursor: text("__CURSOR")?,
+    })
+}
+
+// "2024-05-01T12:00:00+0200 host sshd[123]: message" from --output=short-iso
+fn parse_line(line: &str) -> Option<LogEntry> {
+    // journalctl's own notes, e.g. "-- No entries --"
+    if line.starts_with("-- ") {
+        return None;
+    }
+    let (time, msg) = line.split_once(' ').unwrap_or(("", line));
+    Some(LogEntry { time: time.to_string(), msg: msg.to_string() })
+}
+
+#[derive(serde::Serialize)]
+pub struct JournalUsage {
+    bytes: u64,
+}
+
+// journalctl prints sizes with binary suffixes, e.g. "1.2G" or "512.0K"
+pub fn parse_size(text: &str) -> Option<u64> {
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (num, unit) = text.split_at(split);
+    let shift = match unit {
+        "" | "B" => 0,
+        "K" => 10,
+        "M" => 20,
+        "G" => 30,
+        "T" => 40,
+        _ => return None,
+    };
+    Some((num.parse::<f64>().ok()? * (1u64 << shift) as f64) as u64)
+}
+
+// "Archived and active journals take up 1.2G in the file system."
+fn journal_usage() -> Result<JournalUsage, String> {
+    let output = Command::new("journalctl").arg("--disk-usage").output()
+        .map_err(|_| "journalctl is not available".to_string())?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    text.split_once("take up ")
+        .and_then(|(_, rest)| parse_size(rest.split_whitespace().next()?))
+        .map(|bytes| JournalUsage { bytes })
+        .ok_or_else(|| String::from_utf8_lossy(&output.stderr).trim().to_string())
+}
+
+// --- Commands ---
+
+/// Last `lines` journal entries for `unit`, optionally limited to `priority` (e.g. "err" or "3") and above.
+#[tauri::command]
+pub fn get_service_logs(unit: String, lines: u32, priority: Option<String>, scope: Option<Scope>) -> Vec<LogEntry> {
+    let mut cmd = scope.unwrap_or_default().journalctl();
+    cmd.arg("-u").arg(&unit).arg("-n").arg(lines.to_string()).args(["--output=short-iso", "--no-pager"]);
+    if let Some(p) = priority.filter(|p| !p.is_empty()) {
+        cmd.arg("-p").arg(p);
+    }
+
+    match cmd.output() {
+        Ok(o) => String::from_utf8_lossy(&o.stdout).lines().filter_map(parse_line).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Journal entries newest first, filtered by `priority` (and above), `unit` and a
+/// `since`/`until` window in Unix seconds. Pass the previous page's `next_cursor` to continue.
+#[tauri::command]
+pub async fn query_journal(priority: Option<String>, unit: Option<String>, since: Option<u64>, until: Option<u64>,
+    cursor: Option<String>, limit: u32, scope: Option<Scope>) -> Result<JournalPage, String> {
+    let limit = limit.clamp(1, MAX_QUERY_LIMIT);
+    let mut cmd = scope.unwrap_or_default().journalctl();
+    cmd.args(["--output=json", "--no-pager", "--reverse", "-n"]).arg(limit.to_string());
+    if let Some(p) = priority.filter(|p| !p.is_empty()) {
+        cmd.arg("-p").arg(p);
+    }
+    if let Some(u) = unit.filter(|u| !u.is_empty()) {
+        cmd.arg("-u").arg(u);
+    }
+    if let Some(t) = since {
+        cmd.arg(format!("--since=@{}", t));
+    }
+    if let Some(t) = until {
+        cmd.arg(format!("--until=@{}", t));
+    }
+    // In reverse mode "after" the cursor means older than it
+    if let Some(c) = cursor.filter(|c| !c.is_empty()) {
+        cmd.arg(format!("--after-cursor={}", c));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = cmd.output().map_err(|_| "journalctl is not available".to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        let entries: Vec<JournalEntry> = String::from_utf8_lossy(&output.stdout).lines().filter_map(parse_json_entry).collect();
+        let next_cursor = if entries.len() as u32 == limit { entries.last().map(|e| e.cursor.clone()) } else { None };
+        Ok(JournalPage { entries, next_cursor })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// journald may not capture the kernel log (e.g. in containers); dmesg reads the ring buffer directly.
+// "--raw" lines look like "<6>[   12.345678] usb 1-1: new device"
+fn dmesg_entries(lines: u32, level: u8) -> Result<Vec<JournalEntry>, String> {
+    let output = Command::new("dmesg").arg("--raw").output().map_err(|_| "dmesg is not available".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let btime: u64 = fs::read_to_string("/proc/stat").unwrap_or_default().lines()
+        .find_map(|l| l.strip_prefix("btime ")?.trim().parse().ok())
+        .unwrap_or(0);
+
+    let mut entries: Vec<JournalEntry> = String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| {
+            let (pri, rest) = line.strip_prefix('<')?.split_once('>')?;
+            // The facility is packed into the upper bits
+            let priority = (pri.parse::<u32>().ok()? & 7) as u8;
+            let (secs, message) = rest.strip_prefix('[')?.split_once(']')?;
+            let secs: f64 = secs.trim().parse().ok()?;
+            Some(JournalEntry {
+                timestamp: btime * 1_000_000 + (secs * 1_000_000.0) as u64,
+                unit: "kernel".to_string(),
+                priority,
+                message: message.trim().to_string(),
+                cursor: String::new(),
+            })
+        })
+        .filter(|e| e.priority <= level)
+        .collect();
+    entries.reverse();
+    entries.truncate(lines as usize);
+    Ok(entries)
+}
+
+/// Newest `lines` kernel messages at `level` (0-7) or more severe.
+#[tauri::command]
+pub async fn get_kernel_log(lines: u32, level: u8) -> Result<Vec<JournalEntry>, String> {
+    let lines = lines.clamp(1, MAX_QUERY_LIMIT);
+    let level = level.min(7);
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = Command::new("journalctl")
+            .args(["-k", "-b", "--output=json", "--no-pager", "--reverse", "-n"])
+            .arg(lines.to_string())
+            .arg("-p").arg(level.to_string())
+            .output();
+        match output {
+            Ok(o) if o.status.success() => {
+                let entries: Vec<JournalEntry> = String::from_utf8_lossy(&o.stdout).lines().filter_map(parse_json_entry).collect();
+                if !entries.is_empty() {
+                    return Ok(entries);
+                }
+                // Usually just nothing at this level; a dmesg that can't be read (restricted
+                // to root by kernel.dmesg_restrict) doesn't make that an error
+                Ok(dmesg_entries(lines, level).unwrap_or_default())
+            }
+            _ => dmesg_entries(lines, level),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Follows the journal from the last `backlog` entries onwards, emitting `journal://entries`
+/// batches. Starting a new stream replaces the previous one.
+#[tauri::command]
+pub fn start_journal_stream(priority: Option<String>, unit: Option<String>, backlog: u32, scope: Option<Scope>,
+    app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    let mut cmd = scope.unwrap_or_default().journalctl();
+    cmd.args(["-f", "--output=short-iso", "--no-pager", "-n"]).arg(backlog.to_string());
+    if let Some(p) = priority.filter(|p| !p.is_empty()) {
+        cmd.arg("-p").arg(p);
+    }
+    if let Some(u) = unit.filter(|u| !u.is_empty()) {
+        cmd.arg("-u").arg(u);
+    }
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::null()).spawn()
+        .map_err(|_| "journalctl is not available".to_string())?;
+    let stdout = child.stdout.take().ok_or("journalctl has no output")?;
+
+    if let Some(mut prev) = state.journal_stream.lock().unwrap().replace(child) {
+        let _ = prev.kill();
+        let _ = prev.wait();
+    }
+
+    // The reader never blocks on the window: when the queue is full, entries are counted and dropped
+    let (tx, rx) = mpsc::sync_channel::<LogEntry>(STREAM_QUEUE);
+    let dropped = Arc::new(AtomicU64::new(0));
+    let reader_dropped = dropped.clone();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let Some(entry) = parse_line(&line) else { continue };
+            match tx.try_send(entry) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => { reader_dropped.fetch_add(1, Ordering::Relaxed); }
+                Err(TrySendError::Disconnected(_)) => break,
+            }
+        }
+    });
+
+    // Ends once journalctl is killed and the reader hangs up
+    std::thread::spawn(move || loop {
+        let mut entries = Vec::new();
+        let deadline = Instant::now() + STREAM_FLUSH;
+        let disconnected = loop {
+            match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(entry) => {
+                    entries.push(entry);
+                    if entries.len() >= STREAM_BATCH {
+                        break false;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => break false,
+                Err(RecvTimeoutError::Disconnected) => break true,
+            }
+        };
+        let dropped = dropped.swap(0, Ordering::Relaxed);
+        if !entries.is_empty() || dropped > 0 {
+            let _ = app.emit_all("journal://entries", JournalBatch { entries, dropped });
+        }
+        if disconnected {
+            break;
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_journal_stream(state: State<AppState>) {
+    if let Some(mut child) = state.journal_stream.lock().unwrap().take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+#[tauri::command]
+pub async fn get_journal_usage() -> Result<JournalUsage, String> {
+    tauri::async_runtime::spawn_blocking(journal_usage)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Removes archived journal files until they fit within `max_size` (e.g. "500M") and/or are
+/// newer than `max_age` (e.g. "2weeks"). Asks for authorisation through pkexec unless running as root.
+#[tauri::command]
+pub async fn vacuum_journal(max_size: Option<String>, max_age: Option<String>) -> Result<JournalUsage, String> {
+    // A number with an optional unit; anything else would be rejected by journalctl after the password prompt
+    let valid = |v: &String| v.starts_with(|c: char| c.is_ascii_digit()) && v.chars().all(|c| c.is_ascii_alphanumeric());
+    let mut args = Vec::new();
+    if let Some(size) = max_size.filter(|s| !s.is_empty()) {
+        if !valid(&size) {
+            return Err(format!("Invalid size: {}", size));
+        }
+        args.push(format!("--vacuum-size={}", size));
+    }
+    if let Some(age) = max_age.filter(|a| !a.is_empty()) {
+        if !valid(&age) {
+            return Err(format!("Invalid age: {}", age));
+        }
+        args.push(format!("--vacuum-time={}", age));
+    }
+    if args.is_empty() {
+        return Err("Give a maximum size or age".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut cmd = if unsafe { libc::geteuid() } == 0 {
+            Command::new("journalctl")
+        } else {
+            let mut c = Command::new("pkexec");
+            c.arg("journalctl");
+            c
+        };
+        let output = cmd.args(&args).output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        journal_usage()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,201 @@
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+// OSV pages its results; the kernel has thousands of entries, so stop after this many pages
+const OSV_MAX_PAGES: usize = 10;
+
+/// A well-known, actively exploited kernel vulnerability. Not a complete feed; the online
+/// check covers everything the kernel CNA has published.
+struct BundledCve {
+    id: &'static str,
+    severity: &'static str,
+    summary: &'static str,
+    /// First affected release
+    introduced: (u32, u32, u32),
+    /// Fixed stable releases per branch; a branch newer than the last one listed is unaffected,
+    /// an older one that isn't listed is reported as unknown
+    fixed: &'static [(u32, u32, u32)],
+}
+
+const BUNDLED: &[BundledCve] = &[
+    BundledCve { id: "CVE-2016-5195", severity: "high", summary: "Dirty COW: copy-on-write race lets users write to read-only mappings",
+        introduced: (2, 6, 22), fixed: &[(3, 2, 83), (3, 4, 113), (3, 10, 104), (3, 12, 66), (3, 16, 38), (3, 18, 44), (4, 1, 35),
+            (4, 4, 26), (4, 7, 9), (4, 8, 3)] },
+    BundledCve { id: "CVE-2022-0185", severity: "high", summary: "Heap overflow in filesystem context parsing, reachable from user namespaces",
+        introduced: (5, 1, 0), fixed: &[(5, 4, 173), (5, 10, 93), (5, 15, 16), (5, 16, 2)] },
+    BundledCve { id: "CVE-2022-0847", severity: "high", summary: "Dirty Pipe: pipe buffer flags let users overwrite read-only files",
+        introduced: (5, 8, 0), fixed: &[(5, 10, 102), (5, 15, 25), (5, 16, 11)] },
+    BundledCve { id: "CVE-2022-2588", severity: "high", summary: "Use-after-free in the cls_route traffic filter (DirtyCred)",
+        introduced: (2, 6, 12), fixed: &[(4, 9, 325), (4, 14, 290), (4, 19, 255), (5, 4, 210), (5, 10, 136), (5, 15, 60), (5, 18, 17),
+            (5, 19, 2)] },
+    BundledCve { id: "CVE-2024-1086", severity: "high", summary: "Use-after-free in nf_tables verdict handling, exploited for local root",
+        introduced: (3, 15, 0), fixed: &[(4, 19, 306), (5, 4, 269), (5, 10, 209), (5, 15, 149), (6, 1, 76), (6, 6, 15), (6, 7, 3)] },
+];
+
+#[derive(serde::Serialize)]
+pub struct KernelCve {
+    id: String,
+    /// critical, high, medium, low or unknown
+    severity: String,
+    summary: String,
+    /// First release with the fix in the running kernel's branch, where known
+    fixed_in: Option<String>,
+    /// affected, or unknown when the bundled list has no fix for the branch: it may be an
+    /// end-of-life branch that never got one, or a stable fix the list is missing
+    status: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct KernelCveReport {
+    /// As reported by uname, e.g. 6.1.0-18-amd64
+    release: String,
+    /// Upstream version the comparison used
+    version: String,
+    /// The distro backports fixes without changing the upstream version, so listed CVEs may
+    /// already be patched
+    backported: bool,
+    /// bundled or osv
+    source: String,
+    cves: Vec<KernelCve>,
+    /// Why the online check fell back to the bundled list
+    error: Option<String>,
+}
+
+fn parse_version(text: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = text.split(|c: char| !c.is_ascii_digit()).map(|p| p.parse::<u32>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next()?.ok()?;
+    Some((major, minor, parts.next().and_then(|p| p.ok()).unwrap_or(0)))
+}
+
+// Debian and Ubuntu name their kernels after the ABI (6.1.0-18), not the stable release they
+// are based on; both record the real upstream version elsewhere
+fn upstream_version(release: &str) -> ((u32, u32, u32), bool) {
+    // "Ubuntu 5.15.0-91.101-generic 5.15.131"
+    if let Some(v) = fs::read_to_string("/proc/version_signature").ok()
+        .and_then(|s| s.split_whitespace().last().and_then(parse_version)) {
+        return (v, true);
+    }
+    // "... #1 SMP PREEMPT_DYNAMIC Debian 6.1.76-1 (2024-02-01)"
+    let proc_version = fs::read_to_string("/proc/version").unwrap_or_default();
+    if let Some(v) = proc_version.split_once(" Debian ").and_then(|(_, r)| parse_version(r)) {
+        return (v, true);
+    }
+    let v = parse_version(release).unwrap_or_default();
+    // RHEL and its rebuilds stay on one base version for the whole release
+    (v, release.contains(".el"))
+}
+
+fn fmt_version(v: (u32, u32, u32)) -> String {
+    format!("{}.{}.{}", v.0, v.1, v.2)
+}
+
+fn bundled(version: (u32, u32, u32)) -> Vec<KernelCve> {
+    BUNDLED.iter().filter_map(|cve| {
+        if version < cve.introduced {
+            return None;
+        }
+        let branch_fix = cve.fixed.iter().find(|f| (f.0, f.1) == (version.0, version.1));
+        let newest = cve.fixed.iter().max()?;
+        let status = match branch_fix {
+            Some(fix) if version < *fix => "affected",
+            Some(_) => return None,
+            // Branches after the last fixed one were released with the fix already in
+            None if (version.0, version.1) > (newest.0, newest.1) => return None,
+            None => "unknown",
+        };
+        Some(KernelCve {
+            id: cve.id.to_string(),
+            severity: cve.severity.to_string(),
+            summary: cve.summary.to_string(),
+            fixed_in: branch_fix.map(|f| fmt_version(*f)),
+            status: status.to_string(),
+        })
+    }).collect()
+}
+
+fn osv_query(body: &serde_json::Value) -> Result<serde_json::Value, String> {
+    let mut child = Command::new("curl")
+        .args(["-sf", "--max-time", "30", "-H", "Content-Type: application/json", "--data-binary", "@-", OSV_QUERY_URL])
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::null())
+        .spawn().map_err(|_| "curl is not installed".to_string())?;
+    child.stdin.take().ok_or("No stdin")?.write_all(body.to_string().as_bytes()).map_err(|e| e.to_string())?;
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err("Could not reach the OSV database".to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+// Only some kernel entries carry a severity rating, in database-specific fields
+fn osv_severity(vuln: &serde_json::Value) -> String {
+    let score = vuln["database_specific"]["severity"].as_str()
+        .or_else(|| vuln["affected"][0]["ecosystem_specific"]["severity"].as_str());
+    score.map(|s| s.to_lowercase()).unwrap_or_else(|| "unknown".to_string())
+}
+
+fn osv_cves(version: (u32, u32, u32)) -> Result<Vec<KernelCve>, String> {
+    let mut cves = Vec::new();
+    let mut page_token: Option<String> = None;
+    for _ in 0..OSV_MAX_PAGES {
+        let mut query = serde_json::json!({
+            "package": { "name": "Kernel", "ecosystem": "Linux" },
+            "version": fmt_version(version),
+        });
+        if let Some(token) = &page_token {
+            query["page_token"] = serde_json::Value::String(token.clone());
+        }
+        let response = osv_query(&query)?;
+        for vuln in response["vulns"].as_array().into_iter().flatten() {
+            let id = vuln["aliases"].as_array().and_then(|a| a.iter().find_map(|v| v.as_str().filter(|s| s.starts_with("CVE-"))))
+                .or_else(|| vuln["id"].as_str()).unwrap_or_default();
+            cves.push(KernelCve {
+                id: id.to_string(),
+                severity: osv_severity(vuln),
+                summary: vuln["summary"].as_str().or_else(|| vuln["details"].as_str())
+                    .map(|s| s.lines().next().unwrap_or_default().to_string()).unwrap_or_default(),
+                fixed_in: None,
+                status: "affected".to_string(),
+            });
+        }
+        page_token = response["next_page_token"].as_str().map(String::from);
+        if page_token.is_none() {
+            break;
+        }
+    }
+    Ok(cves)
+}
+
+// --- Commands ---
+
+/// Known vulnerabilities affecting the running kernel version, from the bundled list or,
+/// with `online`, the OSV database.
+#[tauri::command]
+pub async fn get_kernel_cves(online: bool) -> Result<KernelCveReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let release = fs::read_to_string("/proc/sys/kernel/osrelease").map(|r| r.trim().to_string()).unwrap_or_default();
+        let (version, backported) = upstream_version(&release);
+        let mut report = KernelCveReport {
+            release, version: fmt_version(version), backported, source: "bundled".to_string(), cves: Vec::new(), error: None,
+        };
+        if online {
+            match osv_cves(version) {
+                Ok(cves) => {
+                    report.source = "osv".to_string();
+                    report.cves = cves;
+                }
+                Err(e) => report.error = Some(e),
+            }
+        }
+        if report.source == "bundled" {
+            report.cves = bundled(version);
+        }
+        let rank = |s: &str| ["critical", "high", "medium", "low"].iter().position(|r| *r == s).unwrap_or(4);
+        report.cves.sort_by(|a, b| rank(&a.severity).cmp(&rank(&b.severity)).then(b.id.cmp(&a.id)));
+        report
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,173 @@
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+use crate::{eol, journal};
+
+const DEFAULT_HOURS: u32 = 24;
+
+struct Attempt {
+    /// Unix seconds
+    time: u64,
+    service: String,
+    user: String,
+    /// Remote address, or "local" for console, sudo and su
+    source: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct LoginGroup {
+    source: String,
+    user: String,
+    count: u32,
+    first_seen: u64,
+    last_seen: u64,
+    /// Where the attempts came in, e.g. sshd, login, sudo
+    services: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct FailedLogins {
+    total: u32,
+    /// Largest groups first
+    groups: Vec<LoginGroup>,
+}
+
+fn word_after<'a>(text: &'a str, marker: &str) -> Option<&'a str> {
+    text.split_once(marker)?.1.split_whitespace().next()
+}
+
+// sshd: "Failed password for invalid user admin from 1.2.3.4 port 2222 ssh2"
+// pam:  "pam_unix(sudo:auth): authentication failure; logname=bob uid=1000 ... rhost=  user=bob"
+fn parse_message(identifier: &str, msg: &str) -> Option<(String, String, String)> {
+    if msg.starts_with("Failed ") && msg.contains(" for ") {
+        let user = word_after(msg, " for invalid user ").or_else(|| word_after(msg, " for "))?;
+        let source = word_after(msg, " from ").unwrap_or("unknown");
+        return Some((identifier.to_string(), user.to_string(), source.to_string()));
+    }
+    if msg.contains("authentication failure;") {
+        // sshd logs its own "Failed password" line for the same attempt; OpenSSH 9.8 moved
+        // authentication into a separate sshd-session binary
+        if identifier == "sshd" || identifier == "sshd-session" {
+            return None;
+        }
+        let service = msg.split_once('(').and_then(|(_, r)| r.split(':').next()).unwrap_or(identifier);
+        let user = word_after(msg, " user=").or_else(|| word_after(msg, " logname=")).unwrap_or("unknown");
+        let source = word_after(msg, " rhost=").filter(|h| !h.contains('=')).unwrap_or("local");
+        return Some((service.to_string(), user.to_string(), source.to_string()));
+    }
+    None
+}
+
+fn from_journal(hours: u32) -> Option<Vec<Attempt>> {
+    // Facility 4 is auth, 10 is authpriv
+    let output = Command::new("journalctl")
+        .args(["SYSLOG_FACILITY=4", "SYSLOG_FACILITY=10", "--output=json", "--no-pager"])
+        .arg(format!("--since=-{}h", hours))
+        .output().ok()?;
+    // Outside adm and systemd-journal only the user's own journal is readable, which holds no
+    // auth messages; journalctl still succeeds, with a hint on stderr
+    let hidden = String::from_utf8_lossy(&output.stderr).contains("not seeing messages from other users");
+    if !output.status.success() || hidden {
+        return None;
+    }
+    // sshd logs "Invalid user bob from 1.2.3.4 port 51234" as the connection starts, then
+    // "Failed password for invalid user bob ..." for each password tried on it. The first line
+    // only counts for connections that never got that far, matched by sshd pid and port
+    let mut attempts = Vec::new();
+    let mut failed: HashSet<(String, String)> = HashSet::new();
+    let mut invalid = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(v) = serde_json::from_str::<serde_json::Value>(line) else { continue };
+        let text = |key: &str| v.get(key).map(journal::field_text).unwrap_or_default();
+        let (identifier, msg) = (text("SYSLOG_IDENTIFIER"), text("MESSAGE"));
+        let Some(time) = text("__REALTIME_TIMESTAMP").parse::<u64>().ok().map(|t| t / 1_000_000) else { continue };
+        let connection = (text("_PID"), word_after(&msg, " port ").unwrap_or_default().to_string());
+        if let Some(rest) = msg.strip_prefix("Invalid user ") {
+            let Some(user) = rest.split_whitespace().next() else { continue };
+            let source = word_after(&msg, " from ").unwrap_or("unknown").to_string();
+            invalid.push((connection, Attempt { time, service: identifier, user: user.to_string(), source }));
+            continue;
+        }
+        let Some((service, user, source)) = parse_message(&identifier, &msg) else { continue };
+        if msg.starts_with("Failed ") {
+            failed.insert(connection);
+        }
+        attempts.push(Attempt { time, service, user, source });
+    }
+    attempts.extend(invalid.into_iter().filter(|(c, _)| !failed.contains(c)).map(|(_, a)| a));
+    Some(attempts)
+}
+
+// "2024-05-01T10:00:00+00:00" in UTC, as forced through TZ below
+fn parse_iso(text: &str) -> Option<u64> {
+    let (date, time) = text.split_once('T')?;
+    let mut hms = time.get(..8)?.split(':').map(|p| p.parse::<u64>());
+    let (h, m, s) = (hms.next()?.ok()?, hms.next()?.ok()?, hms.next()?.ok()?);
+    Some(eol::days_from_civil(date)? as u64 * 86400 + h * 3600 + m * 60 + s)
+}
+
+// btmp, for systems without a persistent journal or a user who can't read it; root only.
+// "bob      ssh:notty    1.2.3.4          2024-05-01T10:00:00+00:00 - 2024-05-01T10:00:00+00:00  (00:00)"
+fn from_btmp(hours: u32) -> Result<Vec<Attempt>, String> {
+    let output = Command::new("lastb").args(["-w", "--time-format", "iso"]).arg("--since").arg(format!("-{}hours", hours))
+        .env("TZ", "UTC")
+        .output().map_err(|_| "Neither journalctl nor lastb is available".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).lines().filter_map(|line| {
+        // The user and tty columns are padded and never blank, but the host column can be
+        // all spaces, so the host is whatever sits between the tty and the login time
+        let f: Vec<&str> = line.split_whitespace().collect();
+        if f.len() < 3 || f[0] == "btmp" {
+            return None;
+        }
+        let time_at = f.iter().skip(2).position(|w| parse_iso(w).is_some())? + 2;
+        let host = f[2..time_at].join(" ");
+        let remote = f[1].starts_with("ssh");
+        Some(Attempt {
+            time: parse_iso(f[time_at])?,
+            service: if remote { "sshd" } else { "login" }.to_string(),
+            user: f[0].to_string(),
+            source: match (remote, host.is_empty()) {
+                (false, _) => "local".to_string(),
+                (true, true) => "unknown".to_string(),
+                (true, false) => host,
+            },
+        })
+    }).collect())
+}
+
+// --- Commands ---
+
+/// Failed SSH and local authentication attempts over the last `hours`, grouped by source and user.
+#[tauri::command]
+pub async fn get_failed_logins(hours: Option<u32>) -> Result<FailedLogins, String> {
+    let hours = hours.unwrap_or(DEFAULT_HOURS).max(1);
+    tauri::async_runtime::spawn_blocking(move || {
+        let attempts = match from_journal(hours) {
+            Some(a) => a,
+            None => from_btmp(hours)
+                .map_err(|e| format!("{} (reading failed logins needs the adm or systemd-journal group, or root)", e))?,
+        };
+
+        let mut groups: HashMap<(String, String), LoginGroup> = HashMap::new();
+        for a in &attempts {
+            let g = groups.entry((a.source.clone(), a.user.clone())).or_insert_with(|| LoginGroup {
+                source: a.source.clone(), user: a.user.clone(), count: 0,
+                first_seen: a.time, last_seen: a.time, services: Vec::new(),
+            });
+            g.count += 1;
+            g.first_seen = g.first_seen.min(a.time);
+            g.last_seen = g.last_seen.max(a.time);
+            if !g.services.contains(&a.service) {
+                g.services.push(a.service.clone());
+            }
+        }
+        let mut groups: Vec<LoginGroup> = groups.into_values().collect();
+        groups.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_seen.cmp(&a.last_seen)));
+        Ok(FailedLogins { total: attempts.len() as u32, groups })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -1,14 +1,78 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod accounts;
+mod alerts;
+mod app_firewall;
+mod audio;
+mod bandwidth;
+mod batch;
+mod bluetooth;
+mod boot;
+mod burst;
+mod clamav;
+mod cleanup;
+mod config;
+mod coredump;
+mod debloat;
+mod diagnostics;
+mod disk_io;
+mod disk_usage;
+mod displays;
+mod dmi;
+mod drift;
+mod eol;
+mod fail2ban;
+mod file_monitor;
+mod firewall;
+mod flatpak;
+mod fs_watch;
+mod gpu;
+mod health;
+mod history;
+mod hostname;
+mod hygiene;
+mod journal;
+mod kernel_cve;
+mod logins;
+mod metrics_server;
+mod network;
+mod packages;
+mod pci;
+mod profiler;
+mod rootkit;
+mod rules;
+mod sampler;
+mod security;
+mod services;
+mod sessions;
+mod settings;
+mod shaping;
+mod smart;
+mod snap;
+mod sockets;
+mod speedtest;
+mod sshd;
+mod startup;
+mod startup_impact;
+mod storage;
+mod sudoers;
+mod support;
+mod tray;
+mod usb;
+mod vpn;
+mod watch;
+mod wifi;
+
 use sysinfo::{CpuExt, System, SystemExt, ProcessExt, PidExt, UserExt, NetworkExt, ComponentExt};
-use std::sync::Mutex;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicBool;
 use std::process::Command;
-use std::fs;
-use tauri::{State, SystemTray, SystemTrayMenu, SystemTrayEvent, CustomMenuItem, Manager};
+use tauri::{AppHandle, State, SystemTray, SystemTrayMenu, SystemTrayEvent, CustomMenuItem, Manager};
 
 // --- Structs ---
 
-#[derive(serde::Serialize)]
+#[derive(Clone, serde::Serialize)]
 struct ProcInfo {
     id: u32,
     name: String,
@@ -18,42 +82,45 @@ struct ProcInfo {
     mem: u64,
 }
 
-#[derive(serde::Serialize)]
+#[derive(Clone, Default, serde::Serialize)]
 struct SystemStats {
     cpu_util: f32,
     mem_used: u64,
     mem_total: u64,
     net_in: u64,
     cpu_temp: f32,
+    gpu_busy: Option<f32>,
+    vram_used: Option<u64>,
+    vram_total: Option<u64>,
     uptime: u64,
     proc_count: usize,
+    drive_temps: Vec<storage::DriveTemperature>,
 }
 
 #[derive(serde::Serialize)]
 struct SecurityAudit {
     kernel_version: String,
-    secure_boot: bool,
-    root_procs: usize,
-}
-
-#[derive(serde::Serialize)]
-struct ServiceStatus {
-    name: String,
-    status: String,
-    active: bool,
-}
-
-#[derive(serde::Serialize)]
-struct LogEntry {
-    time: String,
-    msg: String,
+    /// enabled, disabled, not_uefi or unknown
+    secure_boot: String,
+    /// uefi or bios
+    firmware: String,
+    /// Root-owned userspace processes; kernel threads are left out
+    root_procs: Vec<RootProcess>,
+    privileges: sudoers::PrivilegeAudit,
+    risky_mounts: usize,
+    /// /proc/sys/kernel/tainted; see `security::get_kernel_integrity` for the reasons
+    kernel_taint: u64,
+    mac: security::MacStatus,
+    /// None when no SSH server is installed
+    ssh: Option<sshd::SshdAudit>,
+    /// From the last update check; None until one has finished
+    pending_updates: Option<packages::UpdateCount>,
 }
 
 #[derive(serde::Serialize)]
-struct StartupApp {
+struct RootProcess {
+    pid: u32,
     name: String,
-    path: String,
-    enabled: bool,
 }
 
 #[derive(serde::Serialize)]
@@ -63,24 +130,95 @@ struct HardwareInfo {
     ram_total: String,
     gpu_model: String,
     os_distro: String,
+    /// Board, BIOS and chassis from /sys/class/dmi/id
+    dmi: dmi::DmiInfo,
+}
+
+#[derive(serde::Serialize)]
+struct IoPriority {
+    class: String,
+    level: u8,
 }
 
 struct AppState {
     sys: Mutex<System>,
+    profiler: Mutex<Option<Arc<AtomicBool>>>,
+    history: Mutex<VecDeque<history::Sample>>,
+    offenders: Mutex<history::Ledger>,
+    file_monitor: Mutex<file_monitor::FileMonitorState>,
+    journal_stream: Mutex<Option<std::process::Child>>,
+    metrics_server: Mutex<Option<Arc<AtomicBool>>>,
+    rules: Mutex<rules::RuleEngine>,
+    app_firewall: Mutex<app_firewall::AppFirewall>,
+    snapshot: Mutex<sampler::Snapshot>,
+    settings: Mutex<settings::Settings>,
+    fs_watch: Mutex<Option<Arc<AtomicBool>>>,
+    alerts: Mutex<alerts::AlertLog>,
+    drift: Mutex<drift::DriftStore>,
+    health: Mutex<health::Health>,
+    watches: Mutex<watch::WatchEngine>,
+    updates: Mutex<Option<packages::UpdateCount>>,
+    clamav: Mutex<clamav::ClamavState>,
+    bandwidth: Mutex<bandwidth::BandwidthState>,
+    /// Last status read through pkexec
+    fail2ban: Mutex<Option<fail2ban::Fail2banStatus>>,
+    /// `packages::apply_updates` is running
+    upgrading: AtomicBool,
 }
 
 // --- Commands ---
 
 #[tauri::command]
-fn get_processes(state: State<AppState>) -> Vec<ProcInfo> {
-    let mut sys = state.sys.lock().unwrap();
-    sys.refresh_processes();
-    sys.refresh_cpu();
-    
+fn get_processes(state: State<AppState>) -> health::Sampled<Vec<ProcInfo>> {
+    let interval = state.settings.lock().unwrap().process_interval_ms;
+    let snapshot = state.snapshot.lock().unwrap();
+    health::Sampled::new(snapshot.processes_at, Some(interval), snapshot.processes.clone())
+}
+
+/// Kernel threads, when `separate_kernel_threads` is enabled; empty otherwise.
+#[tauri::command]
+fn get_kernel_threads(state: State<AppState>) -> health::Sampled<Vec<ProcInfo>> {
+    let interval = state.settings.lock().unwrap().process_interval_ms;
+    let snapshot = state.snapshot.lock().unwrap();
+    health::Sampled::new(snapshot.processes_at, Some(interval), snapshot.kernel_threads.clone())
+}
+
+// Glob with "*" as the only wildcard, e.g. "kworker/*"
+fn name_matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else { return false };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+// Kernel threads are kthreadd (pid 2) and its children
+fn is_kernel_thread(pid: sysinfo::Pid, process: &sysinfo::Process) -> bool {
+    let kthreadd = sysinfo::Pid::from_u32(2);
+    pid == kthreadd || process.parent() == Some(kthreadd)
+}
+
+// Expects `sys` to have been refreshed by the sampler. Returns the process list and, when
+// `separate_kernel_threads` is set, the kernel threads taken out of it.
+fn collect_processes(sys: &System, settings: &settings::Settings) -> (Vec<ProcInfo>, Vec<ProcInfo>) {
     let mut procs: Vec<ProcInfo> = Vec::new();
+    let mut kernel_threads: Vec<ProcInfo> = Vec::new();
     let users = sys.users();
 
     for (pid, process) in sys.processes() {
+        // Filtered before the top-N cut so hidden entries don't take up slots
+        if settings.hidden_processes.iter().any(|p| name_matches(p, process.name())) {
+            continue;
+        }
         let user_name = match process.user_id() {
              Some(uid) => users.iter().find(|u| u.id() == uid)
                  .map(|u| u.name().to_string())
@@ -88,40 +226,53 @@ fn get_processes(state: State<AppState>) -> Vec<ProcInfo> {
              None => "system".to_string()
         };
 
-        procs.push(ProcInfo {
+        let info = ProcInfo {
             id: pid.as_u32(),
             name: process.name().to_string(),
             user: user_name,
             status: format!("{:?}", process.status()),
             cpu: process.cpu_usage(),
             mem: process.memory(),
-        });
+        };
+        if settings.separate_kernel_threads && is_kernel_thread(*pid, process) {
+            kernel_threads.push(info);
+        } else {
+            procs.push(info);
+        }
     }
-    procs.sort_by(|a, b| b.cpu.partial_cmp(&a.cpu).unwrap_or(std::cmp::Ordering::Equal));
-    procs.into_iter().take(60).collect()
+    let top = |mut list: Vec<ProcInfo>| -> Vec<ProcInfo> {
+        list.sort_by(|a, b| b.cpu.partial_cmp(&a.cpu).unwrap_or(std::cmp::Ordering::Equal));
+        list.into_iter().take(60).collect()
+    };
+    (top(procs), top(kernel_threads))
 }
 
 #[tauri::command]
-fn get_system_stats(state: State<AppState>) -> SystemStats {
-    let mut sys = state.sys.lock().unwrap();
-    sys.refresh_cpu();
-    sys.refresh_memory();
-    sys.refresh_networks();
-    sys.refresh_components();
+fn get_system_stats(state: State<AppState>) -> health::Sampled<SystemStats> {
+    let interval = state.settings.lock().unwrap().stats_interval_ms;
+    let snapshot = state.snapshot.lock().unwrap();
+    health::Sampled::new(snapshot.stats_at, Some(interval), snapshot.stats.clone())
+}
 
+fn collect_stats(sys: &System, settings: &settings::Settings, drive_temps: Vec<storage::DriveTemperature>, gpus: &[gpu::GpuReading]) -> SystemStats {
     let mut net_total = 0;
     for (_name, data) in sys.networks() {
         net_total += data.received();
     }
 
-    let mut cpu_t = 0.0;
-    for component in sys.components() {
-        let label = component.label().to_lowercase();
-        if label.contains("cpu") || label.contains("core") || label.contains("package") {
-            cpu_t = component.temperature();
-            break;
-        }
-    }
+    // A picked sensor may be any component; otherwise only CPU sensors make up the headline
+    let is_cpu = |label: &str| {
+        let l = label.to_lowercase();
+        l.contains("cpu") || l.contains("core") || l.contains("package")
+    };
+    let temps: Vec<(String, f32)> = sys.components().iter()
+        .filter(|c| settings.temp_aggregation == settings::Aggregation::Device || is_cpu(c.label()))
+        .map(|c| (c.label().to_string(), c.temperature()))
+        .collect();
+    let cpu_t = settings::aggregate(&settings.temp_aggregation, settings.temp_sensor.as_deref(), &temps).unwrap_or(0.0);
+
+    let gpu_device = settings.gpu_device.as_deref();
+    let vram = gpu::vram(gpus, &settings.gpu_aggregation, gpu_device);
 
     SystemStats {
         cpu_util: sys.global_cpu_info().cpu_usage(),
@@ -129,57 +280,20 @@ fn get_system_stats(state: State<AppState>) -> SystemStats {
         mem_total: sys.total_memory(),
         net_in: net_total,
         cpu_temp: cpu_t,
+        gpu_busy: gpu::busy(gpus, &settings.gpu_aggregation, gpu_device),
+        vram_used: vram.map(|v| v.0),
+        vram_total: vram.map(|v| v.1),
         uptime: sys.uptime(),
         proc_count: sys.processes().len(),
+        drive_temps,
     }
 }
 
-#[tauri::command]
-fn get_startup_apps() -> Vec<StartupApp> {
-    let mut apps = Vec::new();
-    let home = std::env::var("HOME").unwrap_or_default();
-    let path = format!("{}/.config/autostart", home);
-
-    if let Ok(entries) = fs::read_dir(path) {
-        for entry in entries.flatten() {
-            let fname = entry.file_name().to_string_lossy().to_string();
-            if fname.ends_with(".desktop") {
-                apps.push(StartupApp {
-                    name: fname.replace(".desktop", ""),
-                    path: entry.path().to_string_lossy().to_string(),
-                    enabled: true,
-                });
-            } else if fname.ends_with(".desktop.bak") {
-                apps.push(StartupApp {
-                    name: fname.replace(".desktop.bak", ""),
-                    path: entry.path().to_string_lossy().to_string(),
-                    enabled: false,
-                });
-            }
-        }
-    }
-    apps
-}
-
-#[tauri::command]
-fn toggle_startup(path: String, enable: bool) -> bool {
-    let new_path = if enable {
-        path.replace(".desktop.bak", ".desktop")
-    } else {
-        path.replace(".desktop", ".desktop.bak")
-    };
-    fs::rename(path, new_path).is_ok()
-}
-
 #[tauri::command]
 fn get_hardware_info(state: State<AppState>) -> HardwareInfo {
     let sys = state.sys.lock().unwrap();
-    let gpu_out = Command::new("lspci").output()
-        .map(|o| String::from_utf8_lossy(&o.stdout).lines()
-            .find(|l| l.contains("VGA") || l.contains("3D"))
-            .map(|l| l.split(": ").last().unwrap_or("Unknown GPU").to_string())
-            .unwrap_or("Integrated/Unknown".to_string()))
-        .unwrap_or("Unknown".to_string());
+    let gpus: Vec<String> = pci::pci_devices().iter().filter(|d| d.is_gpu()).map(|d| d.name()).collect();
+    let gpu_out = if gpus.is_empty() { "Unknown".to_string() } else { gpus.join(", ") };
 
     HardwareInfo {
         cpu_model: sys.global_cpu_info().brand().to_string(),
@@ -187,53 +301,42 @@ fn get_hardware_info(state: State<AppState>) -> HardwareInfo {
         ram_total: format!("{:.1} GB", sys.total_memory() as f64 / 1024.0 / 1024.0 / 1024.0),
         gpu_model: gpu_out,
         os_distro: sys.name().unwrap_or("Linux".into()),
+        dmi: dmi::read(),
     }
 }
 
-#[tauri::command]
-fn control_service(name: String, action: String) -> bool {
-    Command::new("systemctl").arg(&action).arg(&name).status().map(|s| s.success()).unwrap_or(false)
-}
-
-#[tauri::command]
-fn get_services() -> Vec<ServiceStatus> {
-    let services = vec!["sshd", "NetworkManager", "ufw", "docker", "bluetooth", "cronie"];
-    let mut results = Vec::new();
-    for s in services {
-        let output = Command::new("systemctl").arg("is-active").arg(s).output();
-        let status = match output {
-            Ok(o) => String::from_utf8_lossy(&o.stdout).trim().to_string(),
-            Err(_) => "unknown".to_string(),
-        };
-        results.push(ServiceStatus { name: s.to_string(), active: status == "active", status });
-    }
-    results
-}
-
-#[tauri::command]
-fn get_security_audit(state: State<AppState>) -> SecurityAudit {
-    let sys = state.sys.lock().unwrap();
-    let root_count = sys.processes().values()
-        .filter(|p| format!("{:?}", p.user_id()).contains("0"))
-        .count();
+// Only the process list needs `sys`; semodule, sshd -T and friends can take seconds, and the
+// sampler must not wait for them
+fn security_audit(state: &AppState) -> SecurityAudit {
+    let (mut root_procs, kernel_version) = {
+        let sys = state.sys.lock().unwrap();
+        let procs: Vec<RootProcess> = sys.processes().iter()
+            .filter(|(pid, p)| p.user_id().is_some_and(|u| **u == 0) && !is_kernel_thread(**pid, p))
+            .map(|(pid, p)| RootProcess { pid: pid.as_u32(), name: p.name().to_string() })
+            .collect();
+        (procs, sys.kernel_version().unwrap_or("Unknown".into()))
+    };
+    root_procs.sort_by(|a, b| a.name.cmp(&b.name).then(a.pid.cmp(&b.pid)));
 
     SecurityAudit {
-        kernel_version: sys.kernel_version().unwrap_or("Unknown".into()),
-        secure_boot: true,
-        root_procs: root_count,
+        kernel_version,
+        secure_boot: security::secure_boot_state().to_string(),
+        firmware: security::boot_firmware().to_string(),
+        root_procs,
+        privileges: sudoers::audit(),
+        risky_mounts: storage::risky_mount_count(),
+        kernel_taint: security::kernel_taint(),
+        mac: security::mac_status(),
+        ssh: sshd::audit(),
+        pending_updates: *state.updates.lock().unwrap(),
     }
 }
 
 #[tauri::command]
-fn get_journal_logs() -> Vec<LogEntry> {
-    let output = Command::new("journalctl").args(&["-p", "3", "-n", "10", "--output=short-iso", "--no-pager"]).output();
-    let mut logs = Vec::new();
-    if let Ok(o) = output {
-        for line in String::from_utf8_lossy(&o.stdout).lines() {
-            logs.push(LogEntry { time: "Recent".into(), msg: line.to_string() });
-        }
-    }
-    logs
+async fn get_security_audit(app: AppHandle) -> Result<SecurityAudit, String> {
+    tauri::async_runtime::spawn_blocking(move || security_audit(app.state::<AppState>().inner()))
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -271,22 +374,90 @@ fn set_process_priority(pid: u32, priority: String) -> bool {
     Command::new("renice").arg("-n").arg(val).arg("-p").arg(pid.to_string()).status().map(|s| s.success()).unwrap_or(false)
 }
 
+// ioprio values pack the scheduling class above a 13-bit per-class level
+const IOPRIO_CLASS_SHIFT: i32 = 13;
+const IOPRIO_WHO_PROCESS: i32 = 1;
+
+#[tauri::command]
+fn get_io_priority(pid: u32) -> Result<IoPriority, String> {
+    let ret = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid as i32) };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    let ret = ret as i32;
+    let class = match ret >> IOPRIO_CLASS_SHIFT {
+        1 => "Realtime",
+        2 => "BestEffort",
+        3 => "Idle",
+        // No explicit class means the kernel derives it from the nice value
+        _ => "None",
+    };
+    Ok(IoPriority { class: class.to_string(), level: (ret & ((1 << IOPRIO_CLASS_SHIFT) - 1)) as u8 })
+}
+
+#[tauri::command]
+fn set_io_priority(pid: u32, class: String, level: u8) -> bool {
+    let class_id = match class.as_str() {
+        "Realtime" => 1,
+        "BestEffort" => 2,
+        "Idle" => 3,
+        _ => 0,
+    };
+    // Levels only range 0 (highest) to 7 and are ignored for the idle class
+    let ioprio = (class_id << IOPRIO_CLASS_SHIFT) | i32::from(level.min(7));
+    unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, pid as i32, ioprio) == 0 }
+}
+
 fn main() {
     let mut sys = System::new_all();
     sys.refresh_all();
 
     let quit = CustomMenuItem::new("quit".to_string(), "Quit");
     let show = CustomMenuItem::new("show".to_string(), "Show Dashboard");
-    let tray_menu = SystemTrayMenu::new().add_item(show).add_item(quit);
+    let metrics = CustomMenuItem::new(tray::TRAY_METRICS_ITEM.to_string(), "Starting...").disabled();
+    let tray_menu = SystemTrayMenu::new().add_item(metrics).add_item(show).add_item(quit);
     let tray = SystemTray::new().with_menu(tray_menu);
 
     tauri::Builder::default()
-        .manage(AppState { sys: Mutex::new(sys) })
+        .manage(AppState {
+            sys: Mutex::new(sys),
+            profiler: Mutex::new(None),
+            history: Mutex::new(VecDeque::new()),
+            offenders: Mutex::new(history::Ledger::load()),
+            file_monitor: Mutex::new(file_monitor::FileMonitorState::default()),
+            journal_stream: Mutex::new(None),
+            metrics_server: Mutex::new(None),
+            rules: Mutex::new(rules::RuleEngine::load()),
+            app_firewall: Mutex::new(app_firewall::AppFirewall::default()),
+            snapshot: Mutex::new(sampler::Snapshot::default()),
+            settings: Mutex::new(settings::Settings::load()),
+            fs_watch: Mutex::new(None),
+            alerts: Mutex::new(alerts::AlertLog::default()),
+            drift: Mutex::new(drift::DriftStore::load()),
+            health: Mutex::new(health::Health::default()),
+            watches: Mutex::new(watch::WatchEngine::load()),
+            updates: Mutex::new(None),
+            clamav: Mutex::new(clamav::ClamavState::default()),
+            bandwidth: Mutex::new(bandwidth::BandwidthState::default()),
+            fail2ban: Mutex::new(None),
+            upgrading: AtomicBool::new(false),
+        })
+        .setup(|app| {
+            sampler::spawn(app.handle());
+            drift::spawn(app.handle());
+            packages::spawn_check(app.handle());
+            startup_impact::spawn(app.handle());
+            bandwidth::spawn(app.handle());
+            Ok(())
+        })
         .system_tray(tray)
         .on_system_tray_event(|app, event| match event {
             SystemTrayEvent::MenuItemClick { id, .. } => {
                 match id.as_str() {
-                    "quit" => { std::process::exit(0); }
+                    "quit" => {
+                        app.state::<AppState>().app_firewall.lock().unwrap().clear();
+                        std::process::exit(0);
+                    }
                     "show" => {
                         let window = app.get_window("main").unwrap();
                         window.show().unwrap();
@@ -303,10 +474,54 @@ fn main() {
             _ => {}
         })
         .invoke_handler(tauri::generate_handler![
-            get_processes, get_system_stats, get_security_audit,
-            get_journal_logs, get_services, control_service, 
-            get_startup_apps, toggle_startup, get_hardware_info, 
-            kill_process, suspend_process, resume_process, set_process_priority
+            get_processes, get_kernel_threads, get_system_stats, get_security_audit,
+            journal::get_service_logs, journal::start_journal_stream, journal::stop_journal_stream, journal::query_journal, journal::get_kernel_log, journal::get_journal_usage, journal::vacuum_journal, services::get_services, services::control_service, services::list_units,
+            services::set_service_enabled, services::set_service_masked,
+            services::get_timers, services::trigger_timer, services::get_unit_dependencies,
+            services::get_failed_units, services::reset_failed,
+            startup::get_startup_apps, startup::toggle_startup, startup::add_startup_app, startup::remove_startup_app, get_hardware_info, pci::get_pci_devices, dmi::get_memory_modules, displays::get_displays, audio::get_audio_devices,
+            network::get_interface_details, network::get_public_ip, network::check_connectivity,
+            network::get_dns_config, network::test_dns, network::send_wol, network::set_interface_state, wifi::scan_wifi, wifi::connect_wifi,
+            hostname::get_machine_identity, hostname::set_hostname, disk_usage::analyze_disk_usage, diagnostics::ping_host, diagnostics::trace_route,
+            speedtest::run_speedtest, bandwidth::get_process_bandwidth, sockets::get_connections, vpn::get_vpn_status,
+            bluetooth::get_bluetooth_devices, bluetooth::connect_bluetooth_device, bluetooth::disconnect_bluetooth_device, bluetooth::remove_bluetooth_device,
+            kill_process, suspend_process, resume_process, set_process_priority,
+            get_io_priority, set_io_priority,
+            profiler::start_profiling, profiler::stop_profiling, profiler::capture_profile, history::export_metrics, history::get_top_offenders,
+            file_monitor::start_file_monitor, file_monitor::stop_file_monitor, file_monitor::get_file_access_events,
+            metrics_server::start_metrics_server, metrics_server::stop_metrics_server,
+            rules::get_nice_rules, rules::set_nice_rules,
+            settings::get_settings, settings::set_settings, settings::get_aggregation_sources, burst::start_burst_capture,
+            fs_watch::watch_path, fs_watch::unwatch_path, cleanup::find_large_files,
+            cleanup::find_duplicates, cleanup::resolve_duplicate,
+            alerts::get_alerts, storage::get_inode_usage, storage::find_inode_hogs,
+            storage::get_mount_audit,
+            storage::get_storage_devices,
+            smart::get_smart_health,
+            disk_io::get_cgroup_io, boot::get_boot_analysis,
+            sessions::get_sessions, sessions::control_session,
+            security::get_unit_security, security::get_kernel_integrity,
+            flatpak::get_flatpak_permissions, flatpak::revoke_flatpak_permission,
+            flatpak::get_flatpaks, flatpak::update_flatpak, flatpak::remove_flatpak, snap::get_snaps, snap::refresh_snap,
+            app_firewall::get_app_firewall, app_firewall::set_app_firewall_rules,
+            drift::get_drift_timeline, drift::run_drift_scan,
+            eol::get_support_status, batch::batch, health::get_collector_health,
+            coredump::get_coredumps, coredump::get_coredump_info,
+            debloat::get_debloat_advice, debloat::apply_debloat, debloat::undo_debloat,
+            sockets::get_listening_ports, firewall::get_firewall,
+            firewall::add_firewall_rule, firewall::delete_firewall_rule, logins::get_failed_logins,
+            gpu::get_gpu_processes, gpu::relaunch_on_gpu, gpu::set_nvidia_compute_mode,
+            shaping::get_shaping, shaping::save_shaping_profile, shaping::delete_shaping_profile,
+            shaping::apply_shaping_profile, shaping::clear_shaping,
+            support::preview_support_bundle, support::generate_support_bundle,
+            watch::get_watches, watch::get_watch_variables, watch::save_watch, watch::delete_watch,
+            hygiene::get_file_hygiene, security::audit_sysctl, packages::get_pending_updates,
+            packages::get_package_manager, packages::apply_updates, packages::get_orphan_packages, packages::remove_packages,
+            packages::get_package_cache_size, packages::clean_package_cache, packages::list_installed_packages, packages::search_packages,
+            kernel_cve::get_kernel_cves, fail2ban::get_fail2ban_status, fail2ban::unban_ip,
+            clamav::start_clamav_scan, clamav::stop_clamav_scan, clamav::get_clamav_result,
+            usb::get_usb_devices, usb::get_usb_tree, usb::get_usbguard_policy, usb::allow_usb_device, usb::block_usb_device,
+            rootkit::run_rootkit_scan, accounts::get_account_audit
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
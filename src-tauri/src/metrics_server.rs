@@ -0,0 +1,115 @@
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{ComponentExt, CpuExt, NetworkExt, System, SystemExt};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{storage, AppState};
+
+fn metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(String, f64)]) {
+    let _ = writeln!(out, "# HELP glassview_{} {}", name, help);
+    let _ = writeln!(out, "# TYPE glassview_{} {}", name, kind);
+    for (labels, value) in samples {
+        let _ = writeln!(out, "glassview_{}{} {}", name, labels, value);
+    }
+}
+
+fn label(key: &str, value: &str) -> String {
+    format!("{{{}=\"{}\"}}", key, value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn render(sys: &System, drives: &[storage::DriveTemperature]) -> String {
+    let mut out = String::new();
+    let load = sys.load_average();
+
+    metric(&mut out, "cpu_usage_percent", "gauge", "Global CPU utilisation.",
+        &[(String::new(), sys.global_cpu_info().cpu_usage() as f64)]);
+    metric(&mut out, "cpu_core_usage_percent", "gauge", "Per-core CPU utilisation.",
+        &sys.cpus().iter().map(|c| (label("core", c.name()), c.cpu_usage() as f64)).collect::<Vec<_>>());
+    metric(&mut out, "load_average", "gauge", "System load average.", &[
+        (label("period", "1m"), load.one),
+        (label("period", "5m"), load.five),
+        (label("period", "15m"), load.fifteen),
+    ]);
+    metric(&mut out, "memory_used_bytes", "gauge", "Used memory.",
+        &[(String::new(), sys.used_memory() as f64)]);
+    metric(&mut out, "memory_total_bytes", "gauge", "Total memory.",
+        &[(String::new(), sys.total_memory() as f64)]);
+    metric(&mut out, "swap_used_bytes", "gauge", "Used swap.",
+        &[(String::new(), sys.used_swap() as f64)]);
+    metric(&mut out, "swap_total_bytes", "gauge", "Total swap.",
+        &[(String::new(), sys.total_swap() as f64)]);
+    metric(&mut out, "network_received_bytes_total", "counter", "Bytes received per interface.",
+        &sys.networks().into_iter().map(|(n, d)| (label("interface", n), d.total_received() as f64)).collect::<Vec<_>>());
+    metric(&mut out, "network_transmitted_bytes_total", "counter", "Bytes transmitted per interface.",
+        &sys.networks().into_iter().map(|(n, d)| (label("interface", n), d.total_transmitted() as f64)).collect::<Vec<_>>());
+    metric(&mut out, "temperature_celsius", "gauge", "Sensor temperatures.",
+        &sys.components().iter().map(|c| (label("sensor", c.label()), c.temperature() as f64)).collect::<Vec<_>>());
+    metric(&mut out, "drive_temperature_celsius", "gauge", "Drive temperatures.",
+        &drives.iter().map(|d| (label("device", d.device()), d.celsius() as f64)).collect::<Vec<_>>());
+    metric(&mut out, "processes", "gauge", "Number of processes.",
+        &[(String::new(), sys.processes().len() as f64)]);
+    metric(&mut out, "uptime_seconds", "counter", "Seconds since boot.",
+        &[(String::new(), sys.uptime() as f64)]);
+    out
+}
+
+fn handle(mut stream: TcpStream, app: &AppHandle) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+    let mut request_line = String::new();
+    if BufReader::new(&stream).read_line(&mut request_line).is_err() {
+        return;
+    }
+
+    let response = if request_line.starts_with("GET /metrics") {
+        let body = {
+            let state = app.state::<AppState>();
+            // Kept fresh by the sampler, no need to refresh per scrape
+            let sys = state.sys.lock().unwrap();
+            let snapshot = state.snapshot.lock().unwrap();
+            render(&sys, &snapshot.stats.drive_temps)
+        };
+        format!("HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body)
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+    let _ = stream.write_all(response.as_bytes());
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn start_metrics_server(port: u16, app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+    // Non-blocking so the accept loop can notice the stop flag
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Some(prev) = state.metrics_server.lock().unwrap().replace(stop.clone()) {
+        prev.store(true, Ordering::Relaxed);
+    }
+
+    std::thread::spawn(move || {
+        while !stop.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let _ = stream.set_nonblocking(false);
+                    handle(stream, &app);
+                }
+                Err(_) => std::thread::sleep(Duration::from_millis(100)),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_metrics_server(state: State<AppState>) {
+    if let Some(stop) = state.metrics_server.lock().unwrap().take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+}
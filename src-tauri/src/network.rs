@@ -0,0 +1,444 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::net::{Ipv4Addr, SocketAddr, TcpStream, ToSocketAddrs, UdpSocket};
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use zbus::blocking::Connection;
+use zbus::dbus_proxy;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath};
+
+const SYS_NET: &str = "/sys/class/net";
+// Each probe gives up after this long, so an offline machine answers quickly
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+// Anycast resolvers that answer from almost anywhere
+const INTERNET_PROBES: [&str; 2] = ["1.1.1.1:443", "8.8.8.8:443"];
+const DNS_PROBE: &str = "example.com:443";
+// Answers slower than the resolver's own default retry are a result too, up to this long
+const DNS_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+// The discard port; NICs look at the payload, not the port
+const WOL_PORT: u16 = 9;
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager"
+)]
+trait NetworkManager {
+    fn get_device_by_ip_iface(&self, iface: &str) -> zbus::Result<OwnedObjectPath>;
+    fn activate_connection(&self, connection: &ObjectPath<'_>, device: &ObjectPath<'_>, specific_object: &ObjectPath<'_>) -> zbus::Result<OwnedObjectPath>;
+    fn add_and_activate_connection(
+        &self,
+        connection: HashMap<&str, HashMap<&str, zbus::zvariant::Value<'_>>>,
+        device: &ObjectPath<'_>,
+        specific_object: &ObjectPath<'_>,
+    ) -> zbus::Result<(OwnedObjectPath, OwnedObjectPath)>;
+    fn get_devices(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+}
+
+#[dbus_proxy(interface = "org.freedesktop.NetworkManager.Device", default_service = "org.freedesktop.NetworkManager")]
+trait NmDevice {
+    fn disconnect(&self) -> zbus::Result<()>;
+    #[dbus_proxy(property)]
+    fn managed(&self) -> zbus::Result<bool>;
+}
+
+#[derive(serde::Serialize)]
+pub struct IpAddress {
+    address: String,
+    prefix: u8,
+    /// inet or inet6
+    family: String,
+    /// global, link or host
+    scope: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct WifiLink {
+    ssid: Option<String>,
+    /// Access point the interface is associated with
+    bssid: Option<String>,
+    signal_dbm: Option<i32>,
+    frequency_mhz: Option<u32>,
+    /// e.g. "866.7 MBit/s"
+    tx_bitrate: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct InterfaceDetails {
+    name: String,
+    /// ethernet, wifi, loopback or virtual (bridges, tunnels, veth, ...)
+    kind: String,
+    mac: Option<String>,
+    mtu: Option<u32>,
+    /// Kernel operstate: up, down, dormant, unknown
+    state: String,
+    addresses: Vec<IpAddress>,
+    /// Negotiated link speed; None when down or not reported, as with Wi-Fi
+    speed_mbps: Option<u32>,
+    duplex: Option<String>,
+    wifi: Option<WifiLink>,
+}
+
+fn attr(name: &str, file: &str) -> Option<String> {
+    // speed and duplex fail to read with EINVAL while the link is down
+    fs::read_to_string(format!("{}/{}/{}", SYS_NET, name, file)).ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn is_wireless(name: &str) -> bool {
+    Path::new(&format!("{}/{}/wireless", SYS_NET, name)).exists()
+        || Path::new(&format!("{}/{}/phy80211", SYS_NET, name)).exists()
+}
+
+fn kind(name: &str) -> &'static str {
+    // ARPHRD_LOOPBACK
+    if attr(name, "type").as_deref() == Some("772") {
+        "loopback"
+    } else if is_wireless(name) {
+        "wifi"
+    } else if Path::new(&format!("{}/{}/device", SYS_NET, name)).exists() {
+        "ethernet"
+    } else {
+        "virtual"
+    }
+}
+
+// `ip -j addr` lists every interface with an addr_info array
+fn addresses() -> HashMap<String, Vec<IpAddress>> {
+    let Ok(output) = Command::new("ip").args(["-j", "addr", "show"]).output() else { return HashMap::new() };
+    let links: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap_or_default();
+    links.iter().filter_map(|link| {
+        let name = link.get("ifname")?.as_str()?.to_string();
+        let addrs = link.get("addr_info")?.as_array()?.iter().filter_map(|a| Some(IpAddress {
+            address: a.get("local")?.as_str()?.to_string(),
+            prefix: a.get("prefixlen")?.as_u64()? as u8,
+            family: a.get("family")?.as_str()?.to_string(),
+            scope: a.get("scope").and_then(Value::as_str).unwrap_or_default().to_string(),
+        })).collect();
+        Some((name, addrs))
+    }).collect()
+}
+
+// "Connected to aa:bb:cc:dd:ee:ff (on wlan0)" then tab-indented "SSID: home", "freq: 5180", ...
+fn wifi_link(name: &str) -> Option<WifiLink> {
+    let output = Command::new("iw").args(["dev", name, "link"]).output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let bssid = text.lines().next()?.strip_prefix("Connected to ")?.split_whitespace().next().map(String::from);
+    let field = |key: &str| text.lines().find_map(|l| l.trim().strip_prefix(key)?.strip_prefix(": ")).map(str::trim);
+    Some(WifiLink {
+        ssid: field("SSID").map(String::from),
+        bssid,
+        signal_dbm: field("signal").and_then(|s| s.split_whitespace().next()?.parse().ok()),
+        // Newer iw prints "5180.0"
+        frequency_mhz: field("freq").and_then(|f| f.parse::<f32>().ok()).map(|f| f as u32),
+        // Drop the MCS details after the rate
+        tx_bitrate: field("tx bitrate").map(|b| b.split_whitespace().take(2).collect::<Vec<_>>().join(" ")),
+    })
+}
+
+#[derive(serde::Serialize)]
+pub struct PublicIp {
+    ipv4: Option<String>,
+    ipv6: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct Connectivity {
+    /// Default IPv4 gateway
+    gateway: Option<String>,
+    gateway_reachable: bool,
+    dns_resolves: bool,
+    internet_reachable: bool,
+    /// green when everything works, yellow when the LAN works but DNS or the internet doesn't,
+    /// red without a reachable gateway
+    status: String,
+}
+
+// /proc/net/route has the destination and gateway as little-endian hex
+fn default_gateway() -> Option<Ipv4Addr> {
+    fs::read_to_string("/proc/net/route").ok()?.lines().skip(1).find_map(|l| {
+        let f: Vec<&str> = l.split_whitespace().collect();
+        (f.get(1) == Some(&"00000000")).then(|| u32::from_str_radix(f.get(2)?, 16).ok()).flatten()
+    }).map(|g| Ipv4Addr::from(g.to_le_bytes()))
+}
+
+fn ping(host: &str) -> bool {
+    let secs = PROBE_TIMEOUT.as_secs().to_string();
+    Command::new("ping").args(["-c", "1", "-W", &secs, host]).output().is_ok_and(|o| o.status.success())
+}
+
+// getaddrinfo has no timeout of its own
+fn resolves(host: &str) -> bool {
+    let (tx, rx) = mpsc::channel();
+    let host = host.to_string();
+    std::thread::spawn(move || {
+        let _ = tx.send(host.to_socket_addrs().is_ok_and(|mut a| a.next().is_some()));
+    });
+    rx.recv_timeout(PROBE_TIMEOUT).unwrap_or(false)
+}
+
+fn internet_reachable() -> bool {
+    INTERNET_PROBES.iter()
+        .filter_map(|a| a.parse::<SocketAddr>().ok())
+        .any(|a| TcpStream::connect_timeout(&a, PROBE_TIMEOUT).is_ok())
+}
+
+fn fetch_ip(url: &str) -> Option<String> {
+    let timeout = PROBE_TIMEOUT.as_secs().to_string();
+    let output = Command::new("curl").args(["-sf", "--max-time", &timeout, url]).output().ok()?;
+    let ip = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // Anything but an address (a captive portal page, say) is not an answer
+    (output.status.success() && ip.parse::<std::net::IpAddr>().is_ok()).then_some(ip)
+}
+
+#[derive(serde::Serialize)]
+pub struct DnsServers {
+    /// "Global" or the interface the servers were learned on
+    scope: String,
+    servers: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct DnsConfig {
+    /// From /etc/resolv.conf
+    nameservers: Vec<String>,
+    search: Vec<String>,
+    options: Vec<String>,
+    /// resolv.conf points at systemd-resolved's stub, which forwards to `upstream`
+    resolved_stub: bool,
+    upstream: Vec<DnsServers>,
+}
+
+#[derive(serde::Serialize)]
+pub struct DnsTest {
+    hostname: String,
+    elapsed_ms: f64,
+    addresses: Vec<String>,
+}
+
+// "Global: 1.1.1.1" and "Link 2 (wlp3s0): 192.168.1.1 fe80::1%3"
+fn resolvectl_servers() -> Vec<DnsServers> {
+    let Ok(output) = Command::new("resolvectl").arg("dns").output() else { return Vec::new() };
+    String::from_utf8_lossy(&output.stdout).lines().filter_map(|l| {
+        let (scope, servers) = l.split_once(':')?;
+        let scope = scope.split_once('(').map_or(scope, |(_, s)| s.trim_end_matches(')')).trim().to_string();
+        let servers: Vec<String> = servers.split_whitespace().map(String::from).collect();
+        (!servers.is_empty()).then_some(DnsServers { scope, servers })
+    }).collect()
+}
+
+pub fn valid_hostname(host: &str) -> bool {
+    !host.is_empty() && host.len() <= 253 && host.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+}
+
+// Ok(false) when NetworkManager isn't running or leaves the interface alone. Activating with
+// "/" as the connection lets NetworkManager pick the best profile for the device; polkit
+// decides whether we may.
+fn nm_set_state(name: &str, up: bool) -> Result<bool, String> {
+    let Ok(conn) = Connection::system() else { return Ok(false) };
+    let Ok(nm) = NetworkManagerProxyBlocking::new(&conn) else { return Ok(false) };
+    let Ok(device) = nm.get_device_by_ip_iface(name) else { return Ok(false) };
+    let proxy = NmDeviceProxyBlocking::builder(&conn)
+        .path(device.clone()).and_then(|b| b.build())
+        .map_err(|e| e.to_string())?;
+    if !proxy.managed().unwrap_or(false) {
+        return Ok(false);
+    }
+    let root = ObjectPath::try_from("/").map_err(|e| e.to_string())?;
+    let result = if up { nm.activate_connection(&root, &device, &root).map(|_| ()) } else { proxy.disconnect() };
+    result.map(|_| true).map_err(|e| e.to_string())
+}
+
+fn ip_link_set_state(name: &str, up: bool) -> Result<(), String> {
+    let mut cmd = if unsafe { libc::geteuid() } == 0 {
+        Command::new("ip")
+    } else {
+        let mut c = Command::new("pkexec");
+        c.arg("ip");
+        c
+    };
+    let output = cmd.args(["link", "set", "dev", name, if up { "up" } else { "down" }]).output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(match output.status.code() {
+            Some(126) | Some(127) => "Authentication was cancelled".to_string(),
+            _ => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(())
+}
+
+// "aa:bb:cc:dd:ee:ff" or "AA-BB-CC-DD-EE-FF"
+fn parse_mac(mac: &str) -> Option<[u8; 6]> {
+    let bytes: Option<Vec<u8>> = mac.split([':', '-'])
+        .map(|b| if b.len() == 2 { u8::from_str_radix(b, 16).ok() } else { None })
+        .collect();
+    bytes?.try_into().ok()
+}
+
+// Six 0xff bytes, then the MAC sixteen times
+fn magic_packet(mac: [u8; 6]) -> Vec<u8> {
+    let mut packet = vec![0xff; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(&mac);
+    }
+    packet
+}
+
+// --- Commands ---
+
+/// Every network interface with its addresses and link details, Wi-Fi association included.
+#[tauri::command]
+pub async fn get_interface_details() -> Vec<InterfaceDetails> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut addresses = addresses();
+        let mut names: Vec<String> = fs::read_dir(SYS_NET).into_iter().flatten().flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names.into_iter().map(|name| {
+            let kind = kind(&name);
+            InterfaceDetails {
+                kind: kind.to_string(),
+                mac: attr(&name, "address").filter(|m| m != "00:00:00:00:00:00"),
+                mtu: attr(&name, "mtu").and_then(|m| m.parse().ok()),
+                state: attr(&name, "operstate").unwrap_or_else(|| "unknown".to_string()),
+                addresses: addresses.remove(&name).unwrap_or_default(),
+                // Reported as -1 when the driver doesn't know
+                speed_mbps: attr(&name, "speed").and_then(|s| s.parse::<i64>().ok()).filter(|s| *s > 0).map(|s| s as u32),
+                duplex: attr(&name, "duplex").filter(|d| d != "unknown"),
+                wifi: (kind == "wifi").then(|| wifi_link(&name)).flatten(),
+                name,
+            }
+        }).collect()
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// The addresses the internet sees this machine as, asked of ipify over each IP version.
+#[tauri::command]
+pub async fn get_public_ip() -> Result<PublicIp, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let v4 = std::thread::spawn(|| fetch_ip("https://api.ipify.org"));
+        let ipv6 = fetch_ip("https://api6.ipify.org");
+        let ipv4 = v4.join().unwrap_or(None);
+        if ipv4.is_none() && ipv6.is_none() {
+            return Err("Could not reach ipify".to_string());
+        }
+        Ok(PublicIp { ipv4, ipv6 })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Whether the gateway answers, names resolve and the internet is reachable.
+#[tauri::command]
+pub async fn check_connectivity() -> Connectivity {
+    tauri::async_runtime::spawn_blocking(|| {
+        let gateway = default_gateway();
+        let gateway_reachable = gateway.is_some_and(|g| ping(&g.to_string()));
+        let dns_resolves = resolves(DNS_PROBE);
+        let internet_reachable = internet_reachable();
+        // Some routers drop pings, so a working internet implies a working gateway
+        let status = if dns_resolves && internet_reachable {
+            "green"
+        } else if gateway_reachable || internet_reachable {
+            "yellow"
+        } else {
+            "red"
+        };
+        Connectivity {
+            gateway: gateway.map(|g| g.to_string()),
+            gateway_reachable,
+            dns_resolves,
+            internet_reachable,
+            status: status.to_string(),
+        }
+    })
+    .await
+    .unwrap_or(Connectivity { gateway: None, gateway_reachable: false, dns_resolves: false, internet_reachable: false, status: "red".to_string() })
+}
+
+/// Resolvers from resolv.conf, and the upstream servers behind systemd-resolved's stub.
+#[tauri::command]
+pub fn get_dns_config() -> DnsConfig {
+    let conf = fs::read_to_string("/etc/resolv.conf").unwrap_or_default();
+    let values = |key: &str| -> Vec<String> {
+        conf.lines()
+            .filter_map(|l| l.trim().strip_prefix(key)?.strip_prefix(char::is_whitespace))
+            .flat_map(|v| v.split_whitespace().map(String::from))
+            .collect()
+    };
+    let nameservers = values("nameserver");
+    let resolved_stub = nameservers.iter().any(|n| n == "127.0.0.53" || n == "127.0.0.54");
+    DnsConfig {
+        upstream: if resolved_stub { resolvectl_servers() } else { Vec::new() },
+        search: values("search"),
+        options: values("options"),
+        nameservers,
+        resolved_stub,
+    }
+}
+
+/// Resolves `hostname` through the system resolver and times it.
+#[tauri::command]
+pub async fn test_dns(hostname: String) -> Result<DnsTest, String> {
+    let hostname = hostname.trim().trim_end_matches('.').to_string();
+    if !valid_hostname(&hostname) {
+        return Err(format!("Invalid hostname: {}", hostname));
+    }
+    let (tx, rx) = mpsc::channel();
+    let host = hostname.clone();
+    std::thread::spawn(move || {
+        let start = Instant::now();
+        let result = (host.as_str(), 0).to_socket_addrs().map(|a| a.map(|a| a.ip().to_string()).collect::<Vec<_>>());
+        let _ = tx.send((start.elapsed(), result));
+    });
+    tauri::async_runtime::spawn_blocking(move || {
+        let (elapsed, result) = rx.recv_timeout(DNS_TEST_TIMEOUT)
+            .map_err(|_| format!("No answer within {} s", DNS_TEST_TIMEOUT.as_secs()))?;
+        let mut addresses = result.map_err(|e| e.to_string())?;
+        addresses.sort();
+        addresses.dedup();
+        Ok(DnsTest { hostname, elapsed_ms: elapsed.as_secs_f64() * 1000.0, addresses })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Sends a Wake-on-LAN magic packet for `mac` to `broadcast_addr`, the limited broadcast
+/// address by default; a subnet's directed broadcast reaches machines behind another interface.
+#[tauri::command]
+pub fn send_wol(mac: String, broadcast_addr: Option<String>) -> Result<(), String> {
+    let target = parse_mac(mac.trim()).ok_or_else(|| format!("Invalid MAC address: {}", mac))?;
+    let broadcast: Ipv4Addr = match broadcast_addr.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+        Some(b) => b.parse().map_err(|_| format!("Invalid broadcast address: {}", b))?,
+        None => Ipv4Addr::BROADCAST,
+    };
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(|e| e.to_string())?;
+    socket.set_broadcast(true).map_err(|e| e.to_string())?;
+    socket.send_to(&magic_packet(target), (broadcast, WOL_PORT)).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Brings an interface up or down through NetworkManager, or with `ip link` (through pkexec)
+/// for interfaces it doesn't manage.
+#[tauri::command]
+pub async fn set_interface_state(name: String, up: bool) -> Result<(), String> {
+    // Also keeps anything but a real interface name away from the command line
+    if name.contains('/') || name.starts_with('.') || !Path::new(SYS_NET).join(&name).exists() {
+        return Err(format!("No such interface: {}", name));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        if nm_set_state(&name, up)? {
+            return Ok(());
+        }
+        ip_link_set_state(&name, up)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,665 @@
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::Ordering;
+use tauri::{AppHandle, Manager};
+
+use crate::AppState;
+
+#[derive(Clone, serde::Serialize)]
+pub struct PendingUpdate {
+    name: String,
+    /// Installed version, where the package manager reports it
+    current: Option<String>,
+    available: String,
+    repo: Option<String>,
+    /// Fixes a published security advisory
+    security: bool,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct PendingUpdates {
+    /// pacman, apt, dnf or zypper; none when no supported package manager was found
+    manager: String,
+    updates: Vec<PendingUpdate>,
+    security_count: usize,
+    /// Whether advisories were available to tell security updates apart
+    security_known: bool,
+}
+
+/// Totals from the last check, shown in the security audit.
+#[derive(Clone, Copy, serde::Serialize)]
+pub struct UpdateCount {
+    pub total: usize,
+    pub security: usize,
+}
+
+#[derive(serde::Serialize)]
+pub struct InstalledPackage {
+    name: String,
+    version: String,
+    /// Installed size in bytes
+    size: Option<u64>,
+    description: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct SearchResult {
+    name: String,
+    /// apt-cache doesn't print versions in search results
+    version: Option<String>,
+    repo: Option<String>,
+    description: String,
+    installed: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct OrphanPackage {
+    name: String,
+    version: Option<String>,
+}
+
+/// One distro package manager. Output is parsed with LC_ALL=C, since several of them
+/// translate field names.
+trait Backend {
+    fn name(&self) -> &'static str;
+    fn list_installed(&self) -> Result<Vec<InstalledPackage>, String>;
+    fn search(&self, query: &str) -> Result<Vec<SearchResult>, String>;
+    fn get_updates(&self) -> Result<PendingUpdates, String>;
+    /// Shell script that refreshes metadata and upgrades everything without asking; run as root
+    fn upgrade_script(&self) -> &'static str;
+    /// Packages installed as dependencies that nothing needs anymore
+    fn orphans(&self) -> Result<Vec<OrphanPackage>, String>;
+    /// Shell command that removes `names`, which have been validated
+    fn remove_script(&self, names: &str) -> String;
+    /// Where downloaded packages are kept
+    fn cache_dirs(&self) -> &'static [&'static str];
+    fn clean_cache_script(&self) -> &'static str;
+}
+
+struct Pacman;
+struct Apt;
+struct Dnf;
+struct Zypper;
+
+fn installed(cmd: &str) -> bool {
+    ["/usr/bin", "/usr/sbin", "/bin", "/sbin"].iter().any(|d| Path::new(&format!("{}/{}", d, cmd)).exists())
+}
+
+fn output(cmd: &str, args: &[&str]) -> Result<Output, String> {
+    Command::new(cmd).args(args).env("LC_ALL", "C").output().map_err(|_| format!("{} is not installed", cmd))
+}
+
+fn stdout(o: &Output) -> String {
+    String::from_utf8_lossy(&o.stdout).to_string()
+}
+
+fn finish(manager: &str, mut updates: Vec<PendingUpdate>, security_known: bool) -> PendingUpdates {
+    updates.sort_by(|a, b| b.security.cmp(&a.security).then(a.name.cmp(&b.name)));
+    let security_count = updates.iter().filter(|u| u.security).count();
+    PendingUpdates { manager: manager.to_string(), updates, security_count, security_known }
+}
+
+// "Installed Size  : 1.50 MiB"
+fn parse_size(text: &str) -> Option<u64> {
+    let (num, unit) = text.trim().split_once(' ')?;
+    let mult = match unit.trim() {
+        "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((num.parse::<f64>().ok()? * mult) as u64)
+}
+
+// rpm is the database under both dnf and zypper
+fn rpm_installed() -> Result<Vec<InstalledPackage>, String> {
+    let o = output("rpm", &["-qa", "--qf", "%{NAME}\t%{VERSION}-%{RELEASE}\t%{SIZE}\t%{SUMMARY}\n"])?;
+    Ok(stdout(&o).lines().filter_map(|l| {
+        let mut f = l.splitn(4, '\t');
+        Some(InstalledPackage {
+            name: f.next()?.to_string(),
+            version: f.next()?.to_string(),
+            size: f.next().and_then(|s| s.parse().ok()),
+            description: f.next().unwrap_or_default().to_string(),
+        })
+    }).collect())
+}
+
+impl Backend for Pacman {
+    fn name(&self) -> &'static str {
+        "pacman"
+    }
+
+    // Blank-line separated "Key : value" blocks
+    fn list_installed(&self) -> Result<Vec<InstalledPackage>, String> {
+        let o = output("pacman", &["-Qi"])?;
+        Ok(stdout(&o).split("\n\n").filter_map(|block| {
+            let field = |key: &str| block.lines().find_map(|l| {
+                let (k, v) = l.split_once(" : ")?;
+                (k.trim() == key).then(|| v.trim().to_string())
+            });
+            Some(InstalledPackage {
+                name: field("Name")?,
+                version: field("Version").unwrap_or_default(),
+                size: field("Installed Size").and_then(|s| parse_size(&s)),
+                description: field("Description").unwrap_or_default(),
+            })
+        }).collect())
+    }
+
+    // "core/linux 6.9.2.arch1-1 [installed]" followed by an indented description
+    fn search(&self, query: &str) -> Result<Vec<SearchResult>, String> {
+        let o = output("pacman", &["-Ss", "--", query])?;
+        let text = stdout(&o);
+        let mut results: Vec<SearchResult> = Vec::new();
+        for line in text.lines() {
+            if let Some(desc) = line.strip_prefix("    ") {
+                if let Some(last) = results.last_mut() {
+                    last.description = desc.trim().to_string();
+                }
+                continue;
+            }
+            let f: Vec<&str> = line.split_whitespace().collect();
+            let Some((repo, name)) = f.first().and_then(|n| n.split_once('/')) else { continue };
+            results.push(SearchResult {
+                name: name.to_string(),
+                version: f.get(1).map(|v| v.to_string()),
+                repo: Some(repo.to_string()),
+                description: String::new(),
+                installed: line.contains("[installed"),
+            });
+        }
+        Ok(results)
+    }
+
+    // "linux 6.9.1.arch1-1 -> 6.9.2.arch1-1"
+    fn get_updates(&self) -> Result<PendingUpdates, String> {
+        // checkupdates syncs into a temporary database; plain -Qu relies on the last `pacman -Sy`
+        let o = if installed("checkupdates") { output("checkupdates", &[])? } else { output("pacman", &["-Qu"])? };
+        // Both exit non-zero when there is nothing to update
+        let text = stdout(&o);
+        // Arch has no advisory metadata in the repos; arch-audit knows which upgrades fix CVEs
+        let advisories: Option<HashSet<String>> = installed("arch-audit")
+            .then(|| output("arch-audit", &["-u", "-f", "%n"]).ok()).flatten()
+            .map(|o| stdout(&o).lines().map(|l| l.trim().to_string()).collect());
+        let updates = text.lines().filter_map(|line| {
+            let f: Vec<&str> = line.split_whitespace().collect();
+            let (name, current, available) = match f.as_slice() {
+                [n, c, "->", a, ..] => (*n, Some(c.to_string()), *a),
+                _ => return None,
+            };
+            Some(PendingUpdate {
+                name: name.to_string(), current, available: available.to_string(), repo: None,
+                security: advisories.as_ref().is_some_and(|a| a.contains(name)),
+            })
+        }).collect();
+        Ok(finish("pacman", updates, advisories.is_some()))
+    }
+
+    fn upgrade_script(&self) -> &'static str {
+        "pacman -Syu --noconfirm"
+    }
+
+    // "gtk2 2.24.33-3"; exits 1 when there are none
+    fn orphans(&self) -> Result<Vec<OrphanPackage>, String> {
+        let o = output("pacman", &["-Qtd"])?;
+        Ok(stdout(&o).lines().filter_map(|l| {
+            let (name, version) = l.split_once(' ')?;
+            Some(OrphanPackage { name: name.to_string(), version: Some(version.to_string()) })
+        }).collect())
+    }
+
+    fn remove_script(&self, names: &str) -> String {
+        // -s takes their own now-unneeded dependencies along, -n the pacsave backups
+        format!("pacman -Rns --noconfirm {}", names)
+    }
+
+    fn cache_dirs(&self) -> &'static [&'static str] {
+        &["/var/cache/pacman/pkg"]
+    }
+
+    // paccache keeps the newest version of each installed package for downgrades and drops
+    // everything of uninstalled ones
+    fn clean_cache_script(&self) -> &'static str {
+        "if command -v paccache >/dev/null; then paccache -rk1; paccache -ruk0; else pacman -Sc --noconfirm; fi"
+    }
+}
+
+impl Backend for Apt {
+    fn name(&self) -> &'static str {
+        "apt"
+    }
+
+    fn list_installed(&self) -> Result<Vec<InstalledPackage>, String> {
+        let o = output("dpkg-query", &["-W", "-f", "${db:Status-Abbrev}\t${Package}\t${Version}\t${Installed-Size}\t${binary:Summary}\n"])?;
+        Ok(stdout(&o).lines().filter_map(|l| {
+            let mut f = l.splitn(5, '\t');
+            // "ii " is installed; removed packages with leftover config files are "rc "
+            if !f.next()?.starts_with("ii") {
+                return None;
+            }
+            Some(InstalledPackage {
+                name: f.next()?.to_string(),
+                version: f.next()?.to_string(),
+                // dpkg counts in KiB
+                size: f.next().and_then(|s| s.parse::<u64>().ok()).map(|k| k * 1024),
+                description: f.next().unwrap_or_default().to_string(),
+            })
+        }).collect())
+    }
+
+    // "htop - interactive processes viewer"
+    fn search(&self, query: &str) -> Result<Vec<SearchResult>, String> {
+        let installed: HashSet<String> = self.list_installed()?.into_iter().map(|p| p.name).collect();
+        let o = output("apt-cache", &["search", "--", query])?;
+        Ok(stdout(&o).lines().filter_map(|l| {
+            let (name, desc) = l.split_once(" - ")?;
+            Some(SearchResult {
+                installed: installed.contains(name),
+                name: name.to_string(), version: None, repo: None, description: desc.to_string(),
+            })
+        }).collect())
+    }
+
+    // "openssl/jammy-security 3.0.2-0ubuntu1.10 amd64 [upgradable from: 3.0.2-0ubuntu1.9]"
+    fn get_updates(&self) -> Result<PendingUpdates, String> {
+        let o = output("apt", &["list", "--upgradable"])?;
+        let updates = stdout(&o).lines().filter_map(|line| {
+            let (name, rest) = line.split_once('/')?;
+            let f: Vec<&str> = rest.split_whitespace().collect();
+            let suites = f.first()?;
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current: line.split_once("upgradable from: ").map(|(_, v)| v.trim_end_matches(']').to_string()),
+                available: f.get(1)?.to_string(),
+                repo: Some(suites.to_string()),
+                // Ubuntu's jammy-security and Debian's bookworm-security pockets
+                security: suites.split(',').any(|s| s.ends_with("-security") || s.contains("/updates")),
+            })
+        }).collect();
+        Ok(finish("apt", updates, true))
+    }
+
+    fn upgrade_script(&self) -> &'static str {
+        // Keeps modified config files instead of stopping at dpkg's question
+        "apt-get update\nDEBIAN_FRONTEND=noninteractive apt-get -y -o Dpkg::Options::=--force-confold upgrade"
+    }
+
+    // Simulation lines: "Remv libfoo1 [1.2-3]"
+    fn orphans(&self) -> Result<Vec<OrphanPackage>, String> {
+        let o = output("apt-get", &["-s", "autoremove"])?;
+        Ok(stdout(&o).lines().filter_map(|l| {
+            let mut f = l.strip_prefix("Remv ")?.split_whitespace();
+            Some(OrphanPackage {
+                name: f.next()?.to_string(),
+                version: f.next().map(|v| v.trim_matches(['[', ']']).to_string()),
+            })
+        }).collect())
+    }
+
+    fn remove_script(&self, names: &str) -> String {
+        format!("DEBIAN_FRONTEND=noninteractive apt-get -y remove {}", names)
+    }
+
+    fn cache_dirs(&self) -> &'static [&'static str] {
+        &["/var/cache/apt/archives"]
+    }
+
+    fn clean_cache_script(&self) -> &'static str {
+        "apt-get clean"
+    }
+}
+
+impl Backend for Dnf {
+    fn name(&self) -> &'static str {
+        "dnf"
+    }
+
+    fn list_installed(&self) -> Result<Vec<InstalledPackage>, String> {
+        rpm_installed()
+    }
+
+    // "htop.x86_64 : Interactive process viewer", under "=== Name Matched: htop ===" headings
+    fn search(&self, query: &str) -> Result<Vec<SearchResult>, String> {
+        let installed: HashSet<String> = rpm_installed()?.into_iter().map(|p| p.name).collect();
+        let o = output("dnf", &["search", "-q", "--", query])?;
+        Ok(stdout(&o).lines().filter_map(|l| {
+            let (name_arch, desc) = l.split_once(" : ")?;
+            let name = name_arch.trim().rsplit_once('.').map_or(name_arch.trim(), |(n, _)| n);
+            Some(SearchResult {
+                installed: installed.contains(name),
+                name: name.to_string(), version: None, repo: None, description: desc.trim().to_string(),
+            })
+        }).collect())
+    }
+
+    // "kernel.x86_64   6.8.9-300.fc40   updates"
+    fn get_updates(&self) -> Result<PendingUpdates, String> {
+        let o = output("dnf", &["check-update", "-q"])?;
+        // 100 means updates are available, 0 none, anything else failed
+        if !matches!(o.status.code(), Some(0) | Some(100)) {
+            return Err(String::from_utf8_lossy(&o.stderr).trim().to_string());
+        }
+        // "FEDORA-2024-1a2b  Important/Sec.  openssl-1:3.2.1-2.fc40.x86_64"
+        let advisories: Option<Vec<String>> = output("dnf", &["updateinfo", "list", "--security", "-q"]).ok()
+            .filter(|o| o.status.success())
+            .map(|o| stdout(&o).lines().filter_map(|l| l.split_whitespace().nth(2).map(String::from)).collect());
+        let updates = stdout(&o).lines().filter_map(|line| {
+            let f: Vec<&str> = line.split_whitespace().collect();
+            let [name_arch, version, repo] = f.as_slice() else { return None };
+            let name = name_arch.rsplit_once('.').map_or(*name_arch, |(n, _)| n);
+            Some(PendingUpdate {
+                name: name.to_string(), current: None, available: version.to_string(), repo: Some(repo.to_string()),
+                // Advisory entries are full NEVRAs of the fixed package
+                security: advisories.as_ref().is_some_and(|a| a.iter().any(|nevra| {
+                    nevra.strip_prefix(name).is_some_and(|rest| rest.starts_with('-') && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+                })),
+            })
+        }).collect();
+        Ok(finish("dnf", updates, advisories.is_some()))
+    }
+
+    fn upgrade_script(&self) -> &'static str {
+        "dnf -y upgrade --refresh"
+    }
+
+    fn orphans(&self) -> Result<Vec<OrphanPackage>, String> {
+        let o = output("dnf", &["repoquery", "--unneeded", "-q", "--qf", "%{name} %{version}-%{release}\n"])?;
+        if !o.status.success() {
+            return Err(String::from_utf8_lossy(&o.stderr).trim().to_string());
+        }
+        Ok(stdout(&o).lines().filter_map(|l| {
+            let (name, version) = l.split_once(' ')?;
+            Some(OrphanPackage { name: name.to_string(), version: Some(version.to_string()) })
+        }).collect())
+    }
+
+    fn remove_script(&self, names: &str) -> String {
+        format!("dnf -y remove {}", names)
+    }
+
+    // dnf5 moved its cache
+    fn cache_dirs(&self) -> &'static [&'static str] {
+        &["/var/cache/dnf", "/var/cache/libdnf5"]
+    }
+
+    // "packages" keeps the repository metadata, so the next update doesn't redownload it
+    fn clean_cache_script(&self) -> &'static str {
+        "dnf clean packages"
+    }
+}
+
+// zypper prints tables: "S | Name | Summary | Type" with a "--+--" rule under the header
+fn zypper_rows(text: &str) -> Vec<Vec<String>> {
+    text.lines()
+        .filter(|l| l.contains('|') && !l.contains("-+-"))
+        .skip(1)
+        .map(|l| l.split('|').map(|c| c.trim().to_string()).collect())
+        .collect()
+}
+
+impl Backend for Zypper {
+    fn name(&self) -> &'static str {
+        "zypper"
+    }
+
+    fn list_installed(&self) -> Result<Vec<InstalledPackage>, String> {
+        rpm_installed()
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<SearchResult>, String> {
+        let o = output("zypper", &["--non-interactive", "--quiet", "search", "--type", "package", "--", query])?;
+        Ok(zypper_rows(&stdout(&o)).into_iter().filter_map(|row| {
+            let [status, name, summary, ..] = row.as_slice() else { return None };
+            Some(SearchResult {
+                name: name.clone(), version: None, repo: None, description: summary.clone(),
+                installed: status.starts_with('i'),
+            })
+        }).collect())
+    }
+
+    // "v | Main Repository | openssl | 3.1.4-1.1 | 3.1.4-2.1 | x86_64"
+    fn get_updates(&self) -> Result<PendingUpdates, String> {
+        let o = output("zypper", &["--non-interactive", "--quiet", "list-updates"])?;
+        let updates = zypper_rows(&stdout(&o)).into_iter().filter_map(|row| {
+            let [_, repo, name, current, available, ..] = row.as_slice() else { return None };
+            Some(PendingUpdate {
+                name: name.clone(), current: Some(current.clone()), available: available.clone(), repo: Some(repo.clone()),
+                // Advisories are patches, which don't map onto single package updates here
+                security: false,
+            })
+        }).collect();
+        Ok(finish("zypper", updates, false))
+    }
+
+    fn upgrade_script(&self) -> &'static str {
+        "zypper --non-interactive refresh\nzypper --non-interactive update"
+    }
+
+    // "i | @System | libfoo1 | 1.2-3.1 | x86_64"
+    fn orphans(&self) -> Result<Vec<OrphanPackage>, String> {
+        let o = output("zypper", &["--non-interactive", "--quiet", "packages", "--unneeded"])?;
+        Ok(zypper_rows(&stdout(&o)).into_iter().filter_map(|row| {
+            let [_, _, name, version, ..] = row.as_slice() else { return None };
+            Some(OrphanPackage { name: name.clone(), version: Some(version.clone()) })
+        }).collect())
+    }
+
+    fn remove_script(&self, names: &str) -> String {
+        format!("zypper --non-interactive remove --clean-deps {}", names)
+    }
+
+    fn cache_dirs(&self) -> &'static [&'static str] {
+        &["/var/cache/zypp/packages"]
+    }
+
+    fn clean_cache_script(&self) -> &'static str {
+        "zypper --non-interactive clean"
+    }
+}
+
+// pacman first: Arch users sometimes install apt or dnf to build chroots for other distros
+fn backend() -> Option<Box<dyn Backend>> {
+    if installed("pacman") {
+        Some(Box::new(Pacman))
+    } else if installed("apt") && installed("dpkg-query") {
+        Some(Box::new(Apt))
+    } else if installed("dnf") {
+        Some(Box::new(Dnf))
+    } else if installed("zypper") {
+        Some(Box::new(Zypper))
+    } else {
+        None
+    }
+}
+
+fn check() -> Result<PendingUpdates, String> {
+    match backend() {
+        Some(b) => b.get_updates(),
+        None => Ok(finish("none", Vec::new(), false)),
+    }
+}
+
+fn remember(app: &AppHandle, result: &Result<PendingUpdates, String>) {
+    let state = app.state::<AppState>();
+    if let Ok(u) = result {
+        *state.updates.lock().unwrap() = Some(UpdateCount { total: u.updates.len(), security: u.security_count });
+    }
+    state.health.lock().unwrap().record("updates", None, result);
+}
+
+#[derive(Clone, serde::Serialize)]
+struct UpgradeLine {
+    line: String,
+}
+
+// One pkexec prompt for the whole script. stderr is merged into stdout so progress and
+// errors reach `on_line` in order; on failure the last line is usually the reason.
+fn run_root(script: &str, mut on_line: impl FnMut(&str)) -> Result<(), String> {
+    let elevated = unsafe { libc::geteuid() } != 0;
+    let mut cmd = if elevated {
+        let mut c = Command::new("pkexec");
+        c.arg("sh");
+        c
+    } else {
+        Command::new("sh")
+    };
+    let mut child = cmd.args(["-e", "-s"]).env("LC_ALL", "C")
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::null())
+        .spawn().map_err(|e| e.to_string())?;
+    let script = format!("exec 2>&1\n{}\n", script);
+    child.stdin.take().ok_or("No stdin")?.write_all(script.as_bytes()).map_err(|e| e.to_string())?;
+    let stdout = child.stdout.take().ok_or("No output")?;
+    let mut last = String::new();
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        on_line(&line);
+        if !line.trim().is_empty() {
+            last = line;
+        }
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    match status.code() {
+        Some(0) => Ok(()),
+        // pkexec: dismissed or not authorized; from sh itself they mean a command couldn't run
+        Some(126) | Some(127) if elevated => Err("Authentication was cancelled".to_string()),
+        _ => Err(if last.is_empty() { "Package manager failed".to_string() } else { last }),
+    }
+}
+
+fn upgrade(app: &AppHandle) -> Result<(), String> {
+    let backend = backend().ok_or("No supported package manager found")?;
+    run_root(backend.upgrade_script(), |line| {
+        let _ = app.emit_all("updates://output", UpgradeLine { line: line.to_string() });
+    })
+}
+
+fn cache_size(backend: &dyn Backend) -> u64 {
+    backend.cache_dirs().iter().map(|d| crate::cleanup::dir_size(Path::new(d))).sum()
+}
+
+// Package names are passed to a shell script, so anything beyond what distros allow is refused
+fn valid_package_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || "+-._:@".contains(c))
+}
+
+/// Checks once in the background at startup, so the security audit has a count without waiting.
+pub fn spawn_check(app: AppHandle) {
+    tauri::async_runtime::spawn_blocking(move || remember(&app, &check()));
+}
+
+// --- Commands ---
+
+/// The detected package manager: pacman, apt, dnf, zypper or none.
+#[tauri::command]
+pub fn get_package_manager() -> String {
+    backend().map_or("none", |b| b.name()).to_string()
+}
+
+/// Every installed package, sorted by name.
+#[tauri::command]
+pub async fn list_installed_packages() -> Result<Vec<InstalledPackage>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut packages = backend().ok_or("No supported package manager found")?.list_installed()?;
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(packages)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Packages in the configured repositories matching `query`.
+#[tauri::command]
+pub async fn search_packages(query: String) -> Result<Vec<SearchResult>, String> {
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    tauri::async_runtime::spawn_blocking(move || backend().ok_or("No supported package manager found")?.search(&query))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Available updates from the distro package manager, security fixes first.
+#[tauri::command]
+pub async fn get_pending_updates(app: AppHandle) -> Result<PendingUpdates, String> {
+    let result = tauri::async_runtime::spawn_blocking(check)
+        .await
+        .map_err(|e| e.to_string())?;
+    remember(&app, &result);
+    result
+}
+
+/// Packages pulled in as dependencies that nothing installed needs anymore.
+#[tauri::command]
+pub async fn get_orphan_packages() -> Result<Vec<OrphanPackage>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut orphans = backend().ok_or("No supported package manager found")?.orphans()?;
+        orphans.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(orphans)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Uninstalls `names` as root. Meant for the orphans, but any installed package works.
+#[tauri::command]
+pub async fn remove_packages(names: Vec<String>) -> Result<(), String> {
+    if names.is_empty() {
+        return Ok(());
+    }
+    if let Some(bad) = names.iter().find(|n| !valid_package_name(n)) {
+        return Err(format!("Invalid package name: {}", bad));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let backend = backend().ok_or("No supported package manager found")?;
+        run_root(&backend.remove_script(&names.join(" ")), |_| {})
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Bytes used by the package manager's download cache.
+#[tauri::command]
+pub async fn get_package_cache_size() -> Result<u64, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let backend = backend().ok_or("No supported package manager found")?;
+        Ok(cache_size(backend.as_ref()))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Deletes cached package downloads as root and returns the bytes freed.
+#[tauri::command]
+pub async fn clean_package_cache() -> Result<u64, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let backend = backend().ok_or("No supported package manager found")?;
+        let before = cache_size(backend.as_ref());
+        run_root(backend.clean_cache_script(), |_| {})?;
+        Ok(before.saturating_sub(cache_size(backend.as_ref())))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Upgrades every package through the detected package manager as root. Output lines are
+/// emitted as `updates://output` while it runs.
+#[tauri::command]
+pub async fn apply_updates(app: AppHandle) -> Result<(), String> {
+    if app.state::<AppState>().upgrading.swap(true, Ordering::SeqCst) {
+        return Err("An upgrade is already running".to_string());
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = upgrade(&app);
+        app.state::<AppState>().upgrading.store(false, Ordering::SeqCst);
+        // Whatever happened, the pending list has likely changed
+        remember(&app, &check());
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,68 @@
+use std::process::Command;
+
+#[derive(serde::Serialize)]
+pub struct PciDevice {
+    /// Bus address, e.g. "00:02.0"
+    slot: String,
+    /// e.g. "VGA compatible controller"
+    class: String,
+    /// Four hex digits, e.g. "0300"
+    class_id: String,
+    vendor: String,
+    vendor_id: String,
+    device: String,
+    device_id: String,
+    /// Kernel driver bound to the device, if any
+    driver: Option<String>,
+    /// Modules able to drive it
+    modules: Vec<String>,
+}
+
+impl PciDevice {
+    /// Display controllers: VGA, XGA and 3D
+    pub fn is_gpu(&self) -> bool {
+        self.class_id.starts_with("03")
+    }
+
+    pub fn name(&self) -> String {
+        format!("{} {}", self.vendor, self.device)
+    }
+}
+
+// "Intel Corporation [8086]" into the name and its id
+fn split_id(value: &str) -> (String, String) {
+    match value.trim().rsplit_once(" [") {
+        Some((name, id)) => (name.to_string(), id.trim_end_matches(']').to_string()),
+        None => (value.trim().to_string(), String::new()),
+    }
+}
+
+/// Every PCI device, via `lspci -vmm -k -nn`: blank-line separated "Key:\tValue" records.
+pub fn pci_devices() -> Vec<PciDevice> {
+    let Ok(output) = Command::new("lspci").args(["-vmm", "-k", "-nn"]).output() else { return Vec::new() };
+    String::from_utf8_lossy(&output.stdout).split("\n\n").filter_map(|record| {
+        let field = |key: &str| record.lines().find_map(|l| l.strip_prefix(key)?.strip_prefix(":\t")).map(str::trim);
+        let (class, class_id) = split_id(field("Class")?);
+        let (vendor, vendor_id) = split_id(field("Vendor").unwrap_or_default());
+        let (device, device_id) = split_id(field("Device").unwrap_or_default());
+        Some(PciDevice {
+            slot: field("Slot")?.to_string(),
+            class,
+            class_id,
+            vendor,
+            vendor_id,
+            device,
+            device_id,
+            driver: field("Driver").map(String::from),
+            modules: record.lines().filter_map(|l| l.strip_prefix("Module:\t")).map(|m| m.trim().to_string()).collect(),
+        })
+    }).collect()
+}
+
+// --- Commands ---
+
+/// All PCI devices in bus order, with the driver in use.
+#[tauri::command]
+pub fn get_pci_devices() -> Vec<PciDevice> {
+    pci_devices()
+}
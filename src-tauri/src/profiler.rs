@@ -0,0 +1,167 @@
+use perf_event::events::Software;
+use perf_event::{Builder, Counter};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{config, AppState};
+
+#[derive(Clone, serde::Serialize)]
+pub struct ProfileSample {
+    pid: u32,
+    page_faults: f64,
+    context_switches: f64,
+    cpu_migrations: f64,
+}
+
+fn open_counter(pid: u32, kind: Software) -> Result<Counter, String> {
+    let mut builder = Builder::new().observe_pid(pid as i32).kind(kind);
+    // Count threads the process spawns after we attach, not just the main thread
+    builder.inherit(true);
+    let mut counter = builder.build().map_err(|e| match e.raw_os_error() {
+        Some(1) | Some(13) => "Permission denied (check kernel.perf_event_paranoid)".to_string(),
+        _ => e.to_string(),
+    })?;
+    counter.enable().map_err(|e| e.to_string())?;
+    Ok(counter)
+}
+
+// 99 Hz rather than 100 so samples do not line up with timer ticks
+const PERF_RECORD_HZ: &str = "99";
+const MAX_CAPTURE_SECS: u64 = 60;
+
+fn frame_symbol(line: &str) -> Option<String> {
+    // "    7f3a1c2b4d5e symbol+0x1a (/usr/lib/libfoo.so)"
+    let mut parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() < 2 {
+        return None;
+    }
+    if parts.last().is_some_and(|p| p.starts_with('(')) {
+        parts.pop();
+    }
+    let symbol = parts[1..].join(" ");
+    let symbol = match symbol.rfind("+0x") {
+        Some(idx) => symbol[..idx].to_string(),
+        None => symbol,
+    };
+    // ';' separates frames in the folded format
+    Some(symbol.replace(';', ":"))
+}
+
+/// Folds `perf script` output into `comm;outer;...;leaf count` lines.
+fn collapse_stacks(script: &str) -> String {
+    let mut folded: BTreeMap<String, u64> = BTreeMap::new();
+
+    for block in script.split("\n\n") {
+        let mut lines = block.lines().filter(|l| !l.trim().is_empty());
+        let Some(header) = lines.next() else { continue };
+        let comm = header.split_whitespace().next().unwrap_or("unknown");
+        // perf lists the leaf frame first
+        let mut frames: Vec<String> = lines.filter_map(frame_symbol).collect();
+        frames.push(comm.to_string());
+        frames.reverse();
+        *folded.entry(frames.join(";")).or_insert(0) += 1;
+    }
+
+    let mut out = String::new();
+    for (stack, count) in folded {
+        let _ = writeln!(out, "{} {}", stack, count);
+    }
+    out
+}
+
+// perf runs as us, but a fixed name in /tmp could be a symlink someone planted
+fn record_profile(pid: u32, seconds: u64) -> Result<String, String> {
+    let dir = config::private_temp_dir("glassview-perf")?;
+    let result = record_into(&dir.join("perf.data"), pid, seconds);
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+fn record_into(data: &Path, pid: u32, seconds: u64) -> Result<String, String> {
+    let record = Command::new("perf")
+        .args(["record", "-F", PERF_RECORD_HZ, "-g", "-p", &pid.to_string(), "-o"])
+        .arg(data)
+        .args(["--", "sleep", &seconds.to_string()])
+        .output()
+        .map_err(|_| "perf is not installed".to_string())?;
+    if !record.status.success() {
+        return Err(String::from_utf8_lossy(&record.stderr).trim().to_string());
+    }
+
+    let script = Command::new("perf").arg("script").arg("-i").arg(data).output().map_err(|e| e.to_string())?;
+    if !script.status.success() {
+        return Err(String::from_utf8_lossy(&script.stderr).trim().to_string());
+    }
+    Ok(collapse_stacks(&String::from_utf8_lossy(&script.stdout)))
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn start_profiling(pid: u32, app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    let mut faults = open_counter(pid, Software::PAGE_FAULTS)?;
+    let mut switches = open_counter(pid, Software::CONTEXT_SWITCHES)?;
+    let mut migrations = open_counter(pid, Software::CPU_MIGRATIONS)?;
+
+    // Only one profiling session at a time; starting a new one replaces the old
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Some(prev) = state.profiler.lock().unwrap().replace(stop.clone()) {
+        prev.store(true, Ordering::Relaxed);
+    }
+
+    std::thread::spawn(move || {
+        let proc_dir = format!("/proc/{}", pid);
+        let mut last = [0u64; 3];
+        let mut last_at = Instant::now();
+
+        while !stop.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_secs(1));
+            if !Path::new(&proc_dir).exists() {
+                break;
+            }
+
+            let now = [
+                faults.read().unwrap_or(last[0]),
+                switches.read().unwrap_or(last[1]),
+                migrations.read().unwrap_or(last[2]),
+            ];
+            let secs = last_at.elapsed().as_secs_f64();
+            let rate = |i: usize| now[i].saturating_sub(last[i]) as f64 / secs;
+
+            let _ = app.emit_all("profile://sample", ProfileSample {
+                pid,
+                page_faults: rate(0),
+                context_switches: rate(1),
+                cpu_migrations: rate(2),
+            });
+
+            last = now;
+            last_at = Instant::now();
+        }
+        let _ = app.emit_all("profile://stopped", pid);
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_profiling(state: State<AppState>) {
+    if let Some(stop) = state.profiler.lock().unwrap().take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Records call stacks of `pid` and returns them in the folded format flame graph renderers take.
+#[tauri::command]
+pub async fn capture_profile(pid: u32, seconds: u64) -> Result<String, String> {
+    let seconds = seconds.clamp(1, MAX_CAPTURE_SECS);
+    tauri::async_runtime::spawn_blocking(move || record_profile(pid, seconds))
+        .await
+        .map_err(|e| e.to_string())?
+}
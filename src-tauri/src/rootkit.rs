@@ -0,0 +1,121 @@
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Manager};
+
+#[derive(Clone, serde::Serialize)]
+pub struct RootkitWarning {
+    /// Test group the check belongs to, e.g. "Performing file properties checks"
+    section: String,
+    check: String,
+    /// Verdict as the scanner printed it, e.g. "Warning" or "INFECTED"
+    result: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct RootkitProgress {
+    checks: u32,
+    current: String,
+    warnings: usize,
+}
+
+#[derive(serde::Serialize)]
+pub struct RootkitReport {
+    /// rkhunter or chkrootkit
+    scanner: String,
+    checks: u32,
+    warnings: Vec<RootkitWarning>,
+    /// rkhunter explains each warning in its log
+    log: Option<String>,
+}
+
+fn installed(cmd: &str) -> Option<String> {
+    ["/usr/bin", "/usr/sbin", "/usr/local/bin", "/usr/local/sbin"].iter()
+        .map(|d| format!("{}/{}", d, cmd))
+        .find(|p| Path::new(p).exists())
+}
+
+// "    /usr/bin/lwp-request                       [ Warning ]"
+fn parse_rkhunter(line: &str) -> Option<(String, String)> {
+    let (check, rest) = line.trim_end().rsplit_once('[')?;
+    let result = rest.strip_suffix(']')?.trim();
+    Some((check.trim().to_string(), result.to_string()))
+}
+
+fn rkhunter_suspicious(result: &str) -> bool {
+    result == "Warning" || result == "Found" || result == "Suspicious"
+}
+
+// "Checking `ls'... not infected" or "Checking `lkm'... chkproc: Warning: Possible LKM Trojan installed"
+fn parse_chkrootkit(line: &str) -> Option<(String, String)> {
+    let rest = line.strip_prefix("Checking `")?;
+    let (check, result) = rest.split_once("'...")?;
+    Some((check.to_string(), result.trim().to_string()))
+}
+
+fn chkrootkit_suspicious(result: &str) -> bool {
+    let r = result.to_lowercase();
+    (r.contains("infected") && !r.contains("not infected"))
+        || r.contains("warning")
+        || (r.contains("vulnerable") && !r.contains("not vulnerable"))
+}
+
+fn scan(app: &AppHandle) -> Result<RootkitReport, String> {
+    let (scanner, path) = installed("rkhunter").map(|p| ("rkhunter", p))
+        .or_else(|| installed("chkrootkit").map(|p| ("chkrootkit", p)))
+        .ok_or("Neither rkhunter nor chkrootkit is installed")?;
+    // Both read files only root may open
+    let mut cmd = if unsafe { libc::geteuid() } == 0 {
+        Command::new(&path)
+    } else {
+        let mut c = Command::new("pkexec");
+        c.arg(&path);
+        c
+    };
+    if scanner == "rkhunter" {
+        cmd.args(["--check", "--skip-keypress", "--nocolors", "--no-mail-on-warning"]);
+    }
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::null()).spawn().map_err(|e| e.to_string())?;
+    let stdout = child.stdout.take().ok_or("No scanner output")?;
+
+    let mut report = RootkitReport { scanner: scanner.to_string(), checks: 0, warnings: Vec::new(), log: None };
+    let mut section = String::new();
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        let parsed = if scanner == "rkhunter" { parse_rkhunter(&line) } else { parse_chkrootkit(&line) };
+        let Some((check, result)) = parsed else {
+            let heading = line.trim();
+            if heading.starts_with("Performing") || heading.starts_with("Checking ") {
+                section = heading.trim_end_matches('.').to_string();
+            }
+            continue;
+        };
+        report.checks += 1;
+        let suspicious = if scanner == "rkhunter" { rkhunter_suspicious(&result) } else { chkrootkit_suspicious(&result) };
+        if suspicious {
+            report.warnings.push(RootkitWarning { section: section.clone(), check: check.clone(), result });
+        }
+        let _ = app.emit_all("rootkit://progress", RootkitProgress {
+            checks: report.checks, current: check, warnings: report.warnings.len(),
+        });
+    }
+    let status = child.wait().map_err(|e| e.to_string())?;
+    // pkexec returns 126 or 127 when authentication was dismissed or failed
+    if matches!(status.code(), Some(126) | Some(127)) && report.checks == 0 {
+        return Err("Authentication was cancelled".to_string());
+    }
+    if scanner == "rkhunter" {
+        report.log = Some("/var/log/rkhunter.log".to_string());
+    }
+    Ok(report)
+}
+
+// --- Commands ---
+
+/// Runs rkhunter, or chkrootkit when rkhunter isn't installed, as root. Emits
+/// `rootkit://progress` for every completed check.
+#[tauri::command]
+pub async fn run_rootkit_scan(app: AppHandle) -> Result<RootkitReport, String> {
+    tauri::async_runtime::spawn_blocking(move || scan(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+use tauri::State;
+
+use crate::{config, set_io_priority, AppState};
+
+const RULES_FILE: &str = "rules.toml";
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct NiceRule {
+    /// Matched against the process name or its executable's file name
+    name: String,
+    nice: Option<i32>,
+    io_class: Option<String>,
+    io_level: Option<u8>,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct RuleFile {
+    rules: Vec<NiceRule>,
+}
+
+#[derive(Default)]
+pub struct RuleEngine {
+    rules: Vec<NiceRule>,
+    // PIDs already handled, so each process is only reniced once
+    applied: HashSet<u32>,
+}
+
+impl RuleEngine {
+    pub fn load() -> Self {
+        let file: RuleFile = config::load(RULES_FILE);
+        RuleEngine { rules: file.rules, applied: HashSet::new() }
+    }
+
+    /// Applies matching rules to processes that appeared since the last call.
+    /// Expects `sys` to have fresh process data.
+    pub fn enforce(&mut self, sys: &System) {
+        if self.rules.is_empty() {
+            return;
+        }
+        self.applied.retain(|pid| sys.process(sysinfo::Pid::from_u32(*pid)).is_some());
+
+        for (pid, process) in sys.processes() {
+            let pid = pid.as_u32();
+            if self.applied.contains(&pid) {
+                continue;
+            }
+            let exe_name = process.exe().file_name().map(|n| n.to_string_lossy().to_string());
+            let rule = self.rules.iter()
+                .find(|r| r.name == process.name() || Some(&r.name) == exe_name.as_ref());
+
+            if let Some(rule) = rule {
+                if let Some(nice) = rule.nice {
+                    unsafe { libc::setpriority(libc::PRIO_PROCESS, pid, nice) };
+                }
+                if let Some(class) = &rule.io_class {
+                    set_io_priority(pid, class.clone(), rule.io_level.unwrap_or(4));
+                }
+            }
+            self.applied.insert(pid);
+        }
+    }
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_nice_rules(state: State<AppState>) -> Vec<NiceRule> {
+    state.rules.lock().unwrap().rules.clone()
+}
+
+#[tauri::command]
+pub fn set_nice_rules(rules: Vec<NiceRule>, state: State<AppState>) -> Result<(), String> {
+    config::save(RULES_FILE, &RuleFile { rules: rules.clone() })?;
+    let mut engine = state.rules.lock().unwrap();
+    engine.rules = rules;
+    // Re-check running processes against the new rules
+    engine.applied.clear();
+    Ok(())
+}
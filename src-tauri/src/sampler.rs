@@ -0,0 +1,120 @@
+use std::time::{Duration, Instant};
+use sysinfo::SystemExt;
+use tauri::{AppHandle, Manager};
+
+use crate::health::{unix_millis, Sampled};
+use crate::{app_firewall, collect_processes, collect_stats, gpu, history, storage, tray, watch, AppState, ProcInfo, SystemStats};
+
+// statvfs on every mount is cheap but not worth doing every tick
+const INODE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const GPU_VARIABLES: [&str; 3] = ["gpu_busy", "vram_used", "vram_total"];
+
+// GPU counters are read only for something that shows them: the tray, a watch, or the
+// dashboard while the window is up. Sampling them otherwise keeps idle dGPUs awake.
+fn gpu_wanted(app: &AppHandle, tray_metrics: &[String]) -> bool {
+    tray_metrics.iter().any(|m| m == "gpu" || m == "vram")
+        || app.state::<AppState>().watches.lock().unwrap().uses(&GPU_VARIABLES)
+        || app.get_window("main").is_some_and(|w| w.is_visible().unwrap_or(true) && !w.is_minimized().unwrap_or(false))
+}
+
+/// Latest collected data, served to commands without touching `System` again.
+#[derive(Default)]
+pub struct Snapshot {
+    pub stats: SystemStats,
+    pub processes: Vec<ProcInfo>,
+    pub kernel_threads: Vec<ProcInfo>,
+    /// Unix milliseconds of the last stats and process refresh
+    pub stats_at: u64,
+    pub processes_at: u64,
+}
+
+/// Refreshes `System` at the configured rates and pushes the results to the window.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let start = Instant::now();
+        let (mut next_stats, mut next_procs, mut next_history) = (start, start, start);
+        let mut next_inode_check = start;
+        // The first refresh has no previous sample, so its deltas are not meaningful
+        let mut last_procs: Option<Instant> = None;
+        let mut tray_meter = tray::TrayMeter::default();
+        let mut thermometer = storage::DriveThermometer::default();
+
+        loop {
+            let state = app.state::<AppState>();
+            // Re-read every tick so changed settings apply without a restart
+            let settings = state.settings.lock().unwrap().clone();
+            let now = Instant::now();
+            let stats_due = now >= next_stats;
+            let procs_due = now >= next_procs;
+            // Read before taking the locks; smartctl runs can take a moment
+            let drive_temps = if stats_due { thermometer.read() } else { Vec::new() };
+            let gpus = if stats_due && gpu_wanted(&app, &settings.tray_metrics) { gpu::readings() } else { Vec::new() };
+            let mut firewall_script = None;
+            let mut ledger_due = false;
+
+            {
+                let mut sys = state.sys.lock().unwrap();
+                let mut snapshot = state.snapshot.lock().unwrap();
+
+                if stats_due {
+                    sys.refresh_cpu();
+                    sys.refresh_memory();
+                    sys.refresh_networks();
+                    sys.refresh_components();
+                    snapshot.stats = collect_stats(&sys, &settings, drive_temps, &gpus);
+                    snapshot.stats_at = unix_millis();
+                    let interval = Some(settings.stats_interval_ms);
+                    let _ = app.emit_all("stats://update", Sampled::new(snapshot.stats_at, interval, &snapshot.stats));
+                    state.health.lock().unwrap().ok("stats", interval);
+                    tray_meter.update(&app, &settings.tray_metrics, &sys, &snapshot.stats);
+                    let watches = state.watches.lock().unwrap().evaluate(&sys, &snapshot.stats);
+                    watch::publish(&app, &watches);
+                    next_stats = now + Duration::from_millis(settings.stats_interval_ms);
+
+                    if now >= next_history {
+                        history::record(&mut state.history.lock().unwrap(), &snapshot.stats);
+                        next_history = now + Duration::from_secs(settings.history_resolution_secs);
+                    }
+                }
+
+                if procs_due {
+                    sys.refresh_processes();
+                    state.rules.lock().unwrap().enforce(&sys);
+                    firewall_script = state.app_firewall.lock().unwrap().plan(&sys, &settings.app_firewall_rules);
+                    if let Some(last) = last_procs {
+                        ledger_due = state.offenders.lock().unwrap().record(&sys, now.duration_since(last).as_secs_f64());
+                    }
+                    last_procs = Some(now);
+                    (snapshot.processes, snapshot.kernel_threads) = collect_processes(&sys, &settings);
+                    snapshot.processes_at = unix_millis();
+                    let interval = Some(settings.process_interval_ms);
+                    let _ = app.emit_all("processes://update", Sampled::new(snapshot.processes_at, interval, &snapshot.processes));
+                    if settings.separate_kernel_threads {
+                        let _ = app.emit_all("kernel_threads://update", Sampled::new(snapshot.processes_at, interval, &snapshot.kernel_threads));
+                    }
+                    state.health.lock().unwrap().ok("processes", interval);
+                    next_procs = now + Duration::from_millis(settings.process_interval_ms);
+                }
+            }
+
+            // After the locks are released, since nft can take a moment
+            if let Some(script) = firewall_script {
+                let result = app_firewall::apply(&script);
+                state.health.lock().unwrap().record("app_firewall", None, &result);
+                state.app_firewall.lock().unwrap().applied(result);
+            }
+            if ledger_due {
+                state.offenders.lock().unwrap().save();
+            }
+
+            if now >= next_inode_check {
+                storage::check_inodes(&app);
+                state.health.lock().unwrap().ok("inodes", Some(INODE_CHECK_INTERVAL.as_millis() as u64));
+                next_inode_check = now + INODE_CHECK_INTERVAL;
+            }
+
+            let wake = next_stats.min(next_procs);
+            tokio::time::sleep(wake.saturating_duration_since(Instant::now())).await;
+        }
+    });
+}
@@ -0,0 +1,318 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+// EFI global variable GUID the SecureBoot variable lives under
+const EFI_GLOBAL_GUID: &str = "8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+#[derive(serde::Serialize)]
+pub struct SandboxFinding {
+    /// The unit setting the check is about, e.g. "PrivateNetwork="
+    name: String,
+    description: String,
+    /// True when the unit already has the protection
+    passed: bool,
+    /// Contribution to the overall score; absent for passed checks
+    exposure: Option<f32>,
+}
+
+#[derive(serde::Serialize)]
+pub struct UnitSecurity {
+    unit: String,
+    /// 0.0 (locked down) to 10.0 (fully exposed)
+    exposure: f32,
+    /// systemd's rating: SAFE, OK, MEDIUM, EXPOSED or UNSAFE
+    rating: String,
+    findings: Vec<SandboxFinding>,
+}
+
+// "✗ PrivateNetwork=          Service has access to the host's network          0.5"
+fn parse_finding(line: &str) -> Option<SandboxFinding> {
+    let (passed, rest) = if let Some(r) = line.strip_prefix('✓') {
+        (true, r)
+    } else {
+        (false, line.strip_prefix('✗')?)
+    };
+    // Columns are padded with runs of spaces; descriptions only contain single spaces
+    let cols: Vec<&str> = rest.split("  ").map(str::trim).filter(|c| !c.is_empty()).collect();
+    let name = cols.first()?.to_string();
+    let exposure = cols.last().filter(|_| cols.len() > 2).and_then(|v| v.parse().ok());
+    let description = cols.get(1).filter(|d| d.parse::<f32>().is_err()).unwrap_or(&"").to_string();
+    Some(SandboxFinding { name, description, passed, exposure })
+}
+
+// Bit, letter and meaning of each /proc/sys/kernel/tainted flag (Documentation/admin-guide/tainted-kernels.rst)
+const TAINT_FLAGS: &[(u32, char, &str)] = &[
+    (0, 'P', "Proprietary module loaded"),
+    (1, 'F', "Module was force-loaded"),
+    (2, 'S', "Kernel running on an out-of-spec system"),
+    (3, 'R', "Module was force-unloaded"),
+    (4, 'M', "Processor reported a machine check exception"),
+    (5, 'B', "Bad page referenced or unexpected page flags"),
+    (6, 'U', "Taint requested by userspace"),
+    (7, 'D', "Kernel died recently (oops or BUG)"),
+    (8, 'A', "ACPI table overridden by user"),
+    (9, 'W', "Kernel issued a warning"),
+    (10, 'C', "Staging driver loaded"),
+    (11, 'I', "Working around a severe firmware bug"),
+    (12, 'O', "Out-of-tree module loaded"),
+    (13, 'E', "Unsigned module loaded"),
+    (14, 'L', "Soft lockup occurred"),
+    (15, 'K', "Kernel has been live patched"),
+    (16, 'X', "Auxiliary taint (distribution specific)"),
+    (17, 'T', "Kernel built with the struct randomization plugin"),
+    (18, 'N', "In-kernel test has been run"),
+];
+
+#[derive(serde::Serialize)]
+pub struct TaintFlag {
+    bit: u32,
+    letter: char,
+    reason: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct KernelIntegrity {
+    /// Raw /proc/sys/kernel/tainted value; 0 is an untainted kernel
+    taint: u64,
+    flags: Vec<TaintFlag>,
+    unsigned_modules: Vec<String>,
+    out_of_tree_modules: Vec<String>,
+    /// Unsigned modules are refused outright
+    sig_enforce: bool,
+    /// none, integrity or confidentiality; absent when the LSM isn't built in
+    lockdown: Option<String>,
+}
+
+/// The kernel's taint bitmask; 0 when untainted or unreadable.
+pub fn kernel_taint() -> u64 {
+    fs::read_to_string("/proc/sys/kernel/tainted").ok()
+        .and_then(|t| t.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// "uefi" or "bios", depending on how the running kernel was booted.
+pub fn boot_firmware() -> &'static str {
+    if Path::new("/sys/firmware/efi").exists() { "uefi" } else { "bios" }
+}
+
+/// "enabled", "disabled", "not_uefi" or "unknown" when firmware variables can't be read.
+pub fn secure_boot_state() -> &'static str {
+    if boot_firmware() != "uefi" {
+        return "not_uefi";
+    }
+    // efivarfs prefixes the value with 4 bytes of attributes; the old sysfs interface exposes it bare
+    let value = fs::read(format!("/sys/firmware/efi/efivars/SecureBoot-{}", EFI_GLOBAL_GUID))
+        .ok().and_then(|b| b.get(4).copied())
+        .or_else(|| fs::read(format!("/sys/firmware/efi/vars/SecureBoot-{}/data", EFI_GLOBAL_GUID)).ok()?.first().copied());
+    match value {
+        Some(1) => "enabled",
+        Some(_) => "disabled",
+        // efivarfs not mounted; mokutil may still get at it
+        None => match Command::new("mokutil").arg("--sb-state").output() {
+            Ok(o) => {
+                let text = String::from_utf8_lossy(&o.stdout).to_lowercase();
+                if text.contains("secureboot enabled") {
+                    "enabled"
+                } else if text.contains("secureboot disabled") {
+                    "disabled"
+                } else {
+                    "unknown"
+                }
+            }
+            Err(_) => "unknown",
+        },
+    }
+}
+
+/// Recommended value of a hardening sysctl.
+enum Expect {
+    Equals(i64),
+    AtLeast(i64),
+}
+
+// Key, recommended value and what the recommendation protects against
+const SYSCTL_CHECKS: &[(&str, Expect, &str)] = &[
+    ("kernel.kptr_restrict", Expect::AtLeast(1), "Hides kernel pointers that help exploits bypass KASLR"),
+    ("kernel.dmesg_restrict", Expect::Equals(1), "Keeps the kernel log, and the addresses in it, from unprivileged users"),
+    ("kernel.yama.ptrace_scope", Expect::AtLeast(1), "Only lets processes attach to their own children"),
+    ("kernel.unprivileged_bpf_disabled", Expect::AtLeast(1), "Blocks unprivileged eBPF, a frequent kernel exploit vector"),
+    ("net.core.bpf_jit_harden", Expect::Equals(2), "Blinds constants in JIT-compiled BPF against JIT spraying"),
+    ("kernel.kexec_load_disabled", Expect::Equals(1), "Prevents replacing the running kernel through kexec"),
+    ("kernel.randomize_va_space", Expect::Equals(2), "Full address space layout randomization"),
+    ("kernel.perf_event_paranoid", Expect::AtLeast(2), "Restricts perf events that leak kernel information"),
+    ("dev.tty.ldisc_autoload", Expect::Equals(0), "Stops unprivileged users loading TTY line discipline modules"),
+    ("fs.protected_symlinks", Expect::Equals(1), "Blocks symlink attacks in world-writable sticky directories"),
+    ("fs.protected_hardlinks", Expect::Equals(1), "Blocks hardlinks to files the user can't write"),
+    ("fs.protected_fifos", Expect::AtLeast(1), "Blocks opening other users' FIFOs in sticky directories"),
+    ("fs.protected_regular", Expect::AtLeast(1), "Blocks opening other users' files in sticky directories"),
+    ("fs.suid_dumpable", Expect::Equals(0), "No core dumps of setuid programs, which may contain secrets"),
+    ("net.ipv4.conf.all.rp_filter", Expect::AtLeast(1), "Drops packets with spoofed source addresses"),
+    ("net.ipv4.conf.all.accept_redirects", Expect::Equals(0), "Ignores ICMP redirects that can reroute traffic"),
+    ("net.ipv6.conf.all.accept_redirects", Expect::Equals(0), "Ignores ICMPv6 redirects that can reroute traffic"),
+    ("net.ipv4.conf.all.send_redirects", Expect::Equals(0), "A host that isn't a router has no redirects to send"),
+    ("net.ipv4.conf.all.accept_source_route", Expect::Equals(0), "Ignores source-routed packets"),
+    ("net.ipv4.tcp_syncookies", Expect::Equals(1), "Keeps accepting connections during a SYN flood"),
+    ("net.ipv4.icmp_echo_ignore_broadcasts", Expect::Equals(1), "Doesn't take part in smurf amplification attacks"),
+    ("net.ipv4.conf.all.log_martians", Expect::Equals(1), "Logs packets with impossible source addresses"),
+];
+
+#[derive(serde::Serialize)]
+pub struct SysctlCheck {
+    key: String,
+    /// Absent when the kernel doesn't have the parameter
+    current: Option<String>,
+    recommended: String,
+    /// None when the parameter is missing, which usually means the feature isn't built in
+    passed: Option<bool>,
+    reason: String,
+}
+
+/// Mandatory access control: which module is active and how strictly it is applied.
+#[derive(serde::Serialize)]
+pub struct MacStatus {
+    /// apparmor, selinux or none
+    module: String,
+    /// enforcing, permissive or disabled
+    mode: String,
+    /// Counts need root; None when they couldn't be read
+    profiles_loaded: Option<usize>,
+    /// Profiles in complain mode (AppArmor) or permissive domains (SELinux)
+    profiles_complain: Option<usize>,
+}
+
+fn active_lsms() -> Vec<String> {
+    fs::read_to_string("/sys/kernel/security/lsm").unwrap_or_default()
+        .split(',').map(|m| m.trim().to_string()).filter(|m| !m.is_empty()).collect()
+}
+
+// "/usr/bin/man (enforce)", one line per loaded profile
+fn apparmor_status() -> MacStatus {
+    let enabled = fs::read_to_string("/sys/module/apparmor/parameters/enabled").is_ok_and(|e| e.trim() == "Y");
+    let profiles = fs::read_to_string("/sys/kernel/security/apparmor/profiles").ok();
+    let loaded = profiles.as_ref().map(|p| p.lines().count());
+    let complain = profiles.as_ref().map(|p| p.lines().filter(|l| l.ends_with("(complain)")).count());
+    let mode = match (enabled, loaded, complain) {
+        (false, _, _) => "disabled",
+        // Everything loaded only logs
+        (true, Some(l), Some(c)) if l > 0 && l == c => "permissive",
+        _ => "enforcing",
+    };
+    MacStatus { module: "apparmor".to_string(), mode: mode.to_string(), profiles_loaded: loaded, profiles_complain: complain }
+}
+
+fn selinux_status() -> MacStatus {
+    let mode = match fs::read_to_string("/sys/fs/selinux/enforce").map(|e| e.trim().to_string()) {
+        Ok(e) if e == "1" => "enforcing",
+        Ok(_) => "permissive",
+        Err(_) => "disabled",
+    };
+    let count = |cmd: &str, args: &[&str]| -> Option<usize> {
+        let output = Command::new(cmd).args(args).output().ok().filter(|o| o.status.success())?;
+        Some(String::from_utf8_lossy(&output.stdout).lines().filter(|l| !l.trim().is_empty()).count())
+    };
+    MacStatus {
+        module: "selinux".to_string(),
+        mode: mode.to_string(),
+        profiles_loaded: count("semodule", &["-l"]),
+        // Lists builtin and customized permissive types, without headings
+        profiles_complain: count("semanage", &["permissive", "-l", "-n"]),
+    }
+}
+
+pub fn mac_status() -> MacStatus {
+    let lsms = active_lsms();
+    if lsms.iter().any(|m| m == "selinux") || Path::new("/sys/fs/selinux/enforce").exists() {
+        selinux_status()
+    } else if lsms.iter().any(|m| m == "apparmor") || Path::new("/sys/module/apparmor").exists() {
+        apparmor_status()
+    } else if Path::new("/etc/selinux/config").exists() {
+        // Installed but turned off on the kernel command line
+        MacStatus { module: "selinux".to_string(), mode: "disabled".to_string(), profiles_loaded: None, profiles_complain: None }
+    } else {
+        MacStatus { module: "none".to_string(), mode: "disabled".to_string(), profiles_loaded: None, profiles_complain: None }
+    }
+}
+
+// --- Commands ---
+
+/// Sandboxing exposure of a service as scored by `systemd-analyze security`.
+#[tauri::command]
+pub async fn get_unit_security(unit: String) -> Result<UnitSecurity, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = Command::new("systemd-analyze")
+            .args(["security", "--no-pager", &unit])
+            .output()
+            .map_err(|_| "systemd-analyze is not installed".to_string())?;
+        let out = String::from_utf8_lossy(&output.stdout);
+
+        // "→ Overall exposure level for sshd.service: 9.6 UNSAFE 😨"
+        let summary = out.lines()
+            .find_map(|l| l.split_once("Overall exposure level for ").map(|(_, s)| s.to_string()))
+            .ok_or_else(|| String::from_utf8_lossy(&output.stderr).trim().to_string())?;
+        let mut score = summary.rsplit_once(": ").map(|(_, s)| s).unwrap_or_default().split_whitespace();
+
+        Ok(UnitSecurity {
+            exposure: score.next().and_then(|v| v.parse().ok()).unwrap_or(0.0),
+            rating: score.next().unwrap_or_default().to_string(),
+            findings: out.lines().filter_map(|l| parse_finding(l.trim_start())).collect(),
+            unit,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Compares a curated list of hardening sysctls with their recommended values.
+#[tauri::command]
+pub fn audit_sysctl() -> Vec<SysctlCheck> {
+    SYSCTL_CHECKS.iter().map(|(key, expect, reason)| {
+        let current = fs::read_to_string(format!("/proc/sys/{}", key.replace('.', "/"))).ok()
+            .map(|v| v.trim().to_string());
+        let value = current.as_ref().and_then(|v| v.parse::<i64>().ok());
+        let (recommended, passed) = match expect {
+            Expect::Equals(want) => (want.to_string(), value.map(|v| v == *want)),
+            Expect::AtLeast(min) => (format!(">= {}", min), value.map(|v| v >= *min)),
+        };
+        SysctlCheck { key: key.to_string(), current, recommended, passed, reason: reason.to_string() }
+    }).collect()
+}
+
+/// Taint flags with their reasons, modules that are unsigned or built out of tree, and
+/// whether signature enforcement and lockdown are active.
+#[tauri::command]
+pub fn get_kernel_integrity() -> KernelIntegrity {
+    let taint = kernel_taint();
+    let flags = TAINT_FLAGS.iter()
+        .filter(|(bit, _, _)| taint & (1 << bit) != 0)
+        .map(|(bit, letter, reason)| TaintFlag { bit: *bit, letter: *letter, reason: reason.to_string() })
+        .collect();
+
+    // /sys/module/<name>/taint holds the letters the module contributed, e.g. "OE"
+    let (mut unsigned_modules, mut out_of_tree_modules) = (Vec::new(), Vec::new());
+    for line in fs::read_to_string("/proc/modules").unwrap_or_default().lines() {
+        let Some(name) = line.split_whitespace().next() else { continue };
+        let letters = fs::read_to_string(format!("/sys/module/{}/taint", name)).unwrap_or_default();
+        if letters.contains('E') {
+            unsigned_modules.push(name.to_string());
+        }
+        if letters.contains('O') {
+            out_of_tree_modules.push(name.to_string());
+        }
+    }
+    unsigned_modules.sort();
+    out_of_tree_modules.sort();
+
+    // "none [integrity] confidentiality", with the active mode in brackets
+    let lockdown = fs::read_to_string("/sys/kernel/security/lockdown").ok()
+        .and_then(|l| l.split_once('[')?.1.split_once(']').map(|(m, _)| m.to_string()));
+
+    KernelIntegrity {
+        taint,
+        flags,
+        unsigned_modules,
+        out_of_tree_modules,
+        sig_enforce: fs::read_to_string("/sys/module/module/parameters/sig_enforce").is_ok_and(|v| v.trim() == "Y"),
+        lockdown,
+    }
+}
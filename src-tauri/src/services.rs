@@ -0,0 +1,377 @@
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+use tauri::State;
+use zbus::blocking::Connection;
+use zbus::dbus_proxy;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::health::Sampled;
+use crate::history::unix_now;
+use crate::AppState;
+
+// Services shown on the services panel by default
+const WATCHED_SERVICES: [&str; 6] = ["sshd", "NetworkManager", "ufw", "docker", "bluetooth", "cronie"];
+const WATCHED_USER_SERVICES: [&str; 5] = ["pipewire", "pipewire-pulse", "wireplumber", "syncthing", "gpg-agent"];
+
+// name, description, load, active, sub, following, object path, job id, job type, job path
+type UnitTuple = (String, String, String, String, String, String, OwnedObjectPath, u32, String, OwnedObjectPath);
+
+#[dbus_proxy(
+    interface = "org.freedesktop.systemd1.Manager",
+    default_service = "org.freedesktop.systemd1",
+    default_path = "/org/freedesktop/systemd1"
+)]
+trait SystemdManager {
+    fn list_units(&self) -> zbus::Result<Vec<UnitTuple>>;
+    fn list_units_by_names(&self, names: &[&str]) -> zbus::Result<Vec<UnitTuple>>;
+    fn list_units_filtered(&self, states: &[&str]) -> zbus::Result<Vec<UnitTuple>>;
+    /// (path, state) for every unit file systemd knows of, loaded or not
+    fn list_unit_files(&self) -> zbus::Result<Vec<(String, String)>>;
+    fn get_unit_file_state(&self, name: &str) -> zbus::Result<String>;
+    fn load_unit(&self, name: &str) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[dbus_proxy(interface = "org.freedesktop.systemd1.Unit", default_service = "org.freedesktop.systemd1")]
+trait SystemdUnit {
+    #[dbus_proxy(property)]
+    fn wants(&self) -> zbus::Result<Vec<String>>;
+    #[dbus_proxy(property)]
+    fn requires(&self) -> zbus::Result<Vec<String>>;
+    #[dbus_proxy(property)]
+    fn after(&self) -> zbus::Result<Vec<String>>;
+    #[dbus_proxy(property)]
+    fn before(&self) -> zbus::Result<Vec<String>>;
+}
+
+#[dbus_proxy(interface = "org.freedesktop.systemd1.Timer", default_service = "org.freedesktop.systemd1")]
+trait SystemdTimer {
+    #[dbus_proxy(property, name = "NextElapseUSecRealtime")]
+    fn next_elapse_realtime(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "NextElapseUSecMonotonic")]
+    fn next_elapse_monotonic(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "LastTriggerUSec")]
+    fn last_trigger(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn unit(&self) -> zbus::Result<String>;
+}
+
+/// Which systemd manager a command talks to: the system one, or the calling user's.
+#[derive(Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    #[default]
+    System,
+    User,
+}
+
+impl Scope {
+    fn connection(self) -> zbus::Result<Connection> {
+        match self {
+            Scope::System => Connection::system(),
+            // The user manager is reachable on the session bus
+            Scope::User => Connection::session(),
+        }
+    }
+
+    fn systemctl(self) -> Command {
+        let mut cmd = Command::new("systemctl");
+        if self == Scope::User {
+            cmd.arg("--user");
+        }
+        cmd
+    }
+
+    /// journalctl with `--user` added for the user scope.
+    pub fn journalctl(self) -> Command {
+        let mut cmd = Command::new("journalctl");
+        if self == Scope::User {
+            cmd.arg("--user");
+        }
+        cmd
+    }
+
+    fn watched(self) -> &'static [&'static str] {
+        match self {
+            Scope::System => &WATCHED_SERVICES,
+            Scope::User => &WATCHED_USER_SERVICES,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct ServiceStatus {
+    name: String,
+    status: String,
+    active: bool,
+    /// Boot-time state: enabled, disabled, masked, static, ...
+    unit_file_state: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct UnitInfo {
+    name: String,
+    description: String,
+    load_state: String,
+    active_state: String,
+    sub_state: String,
+}
+
+impl From<UnitTuple> for UnitInfo {
+    fn from(u: UnitTuple) -> Self {
+        UnitInfo { name: u.0, description: u.1, load_state: u.2, active_state: u.3, sub_state: u.4 }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct TimerInfo {
+    name: String,
+    /// The unit the timer activates, usually a .service
+    unit: String,
+    active_state: String,
+    /// Unix seconds; 0 when unknown or never
+    last_run: u64,
+    next_run: u64,
+}
+
+fn systemd(scope: Scope) -> zbus::Result<SystemdManagerProxyBlocking<'static>> {
+    let conn = scope.connection()?;
+    SystemdManagerProxyBlocking::new(&conn)
+}
+
+#[derive(serde::Serialize)]
+pub struct UnitDependencies {
+    unit: String,
+    wants: Vec<String>,
+    requires: Vec<String>,
+    /// Ordering only: these start first if they are being started anyway
+    after: Vec<String>,
+    before: Vec<String>,
+}
+
+/// Unit file state ("enabled", "disabled", ...) and whether the unit is running, for several
+/// units over one connection; units that aren't installed are left out.
+pub fn unit_states(units: &[&str], scope: Scope) -> HashMap<String, (String, bool)> {
+    let Ok(manager) = systemd(scope) else { return HashMap::new() };
+    let active: HashSet<String> = manager.list_units_by_names(units).unwrap_or_default().into_iter()
+        .filter(|u| u.3 == "active")
+        .map(|u| u.0)
+        .collect();
+    units.iter()
+        .filter_map(|u| Some((u.to_string(), (manager.get_unit_file_state(u).ok()?, active.contains(*u)))))
+        .collect()
+}
+
+fn timer_proxy(conn: &Connection, path: OwnedObjectPath) -> zbus::Result<SystemdTimerProxyBlocking<'static>> {
+    SystemdTimerProxyBlocking::builder(conn).path(path)?.build()
+}
+
+fn monotonic_usec() -> u64 {
+    let mut ts: libc::timespec = unsafe { std::mem::zeroed() };
+    unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+    ts.tv_sec as u64 * 1_000_000 + ts.tv_nsec as u64 / 1000
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_services(scope: Option<Scope>, state: State<AppState>) -> Sampled<Vec<ServiceStatus>> {
+    let scope = scope.unwrap_or_default();
+    let names: Vec<String> = scope.watched().iter().map(|s| format!("{}.service", s)).collect();
+    let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    let manager = systemd(scope).map_err(|e| e.to_string());
+    let units = manager.as_ref().ok().and_then(|m| m.list_units_by_names(&names).ok()).unwrap_or_default();
+    state.health.lock().unwrap().record("services", None, &manager);
+    let manager = manager.ok();
+
+    Sampled::now(scope.watched().iter().zip(&names).map(|(s, unit)| {
+        let status = units.iter()
+            .find(|u| u.0 == *unit)
+            .map(|u| u.3.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        let unit_file_state = manager.as_ref()
+            .and_then(|m| m.get_unit_file_state(unit).ok())
+            .unwrap_or_else(|| "unknown".to_string());
+        ServiceStatus { name: s.to_string(), active: status == "active", status, unit_file_state }
+    }).collect())
+}
+
+/// Lists every unit whose name contains `filter` (case-insensitive), including installed ones
+/// that aren't loaded; empty matches all.
+#[tauri::command]
+pub fn list_units(filter: String, scope: Option<Scope>) -> Result<Sampled<Vec<UnitInfo>>, String> {
+    let filter = filter.to_lowercase();
+    let manager = systemd(scope.unwrap_or_default()).map_err(|e| e.to_string())?;
+    let mut units: Vec<UnitInfo> = manager.list_units()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|u| u.0.to_lowercase().contains(&filter))
+        .map(UnitInfo::from)
+        .collect();
+    // ListUnits only returns what's in memory, so a disabled service that never ran is missing.
+    // Templates ("foo@.service") only run as instances, which are listed when loaded
+    let loaded: HashSet<String> = units.iter().map(|u| u.name.clone()).collect();
+    for (path, state) in manager.list_unit_files().unwrap_or_default() {
+        let name = path.rsplit('/').next().unwrap_or_default().to_string();
+        if name.contains("@.") || loaded.contains(&name) || !name.to_lowercase().contains(&filter) {
+            continue;
+        }
+        units.push(UnitInfo {
+            name,
+            description: String::new(),
+            load_state: if state == "masked" { "masked" } else { "not-loaded" }.to_string(),
+            active_state: "inactive".to_string(),
+            sub_state: "dead".to_string(),
+        });
+    }
+    units.sort_by(|a, b| a.name.cmp(&b.name));
+    // The same unit file can sit in both /etc and /usr/lib
+    units.dedup_by(|a, b| a.name == b.name);
+    Ok(Sampled::now(units))
+}
+
+#[tauri::command]
+pub fn get_failed_units(scope: Option<Scope>) -> Result<Vec<UnitInfo>, String> {
+    let mut units: Vec<UnitInfo> = systemd(scope.unwrap_or_default())
+        .and_then(|m| m.list_units_filtered(&["failed"]))
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(UnitInfo::from)
+        .collect();
+    units.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(units)
+}
+
+pub fn timers(scope: Option<Scope>) -> Result<Vec<TimerInfo>, String> {
+    let conn = scope.unwrap_or_default().connection().map_err(|e| e.to_string())?;
+    let manager = SystemdManagerProxyBlocking::new(&conn).map_err(|e| e.to_string())?;
+    let units = manager.list_units().map_err(|e| e.to_string())?;
+    let now = unix_now();
+    let now_mono = monotonic_usec();
+
+    let mut timers: Vec<TimerInfo> = units.into_iter()
+        .filter(|u| u.0.ends_with(".timer"))
+        .filter_map(|u| {
+            let timer = timer_proxy(&conn, u.6).ok()?;
+            let realtime = timer.next_elapse_realtime().unwrap_or(0);
+            let monotonic = timer.next_elapse_monotonic().unwrap_or(0);
+            // Boot-relative timers (OnBootSec=, OnUnitActiveSec=) only report a monotonic deadline
+            let next_run = if realtime > 0 {
+                realtime / 1_000_000
+            } else if monotonic > now_mono {
+                now + (monotonic - now_mono) / 1_000_000
+            } else {
+                0
+            };
+            Some(TimerInfo {
+                name: u.0,
+                unit: timer.unit().unwrap_or_default(),
+                active_state: u.3,
+                last_run: timer.last_trigger().unwrap_or(0) / 1_000_000,
+                next_run,
+            })
+        })
+        .collect();
+    timers.sort_by_key(|t| if t.next_run == 0 { u64::MAX } else { t.next_run });
+    Ok(timers)
+}
+
+#[tauri::command]
+pub async fn get_timers(scope: Option<Scope>) -> Result<Vec<TimerInfo>, String> {
+    tauri::async_runtime::spawn_blocking(move || timers(scope))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Direct Wants/Requires/After/Before relations of `unit`; the frontend walks them to build a tree.
+#[tauri::command]
+pub fn get_unit_dependencies(unit: String, scope: Option<Scope>) -> Result<UnitDependencies, String> {
+    let conn = scope.unwrap_or_default().connection().map_err(|e| e.to_string())?;
+    let manager = SystemdManagerProxyBlocking::new(&conn).map_err(|e| e.to_string())?;
+    // LoadUnit also works for units that are not currently loaded
+    let path = manager.load_unit(&unit).map_err(|e| e.to_string())?;
+    let proxy = SystemdUnitProxyBlocking::builder(&conn).path(path)
+        .and_then(|b| b.build())
+        .map_err(|e| e.to_string())?;
+
+    let sorted = |mut v: Vec<String>| { v.sort(); v };
+    Ok(UnitDependencies {
+        wants: sorted(proxy.wants().unwrap_or_default()),
+        requires: sorted(proxy.requires().unwrap_or_default()),
+        after: sorted(proxy.after().unwrap_or_default()),
+        before: sorted(proxy.before().unwrap_or_default()),
+        unit,
+    })
+}
+
+/// Starts the unit behind `timer` right away, without waiting for its schedule.
+#[tauri::command]
+pub async fn trigger_timer(timer: String, scope: Option<Scope>) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let unit = scope.unwrap_or_default().connection().ok()
+            .and_then(|conn| {
+                let manager = SystemdManagerProxyBlocking::new(&conn).ok()?;
+                let path = manager.list_units_by_names(&[timer.as_str()]).ok()?.into_iter().next()?.6;
+                timer_proxy(&conn, path).ok()?.unit().ok()
+            });
+        match unit {
+            Some(unit) => unit_action(&unit, "start", scope.unwrap_or_default()).is_ok(),
+            None => false,
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// Runtime actions stay on systemctl so polkit can prompt for authentication
+#[tauri::command]
+pub async fn control_service(name: String, action: String, scope: Option<Scope>) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || unit_action(&name, &action, scope.unwrap_or_default()).is_ok())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// systemctl's own message on failure, e.g. "Unit file foo.service does not exist."
+fn systemctl(scope: Scope, action: &str, unit: &str) -> Result<(), String> {
+    let output = scope.systemctl().arg(action).arg(unit).output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if message.is_empty() { format!("Failed to {} {}", action, unit) } else { message });
+    }
+    Ok(())
+}
+
+/// Runs a runtime action such as start, stop or restart on `name`.
+pub fn unit_action(name: &str, action: &str, scope: Scope) -> Result<(), String> {
+    systemctl(scope, action, name)
+}
+
+pub fn enable_unit(name: &str, enabled: bool, scope: Scope) -> Result<(), String> {
+    systemctl(scope, if enabled { "enable" } else { "disable" }, name)
+}
+
+pub fn mask_unit(name: &str, masked: bool, scope: Scope) -> Result<(), String> {
+    systemctl(scope, if masked { "mask" } else { "unmask" }, name)
+}
+
+#[tauri::command]
+pub async fn set_service_enabled(name: String, enabled: bool, scope: Option<Scope>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || enable_unit(&name, enabled, scope.unwrap_or_default()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Clears the failed state so the unit drops off the failed list and its restart counter resets.
+#[tauri::command]
+pub async fn reset_failed(unit: String, scope: Option<Scope>) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || unit_action(&unit, "reset-failed", scope.unwrap_or_default()).is_ok())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Masking links the unit to /dev/null so nothing, not even dependencies, can start it.
+#[tauri::command]
+pub async fn set_service_masked(name: String, masked: bool, scope: Option<Scope>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || mask_unit(&name, masked, scope.unwrap_or_default()))
+        .await
+        .map_err(|e| e.to_string())?
+}
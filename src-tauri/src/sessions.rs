@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+use sysinfo::{PidExt, ProcessExt, SystemExt};
+use tauri::State;
+use zbus::blocking::Connection;
+use zbus::dbus_proxy;
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::AppState;
+
+// id, uid, user, seat, object path
+type SessionTuple = (String, u32, String, String, OwnedObjectPath);
+
+#[dbus_proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+trait LoginManager {
+    fn list_seats(&self) -> zbus::Result<Vec<(String, OwnedObjectPath)>>;
+    fn list_sessions(&self) -> zbus::Result<Vec<SessionTuple>>;
+}
+
+#[dbus_proxy(interface = "org.freedesktop.login1.Session", default_service = "org.freedesktop.login1")]
+trait LoginSession {
+    /// x11, wayland, tty, mir or unspecified
+    #[dbus_proxy(property, name = "Type")]
+    fn kind(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn state(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn remote(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property)]
+    fn remote_host(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn display(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "TTY")]
+    fn tty(&self) -> zbus::Result<String>;
+}
+
+#[derive(serde::Serialize)]
+pub struct SessionInfo {
+    id: String,
+    uid: u32,
+    user: String,
+    kind: String,
+    state: String,
+    remote: bool,
+    remote_host: String,
+    /// X display or TTY, whichever the session has
+    terminal: String,
+    cpu: f32,
+    mem: u64,
+    processes: usize,
+}
+
+#[derive(serde::Serialize)]
+pub struct SeatInfo {
+    /// Empty for sessions without a seat, e.g. SSH or VNC logins
+    seat: String,
+    sessions: Vec<SessionInfo>,
+}
+
+// logind places each session's processes in "session-<id>.scope"
+fn session_of(pid: u32) -> Option<String> {
+    let cgroup = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    cgroup.split('/')
+        .find_map(|part| part.trim().strip_prefix("session-")?.strip_suffix(".scope").map(String::from))
+}
+
+fn session_info(conn: &Connection, s: SessionTuple, usage: &HashMap<String, (f32, u64, usize)>) -> SessionInfo {
+    let proxy = LoginSessionProxyBlocking::builder(conn).path(s.4).and_then(|b| b.build()).ok();
+    let prop = |f: fn(&LoginSessionProxyBlocking<'static>) -> zbus::Result<String>| {
+        proxy.as_ref().and_then(|p| f(p).ok()).unwrap_or_default()
+    };
+    let display = prop(|p| p.display());
+    let (cpu, mem, processes) = usage.get(&s.0).copied().unwrap_or_default();
+    SessionInfo {
+        kind: prop(|p| p.kind()),
+        state: prop(|p| p.state()),
+        remote: proxy.as_ref().and_then(|p| p.remote().ok()).unwrap_or(false),
+        remote_host: prop(|p| p.remote_host()),
+        terminal: if display.is_empty() { prop(|p| p.tty()) } else { display },
+        id: s.0,
+        uid: s.1,
+        user: s.2,
+        cpu,
+        mem,
+        processes,
+    }
+}
+
+// --- Commands ---
+
+/// Seats with their sessions, each carrying the CPU and memory of its own processes only.
+#[tauri::command]
+pub fn get_sessions(state: State<AppState>) -> Result<Vec<SeatInfo>, String> {
+    let conn = Connection::system().map_err(|e| e.to_string())?;
+    let manager = LoginManagerProxyBlocking::new(&conn).map_err(|e| e.to_string())?;
+    let seats = manager.list_seats().map_err(|e| e.to_string())?;
+    let sessions = manager.list_sessions().map_err(|e| e.to_string())?;
+
+    let mut usage: HashMap<String, (f32, u64, usize)> = HashMap::new();
+    {
+        let sys = state.sys.lock().unwrap();
+        for (pid, process) in sys.processes() {
+            if let Some(id) = session_of(pid.as_u32()) {
+                let u = usage.entry(id).or_default();
+                u.0 += process.cpu_usage();
+                u.1 += process.memory();
+                u.2 += 1;
+            }
+        }
+    }
+
+    let mut out: Vec<SeatInfo> = seats.into_iter().map(|(seat, _)| SeatInfo { seat, sessions: Vec::new() }).collect();
+    out.push(SeatInfo { seat: String::new(), sessions: Vec::new() });
+    for s in sessions {
+        let seat = s.3.clone();
+        let idx = out.iter().position(|g| g.seat == seat).unwrap_or(out.len() - 1);
+        out[idx].sessions.push(session_info(&conn, s, &usage));
+    }
+    out.retain(|g| !g.seat.is_empty() || !g.sessions.is_empty());
+    Ok(out)
+}
+
+// Via loginctl so polkit can prompt, same as systemctl for services
+#[tauri::command]
+pub async fn control_session(id: String, action: String) -> Result<(), String> {
+    if !matches!(action.as_str(), "lock" | "unlock" | "terminate" | "activate") {
+        return Err(format!("Unknown session action: {}", action));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let output = Command::new("loginctl").arg(format!("{}-session", action)).arg(&id)
+            .output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(if message.is_empty() { format!("Failed to {} session {}", action, id) } else { message });
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,136 @@
+use sysinfo::{ComponentExt, SystemExt};
+use tauri::State;
+
+use crate::app_firewall::AppRule;
+use crate::{config, gpu, tray, AppState};
+
+const SETTINGS_FILE: &str = "settings.toml";
+// Anything faster makes the sampler itself the top process
+const MIN_INTERVAL_MS: u64 = 250;
+
+/// How readings from several GPUs or sensors become the one headline number.
+#[derive(Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Aggregation {
+    #[default]
+    Max,
+    Avg,
+    /// The device or sensor named in the matching setting
+    Device,
+}
+
+/// Folds named readings by `mode`; a selected device that has disappeared falls back to max.
+pub fn aggregate(mode: &Aggregation, device: Option<&str>, readings: &[(String, f32)]) -> Option<f32> {
+    let max = || readings.iter().map(|r| r.1).reduce(f32::max);
+    match mode {
+        Aggregation::Max => max(),
+        Aggregation::Avg => (!readings.is_empty()).then(|| readings.iter().map(|r| r.1).sum::<f32>() / readings.len() as f32),
+        Aggregation::Device => readings.iter().find(|r| Some(r.0.as_str()) == device).map(|r| r.1).or_else(max),
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub stats_interval_ms: u64,
+    pub process_interval_ms: u64,
+    /// Seconds between history samples
+    pub history_resolution_secs: u64,
+    /// Metrics shown in the tray tooltip and label, in order; see `tray::TRAY_METRICS`
+    pub tray_metrics: Vec<String>,
+    /// Directories and files hashed for configuration drift; empty disables scanning
+    pub drift_paths: Vec<String>,
+    pub drift_interval_hours: u64,
+    /// Process names left out of the process list; "*" matches any run of characters
+    pub hidden_processes: Vec<String>,
+    /// List kernel threads on their own instead of mixed in with user processes
+    pub separate_kernel_threads: bool,
+    pub gpu_aggregation: Aggregation,
+    /// DRM card name used with `Aggregation::Device`, e.g. "card1"
+    pub gpu_device: Option<String>,
+    /// Applies to CPU sensors unless `Aggregation::Device` picks any sensor by label
+    pub temp_aggregation: Aggregation,
+    pub temp_sensor: Option<String>,
+    /// A large file fetched by the speed test; plain http:// so TLS doesn't skew the result
+    pub speedtest_download_url: String,
+    /// Accepts a POSTed body and discards it
+    pub speedtest_upload_url: String,
+    /// Per-app outbound allow/deny decisions, enforced by `app_firewall`
+    pub app_firewall_rules: Vec<AppRule>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            stats_interval_ms: 1000,
+            process_interval_ms: 1000,
+            history_resolution_secs: 1,
+            tray_metrics: vec!["cpu".to_string(), "mem".to_string()],
+            drift_paths: vec!["/etc".to_string()],
+            drift_interval_hours: 24,
+            hidden_processes: Vec::new(),
+            separate_kernel_threads: false,
+            gpu_aggregation: Aggregation::Max,
+            gpu_device: None,
+            temp_aggregation: Aggregation::Max,
+            temp_sensor: None,
+            speedtest_download_url: "http://speedtest.tele2.net/100MB.zip".to_string(),
+            speedtest_upload_url: "http://speedtest.tele2.net/upload.php".to_string(),
+            app_firewall_rules: Vec::new(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        config::load(SETTINGS_FILE)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        config::save(SETTINGS_FILE, self)
+    }
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_settings(state: State<AppState>) -> Settings {
+    state.settings.lock().unwrap().clone()
+}
+
+#[derive(serde::Serialize)]
+pub struct AggregationSources {
+    gpus: Vec<String>,
+    sensors: Vec<String>,
+}
+
+/// GPU and temperature sensor names that `gpu_device` and `temp_sensor` can pick from.
+#[tauri::command]
+pub fn get_aggregation_sources(state: State<AppState>) -> AggregationSources {
+    let sys = state.sys.lock().unwrap();
+    AggregationSources {
+        gpus: gpu::card_names(),
+        sensors: sys.components().iter().map(|c| c.label().to_string()).collect(),
+    }
+}
+
+#[tauri::command]
+pub fn set_settings(mut settings: Settings, state: State<AppState>) -> Result<(), String> {
+    settings.stats_interval_ms = settings.stats_interval_ms.max(MIN_INTERVAL_MS);
+    settings.process_interval_ms = settings.process_interval_ms.max(MIN_INTERVAL_MS);
+    settings.history_resolution_secs = settings.history_resolution_secs.max(1);
+    settings.drift_interval_hours = settings.drift_interval_hours.max(1);
+    if let Some(bad) = settings.tray_metrics.iter().find(|m| !tray::TRAY_METRICS.contains(&m.as_str())) {
+        return Err(format!("Unknown tray metric: {}", bad));
+    }
+    if let Some(bad) = [&settings.speedtest_download_url, &settings.speedtest_upload_url].into_iter().find(|u| !u.starts_with("http://")) {
+        return Err(format!("Speed test endpoints must be http:// URLs: {}", bad));
+    }
+
+    let mut current = state.settings.lock().unwrap();
+    // Edited through set_app_firewall_rules; the settings form may hold an older copy
+    settings.app_firewall_rules = current.app_firewall_rules.clone();
+    settings.save()?;
+    *current = settings;
+    Ok(())
+}
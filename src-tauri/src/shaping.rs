@@ -0,0 +1,228 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::config;
+
+const SHAPING_FILE: &str = "shaping.toml";
+const BOOT_SCRIPT: &str = "/etc/glassview/shaping.sh";
+const BOOT_UNIT: &str = "/etc/systemd/system/glassview-shaping.service";
+// Firewall mark that steers a cgroup's packets into the limited htb class
+const CGROUP_MARK: &str = "0x6776";
+
+/// A named bandwidth cap, e.g. "Video call" limiting downloads on wlan0 to 5000 kbit/s.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShapingProfile {
+    name: String,
+    interface: String,
+    download_kbit: Option<u32>,
+    upload_kbit: Option<u32>,
+    /// Only limit uploads from this cgroup (path below /sys/fs/cgroup) instead of the whole interface
+    cgroup: Option<String>,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct ShapingFile {
+    /// Last profile applied; `get_shaping` only reports it while tc still shows its qdiscs
+    active: Option<String>,
+    /// Whether the active profile is reapplied at boot
+    persistent: bool,
+    profiles: Vec<ShapingProfile>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ShapingState {
+    profiles: Vec<ShapingProfile>,
+    active: Option<String>,
+    persistent: bool,
+    interfaces: Vec<String>,
+}
+
+fn interfaces() -> Vec<String> {
+    let mut list: Vec<String> = fs::read_dir("/sys/class/net").into_iter().flatten().flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n != "lo")
+        .collect();
+    list.sort();
+    list
+}
+
+// Everything below ends up in a root shell, so only accept names that are already on the system
+fn validate(profile: &ShapingProfile) -> Result<(), String> {
+    let safe = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || "-_.@:/".contains(c));
+    if !safe(&profile.interface) || !interfaces().contains(&profile.interface) {
+        return Err(format!("Unknown interface: {}", profile.interface));
+    }
+    if let Some(cg) = &profile.cgroup {
+        // Relative to the cgroup root: joining an absolute path would replace the root, and a
+        // symlink could still lead out of it, hence the check on the canonical path
+        let root = Path::new("/sys/fs/cgroup");
+        let inside = !cg.starts_with('/') && !cg.split('/').any(|c| c == "..")
+            && root.join(cg).canonicalize().is_ok_and(|p| p.starts_with(root) && p.is_dir());
+        if !safe(cg) || !inside {
+            return Err(format!("Unknown cgroup: {}", cg));
+        }
+    }
+    if profile.download_kbit.is_none() && profile.upload_kbit.is_none() {
+        return Err("Set a download or upload limit".to_string());
+    }
+    Ok(())
+}
+
+fn reset_commands(interface: &str) -> String {
+    // Nothing to delete is fine; the scripts otherwise stop at the first failing command
+    format!("tc qdisc del dev {0} root 2>/dev/null || true\ntc qdisc del dev {0} ingress 2>/dev/null || true\nnft delete table inet glassview_shaping 2>/dev/null || true\n", interface)
+}
+
+// Uploads are shaped with cake (or htb when only one cgroup is limited); downloads can only be
+// policed at ingress, which drops the excess and lets TCP back off.
+fn apply_commands(p: &ShapingProfile) -> String {
+    let dev = &p.interface;
+    let mut script = reset_commands(dev);
+    if let Some(up) = p.upload_kbit {
+        match &p.cgroup {
+            None => script += &format!("tc qdisc add dev {} root cake bandwidth {}kbit\n", dev, up),
+            Some(cg) => {
+                script += &format!("tc qdisc add dev {} root handle 1: htb default 1\n", dev);
+                script += &format!("tc class add dev {} parent 1: classid 1:1 htb rate 10gbit\n", dev);
+                script += &format!("tc class add dev {} parent 1: classid 1:10 htb rate {1}kbit ceil {1}kbit\n", dev, up);
+                script += &format!("tc filter add dev {} parent 1: protocol all handle {} fw classid 1:10\n", dev, CGROUP_MARK);
+                let level = cg.trim_matches('/').split('/').count();
+                script += &format!(
+                    "nft -f - <<'EOF'\ntable inet glassview_shaping {{\n  chain output {{\n    type route hook output priority mangle;\n    socket cgroupv2 level {} \"{}\" meta mark set {}\n  }}\n}}\nEOF\n",
+                    level, cg.trim_matches('/'), CGROUP_MARK,
+                );
+            }
+        }
+    }
+    if let Some(down) = p.download_kbit {
+        script += &format!("tc qdisc add dev {} handle ffff: ingress\n", dev);
+        script += &format!("tc filter add dev {} parent ffff: protocol all matchall action police rate {}kbit burst 64k drop\n", dev, down);
+    }
+    script
+}
+
+// Whether the interface still carries the qdiscs `apply_commands` set up for the profile; they
+// are gone after a reboot without the boot unit, or when something else reset the interface.
+// Listing qdiscs needs no root
+fn in_effect(p: &ShapingProfile) -> bool {
+    let Ok(output) = Command::new("tc").args(["qdisc", "show", "dev", &p.interface]).output() else { return false };
+    let qdiscs = String::from_utf8_lossy(&output.stdout);
+    let has = |kind: &str, placement: &str| qdiscs.lines().any(|l| l.starts_with(kind) && l.contains(placement));
+    let upload = match (p.upload_kbit, &p.cgroup) {
+        (None, _) => true,
+        (Some(_), None) => has("qdisc cake ", " root "),
+        (Some(_), Some(_)) => has("qdisc htb 1: ", " root "),
+    };
+    let download = p.download_kbit.is_none() || has("qdisc ingress ffff: ", "");
+    output.status.success() && upload && download
+}
+
+fn boot_commands(script: Option<&str>) -> String {
+    match script {
+        Some(script) => format!(
+            "mkdir -p /etc/glassview\ncat > {} <<'GLASSVIEW'\n#!/bin/sh -e\n{}GLASSVIEW\nchmod 755 {0}\ncat > {} <<'GLASSVIEW'\n[Unit]\nDescription=Glassview traffic shaping\nAfter=network-online.target\nWants=network-online.target\n\n[Service]\nType=oneshot\nExecStart={0}\n\n[Install]\nWantedBy=multi-user.target\nGLASSVIEW\nsystemctl daemon-reload\nsystemctl enable glassview-shaping.service\n",
+            BOOT_SCRIPT, script, BOOT_UNIT,
+        ),
+        None => format!("if [ -e {1} ]; then systemctl disable glassview-shaping.service; rm -f {0} {1}; systemctl daemon-reload; fi\n", BOOT_SCRIPT, BOOT_UNIT),
+    }
+}
+
+// One pkexec prompt for the whole change
+fn run_root(script: &str) -> Result<(), String> {
+    let mut child = Command::new("pkexec").args(["sh", "-e", "-s"])
+        .stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::piped())
+        .spawn().map_err(|e| e.to_string())?;
+    child.stdin.take().ok_or("No stdin")?.write_all(script.as_bytes()).map_err(|e| e.to_string())?;
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_shaping() -> ShapingState {
+    let file: ShapingFile = config::load(SHAPING_FILE);
+    let active = file.active.filter(|a| file.profiles.iter().any(|p| &p.name == a && in_effect(p)));
+    ShapingState { profiles: file.profiles, active, persistent: file.persistent, interfaces: interfaces() }
+}
+
+/// Adds a profile, or replaces the one with the same name. Editing the active profile applies
+/// the new limits right away, and rewrites the boot script when it is persistent.
+#[tauri::command]
+pub async fn save_shaping_profile(profile: ShapingProfile) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err("Profile needs a name".to_string());
+    }
+    let profile = ShapingProfile { cgroup: profile.cgroup.filter(|c| !c.is_empty()), ..profile };
+    validate(&profile)?;
+    let mut file: ShapingFile = config::load(SHAPING_FILE);
+    let previous = file.profiles.iter().find(|p| p.name == profile.name).cloned();
+    if let Some(prev) = previous.filter(|_| file.active.as_deref() == Some(profile.name.as_str())) {
+        // The interface may have changed too
+        let commands = apply_commands(&profile);
+        let script = reset_commands(&prev.interface) + &commands + &boot_commands(file.persistent.then_some(commands.as_str()));
+        tauri::async_runtime::spawn_blocking(move || run_root(&script))
+            .await
+            .map_err(|e| e.to_string())??;
+    }
+    file.profiles.retain(|p| p.name != profile.name);
+    file.profiles.push(profile);
+    config::save(SHAPING_FILE, &file)
+}
+
+#[tauri::command]
+pub fn delete_shaping_profile(name: String) -> Result<(), String> {
+    let mut file: ShapingFile = config::load(SHAPING_FILE);
+    if file.active.as_deref() == Some(name.as_str()) {
+        return Err("Revert the profile before deleting it".to_string());
+    }
+    file.profiles.retain(|p| p.name != name);
+    config::save(SHAPING_FILE, &file)
+}
+
+/// Applies a profile, replacing whatever shaping was active, and optionally installs a boot unit
+/// that reapplies it.
+#[tauri::command]
+pub async fn apply_shaping_profile(name: String, persistent: bool) -> Result<(), String> {
+    let mut file: ShapingFile = config::load(SHAPING_FILE);
+    let profile = file.profiles.iter().find(|p| p.name == name).cloned()
+        .ok_or_else(|| format!("Unknown profile: {}", name))?;
+    validate(&profile)?;
+
+    let mut script = String::new();
+    // The previous profile may have been on another interface
+    if let Some(prev) = file.active.as_ref().and_then(|a| file.profiles.iter().find(|p| &p.name == a)) {
+        script += &reset_commands(&prev.interface);
+    }
+    let commands = apply_commands(&profile);
+    script += &commands;
+    script += &boot_commands(persistent.then_some(commands.as_str()));
+
+    tauri::async_runtime::spawn_blocking(move || run_root(&script))
+        .await
+        .map_err(|e| e.to_string())??;
+    file.active = Some(name);
+    file.persistent = persistent;
+    config::save(SHAPING_FILE, &file)
+}
+
+/// Removes the active profile's limits and its boot unit.
+#[tauri::command]
+pub async fn clear_shaping() -> Result<(), String> {
+    let mut file: ShapingFile = config::load(SHAPING_FILE);
+    let Some(profile) = file.active.as_ref().and_then(|a| file.profiles.iter().find(|p| &p.name == a)) else {
+        return Ok(());
+    };
+    let script = reset_commands(&profile.interface) + &boot_commands(None);
+    tauri::async_runtime::spawn_blocking(move || run_root(&script))
+        .await
+        .map_err(|e| e.to_string())??;
+    file.active = None;
+    file.persistent = false;
+    config::save(SHAPING_FILE, &file)
+}
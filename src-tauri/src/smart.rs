@@ -0,0 +1,140 @@
+use serde_json::Value;
+use std::path::Path;
+use std::process::{Command, Output};
+
+// Percent of rated endurance past which an SSD is flagged
+const WEAR_ALERT_PERCENT: u64 = 90;
+
+#[derive(serde::Serialize)]
+pub struct SmartHealth {
+    device: String,
+    model: Option<String>,
+    /// Overall self-assessment; None when the drive doesn't report one
+    passed: Option<bool>,
+    reallocated_sectors: Option<u64>,
+    /// Unreadable sectors waiting to be reallocated
+    pending_sectors: Option<u64>,
+    /// Share of rated endurance used, for SSDs and NVMe
+    wear_percent: Option<u64>,
+    power_on_hours: Option<u64>,
+    temperature: Option<i64>,
+    /// Why the drive is considered failing; empty when healthy
+    warnings: Vec<String>,
+}
+
+fn installed(cmd: &str) -> Option<String> {
+    ["/usr/bin", "/usr/sbin", "/usr/local/bin", "/usr/local/sbin"].iter()
+        .map(|d| format!("{}/{}", d, cmd))
+        .find(|p| Path::new(p).exists())
+}
+
+// Exit status is a bitmask: bits 0 and 1 mean the command itself failed; the rest report
+// disk problems and still come with a full report
+fn usable(output: &Output) -> bool {
+    output.status.code().is_some_and(|c| c & 0b11 == 0)
+}
+
+// Without root, opening the device fails, so retry through pkexec
+fn smartctl(device: &str) -> Result<Value, String> {
+    let path = installed("smartctl").ok_or("smartmontools is not installed")?;
+    let args = ["--json", "--all", device];
+    let mut output = Command::new(&path).args(args).output().map_err(|e| e.to_string())?;
+    if !usable(&output) && unsafe { libc::geteuid() } != 0 {
+        output = Command::new("pkexec").arg(&path).args(args).output().map_err(|e| e.to_string())?;
+        if matches!(output.status.code(), Some(126) | Some(127)) {
+            return Err("Authentication cancelled".to_string());
+        }
+    }
+    let json: Value = serde_json::from_slice(&output.stdout).map_err(|_| format!("smartctl failed for {}", device))?;
+    if !usable(&output) {
+        let message = json.pointer("/smartctl/messages/0/string").and_then(Value::as_str).unwrap_or("smartctl failed");
+        return Err(message.to_string());
+    }
+    Ok(json)
+}
+
+// Raw value of an ATA attribute by id
+fn ata_attribute(json: &Value, id: u64) -> Option<&Value> {
+    json.pointer("/ata_smart_attributes/table")?.as_array()?.iter()
+        .find(|a| a.get("id").and_then(Value::as_u64) == Some(id))
+}
+
+fn ata_raw(json: &Value, id: u64) -> Option<u64> {
+    ata_attribute(json, id)?.pointer("/raw/value")?.as_u64()
+}
+
+// Vendors count remaining life down from 100 in the normalized value of one of these
+fn ata_wear(json: &Value) -> Option<u64> {
+    // 177 Wear_Leveling_Count (Samsung), 231 SSD_Life_Left, 233 Media_Wearout_Indicator (Intel)
+    [177, 231, 233].iter()
+        .find_map(|id| ata_attribute(json, *id)?.get("value")?.as_u64())
+        .map(|left| 100u64.saturating_sub(left.min(100)))
+}
+
+fn health(device: &str, json: &Value) -> SmartHealth {
+    let nvme = json.get("nvme_smart_health_information_log");
+    let nvme_u64 = |key: &str| nvme.and_then(|n| n.get(key)).and_then(Value::as_u64);
+
+    let passed = json.pointer("/smart_status/passed").and_then(Value::as_bool);
+    let reallocated_sectors = ata_raw(json, 5);
+    let pending_sectors = ata_raw(json, 197);
+    let wear_percent = nvme_u64("percentage_used").or_else(|| ata_wear(json));
+
+    let mut warnings = Vec::new();
+    if passed == Some(false) {
+        warnings.push("Drive reports its own SMART check failed".to_string());
+    }
+    if let Some(n) = reallocated_sectors.filter(|n| *n > 0) {
+        warnings.push(format!("{} reallocated sectors", n));
+    }
+    if let Some(n) = pending_sectors.filter(|n| *n > 0) {
+        warnings.push(format!("{} sectors pending reallocation", n));
+    }
+    if let Some(n) = nvme_u64("media_errors").filter(|n| *n > 0) {
+        warnings.push(format!("{} media errors", n));
+    }
+    if nvme_u64("critical_warning").is_some_and(|w| w != 0) {
+        warnings.push("NVMe critical warning set".to_string());
+    }
+    if let Some(w) = wear_percent.filter(|w| *w >= WEAR_ALERT_PERCENT) {
+        warnings.push(format!("{}% of rated endurance used", w));
+    }
+
+    SmartHealth {
+        device: device.to_string(),
+        model: json.get("model_name").and_then(Value::as_str).map(String::from),
+        passed,
+        reallocated_sectors,
+        pending_sectors,
+        wear_percent,
+        power_on_hours: json.pointer("/power_on_time/hours").and_then(Value::as_u64),
+        temperature: json.pointer("/temperature/current").and_then(Value::as_i64),
+        warnings,
+    }
+}
+
+/// Current temperature of `device` without a password prompt, so only when running as root.
+/// Drives in standby are left asleep and report nothing.
+pub fn temperature(device: &str) -> Option<f32> {
+    if unsafe { libc::geteuid() } != 0 {
+        return None;
+    }
+    let output = Command::new(installed("smartctl")?).args(["--json", "-n", "standby", "-A", device]).output().ok()?;
+    let json: Value = serde_json::from_slice(&output.stdout).ok()?;
+    json.pointer("/temperature/current").and_then(Value::as_f64).map(|t| t as f32)
+}
+
+// --- Commands ---
+
+/// SMART health of one disk, e.g. "/dev/sda". Asks for authentication unless run as root.
+#[tauri::command]
+pub async fn get_smart_health(device: String) -> Result<SmartHealth, String> {
+    // Only whole disks the kernel knows about, so nothing else reaches a root command line
+    let name = device.strip_prefix("/dev/").unwrap_or_default();
+    if name.is_empty() || name.contains('/') || !Path::new(&format!("/sys/block/{}", name)).exists() {
+        return Err(format!("{} is not a disk", device));
+    }
+    tauri::async_runtime::spawn_blocking(move || smartctl(&device).map(|json| health(&device, &json)))
+        .await
+        .map_err(|e| e.to_string())?
+}
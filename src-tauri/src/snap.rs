@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::fs;
+use std::process::Command;
+
+#[derive(serde::Serialize)]
+pub struct Snap {
+    name: String,
+    version: String,
+    revision: String,
+    /// Channel followed, e.g. "latest/stable"
+    tracking: String,
+    publisher: String,
+    /// Size of the mounted squashfs image
+    size: Option<u64>,
+    /// Version the store offers, when a refresh is pending
+    refresh: Option<String>,
+}
+
+fn snap(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("snap").args(args).output().map_err(|_| "snapd is not installed".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// Whitespace-aligned table with a header row: "Name  Version  Rev  Tracking  Publisher  Notes"
+fn rows(text: &str) -> Vec<Vec<String>> {
+    text.lines().skip(1).map(|l| l.split_whitespace().map(String::from).collect()).collect()
+}
+
+// --- Commands ---
+
+/// Installed snaps with the refreshes the store has pending for them.
+#[tauri::command]
+pub async fn get_snaps() -> Result<Vec<Snap>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let list = snap(&["list"])?;
+        // Prints "All snaps up to date." to stderr and fails when there's nothing to refresh
+        let refreshes: HashMap<String, String> = rows(&snap(&["refresh", "--list"]).unwrap_or_default()).into_iter()
+            .filter_map(|r| Some((r.first()?.clone(), r.get(1)?.clone())))
+            .collect();
+        Ok(rows(&list).into_iter().filter_map(|r| {
+            let [name, version, revision, tracking, publisher, ..] = r.as_slice() else { return None };
+            let image = format!("/var/lib/snapd/snaps/{}_{}.snap", name, revision);
+            Some(Snap {
+                size: fs::metadata(image).ok().map(|m| m.len()),
+                refresh: refreshes.get(name).cloned(),
+                name: name.clone(),
+                version: version.clone(),
+                revision: revision.clone(),
+                tracking: tracking.clone(),
+                // Verified publishers carry a check mark
+                publisher: publisher.trim_end_matches(['✓', '✪', '*']).to_string(),
+            })
+        }).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Refreshes one snap, or all of them when `name` is None. snapd asks polkit for
+/// authentication; pkexec is the fallback where it can't.
+#[tauri::command]
+pub async fn refresh_snap(name: Option<String>) -> Result<(), String> {
+    if let Some(n) = &name {
+        if !n.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+            return Err(format!("Invalid snap name: {}", n));
+        }
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut args = vec!["refresh"];
+        if let Some(n) = &name {
+            args.push(n);
+        }
+        match snap(&args) {
+            Err(e) if e.contains("access denied") => {
+                let output = Command::new("pkexec").arg("snap").args(&args).output().map_err(|e| e.to_string())?;
+                if output.status.success() { Ok(()) } else { Err(String::from_utf8_lossy(&output.stderr).trim().to_string()) }
+            }
+            other => other.map(|_| ()),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::bandwidth;
+
+// st column of /proc/net/tcp*: TCP_LISTEN; unconnected UDP sockets report TCP_CLOSE
+const TCP_LISTEN: &str = "0A";
+const UDP_UNCONNECTED: &str = "07";
+
+#[derive(serde::Serialize)]
+pub struct Connection {
+    /// tcp or tcp6
+    protocol: String,
+    local_address: String,
+    local_port: u16,
+    remote_address: String,
+    remote_port: u16,
+    /// e.g. ESTABLISHED or TIME_WAIT
+    state: String,
+    uid: u32,
+    pid: Option<u32>,
+    process: Option<String>,
+    /// From tcp_info; absent when ss is missing or the socket closed meanwhile
+    bytes_received: Option<u64>,
+    bytes_sent: Option<u64>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ListeningPort {
+    /// tcp, tcp6, udp or udp6
+    protocol: String,
+    address: String,
+    port: u16,
+    uid: u32,
+    /// Unknown for other users' sockets unless running as root
+    pid: Option<u32>,
+    process: Option<String>,
+    /// Bound to something other than loopback, so reachable from the network
+    exposed: bool,
+}
+
+/// One socket line of /proc/net/{tcp,udp}{,6}.
+pub struct SocketEntry {
+    pub local: (IpAddr, u16),
+    pub remote: (IpAddr, u16),
+    pub state: String,
+    pub uid: u32,
+    pub inode: u64,
+}
+
+// The kernel's TCP state numbers, as in include/net/tcp_states.h
+fn tcp_state(hex: &str) -> &'static str {
+    match hex {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+// Addresses are hex in host byte order, one 32-bit word at a time: "0100007F:0035" is 127.0.0.1:53
+fn parse_addr(text: &str) -> Option<(IpAddr, u16)> {
+    let (addr, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let words: Vec<u32> = (0..addr.len() / 8)
+        .map(|i| u32::from_str_radix(&addr[i * 8..i * 8 + 8], 16))
+        .collect::<Result<_, _>>().ok()?;
+    let ip = match words.as_slice() {
+        [w] => IpAddr::V4(Ipv4Addr::from(w.to_le_bytes())),
+        [a, b, c, d] => {
+            let mut bytes = [0u8; 16];
+            for (i, w) in [a, b, c, d].iter().enumerate() {
+                bytes[i * 4..i * 4 + 4].copy_from_slice(&w.to_le_bytes());
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some((ip, port))
+}
+
+/// Sockets of one protocol table, e.g. "tcp6".
+pub fn read_sockets(protocol: &str) -> Vec<SocketEntry> {
+    fs::read_to_string(format!("/proc/net/{}", protocol)).unwrap_or_default().lines().skip(1)
+        .filter_map(|line| {
+            let f: Vec<&str> = line.split_whitespace().collect();
+            Some(SocketEntry {
+                local: parse_addr(f.get(1)?)?,
+                remote: parse_addr(f.get(2)?)?,
+                state: f.get(3)?.to_string(),
+                uid: f.get(7)?.parse().ok()?,
+                inode: f.get(9)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Socket inode to owning pid, from the /proc/<pid>/fd links we are allowed to read.
+pub fn socket_owners() -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    for proc_entry in fs::read_dir("/proc").into_iter().flatten().flatten() {
+        let Ok(pid) = proc_entry.file_name().to_string_lossy().parse::<u32>() else { continue };
+        for fd in fs::read_dir(proc_entry.path().join("fd")).into_iter().flatten().flatten() {
+            let Ok(link) = fs::read_link(fd.path()) else { continue };
+            let link = link.to_string_lossy();
+            if let Some(inode) = link.strip_prefix("socket:[").and_then(|l| l.strip_suffix(']')) {
+                if let Ok(inode) = inode.parse() {
+                    owners.insert(inode, pid);
+                }
+            }
+        }
+    }
+    owners
+}
+
+// Dual-stack sockets show IPv4 peers as ::ffff:a.b.c.d
+pub fn is_loopback(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(v6.is_loopback(), |v4| v4.is_loopback()),
+        IpAddr::V4(v4) => v4.is_loopback(),
+    }
+}
+
+pub fn process_name(pid: u32) -> Option<String> {
+    fs::read_to_string(format!("/proc/{}/comm", pid)).ok().map(|c| c.trim().to_string())
+}
+
+// --- Commands ---
+
+/// Listening TCP sockets and bound UDP sockets with the process that owns each.
+#[tauri::command]
+pub fn get_listening_ports() -> Vec<ListeningPort> {
+    let owners = socket_owners();
+    let mut ports: Vec<ListeningPort> = ["tcp", "tcp6", "udp", "udp6"].iter()
+        .flat_map(|proto| read_sockets(proto).into_iter().map(move |s| (*proto, s)))
+        .filter(|(proto, s)| {
+            if proto.starts_with("tcp") { s.state == TCP_LISTEN } else { s.state == UDP_UNCONNECTED && s.remote.1 == 0 }
+        })
+        .map(|(proto, s)| {
+            let pid = owners.get(&s.inode).copied();
+            ListeningPort {
+                protocol: proto.to_string(),
+                address: s.local.0.to_string(),
+                port: s.local.1,
+                uid: s.uid,
+                pid,
+                process: pid.and_then(process_name),
+                exposed: !is_loopback(&s.local.0),
+            }
+        })
+        .collect();
+    ports.sort_by(|a, b| (b.exposed, a.port, &a.protocol).cmp(&(a.exposed, b.port, &b.protocol)));
+    ports
+}
+
+/// Connected TCP sockets, netstat style, established ones first.
+#[tauri::command]
+pub async fn get_connections() -> Vec<Connection> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let owners = socket_owners();
+        let counters = bandwidth::tcp_counters().unwrap_or_default();
+        let mut connections: Vec<Connection> = ["tcp", "tcp6"].iter()
+            .flat_map(|proto| read_sockets(proto).into_iter().map(move |s| (*proto, s)))
+            .filter(|(_, s)| s.state != TCP_LISTEN && s.remote.1 != 0)
+            .map(|(proto, s)| {
+                // TIME_WAIT sockets belong to no process any more, so have neither
+                let pid = owners.get(&s.inode).copied();
+                let bytes = counters.get(&s.inode);
+                Connection {
+                    protocol: proto.to_string(),
+                    local_address: s.local.0.to_string(),
+                    local_port: s.local.1,
+                    remote_address: s.remote.0.to_string(),
+                    remote_port: s.remote.1,
+                    state: tcp_state(&s.state).to_string(),
+                    uid: s.uid,
+                    pid,
+                    process: pid.and_then(process_name),
+                    bytes_received: bytes.map(|b| b.received),
+                    bytes_sent: bytes.map(|b| b.sent),
+                }
+            })
+            .collect();
+        connections.sort_by(|a, b| (a.state != "ESTABLISHED", &a.process, a.remote_port).cmp(&(b.state != "ESTABLISHED", &b.process, b.remote_port)));
+        connections
+    })
+    .await
+    .unwrap_or_default()
+}
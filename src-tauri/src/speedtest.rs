@@ -0,0 +1,226 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::AppState;
+
+// Each direction runs for at most this long, or until the transfer finishes
+const PHASE_TIME: Duration = Duration::from_secs(10);
+const PROGRESS_EVERY: Duration = Duration::from_millis(250);
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+const LATENCY_PROBES: usize = 5;
+// Sent as one request body; fast links finish it before PHASE_TIME runs out
+const UPLOAD_BYTES: usize = 100 * 1024 * 1024;
+const CHUNK: usize = 64 * 1024;
+// Sent first to check the upload endpoint accepts POSTs at all
+const PROBE_BYTES: usize = 1024;
+const ACK_POLL: Duration = Duration::from_millis(10);
+
+#[derive(Clone, serde::Serialize)]
+pub struct SpeedtestProgress {
+    /// latency, download or upload
+    phase: String,
+    bytes: u64,
+    /// Average over the phase so far
+    mbps: f64,
+}
+
+#[derive(serde::Serialize)]
+pub struct SpeedtestResult {
+    server: String,
+    /// Best TCP connect time to the download server
+    latency_ms: f64,
+    download_mbps: f64,
+    upload_mbps: f64,
+    download_bytes: u64,
+    upload_bytes: u64,
+}
+
+struct Endpoint {
+    host: String,
+    addr: SocketAddr,
+    path: String,
+}
+
+// Plain HTTP only, so there is no TLS cost distorting the result
+fn endpoint(url: &str) -> Result<Endpoint, String> {
+    let rest = url.strip_prefix("http://").ok_or_else(|| format!("Only http:// endpoints are supported: {}", url))?;
+    let (authority, path) = rest.split_once('/').map_or((rest, "/".to_string()), |(a, p)| (a, format!("/{}", p)));
+    let with_port = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+    let addr = with_port.to_socket_addrs().map_err(|e| format!("{}: {}", authority, e))?
+        .next().ok_or_else(|| format!("{} has no address", authority))?;
+    Ok(Endpoint { host: authority.to_string(), addr, path })
+}
+
+fn connect(ep: &Endpoint) -> Result<TcpStream, String> {
+    let stream = TcpStream::connect_timeout(&ep.addr, IO_TIMEOUT).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(IO_TIMEOUT)).map_err(|e| e.to_string())?;
+    stream.set_write_timeout(Some(IO_TIMEOUT)).map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+fn mbps(bytes: u64, elapsed: Duration) -> f64 {
+    bytes as f64 * 8.0 / elapsed.as_secs_f64().max(0.001) / 1_000_000.0
+}
+
+fn progress(app: &AppHandle, phase: &str, bytes: u64, elapsed: Duration) {
+    let _ = app.emit_all("speedtest://progress", SpeedtestProgress { phase: phase.to_string(), bytes, mbps: mbps(bytes, elapsed) });
+}
+
+fn latency(ep: &Endpoint) -> Result<f64, String> {
+    (0..LATENCY_PROBES)
+        .filter_map(|_| {
+            let start = Instant::now();
+            TcpStream::connect_timeout(&ep.addr, IO_TIMEOUT).ok().map(|_| start.elapsed().as_secs_f64() * 1000.0)
+        })
+        .reduce(f64::min)
+        .ok_or_else(|| format!("Could not connect to {}", ep.host))
+}
+
+// Reads the status line and headers, leaving the reader at the body
+fn read_head(reader: &mut BufReader<TcpStream>) -> Result<u16, String> {
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+    let status = line.split_whitespace().nth(1).and_then(|s| s.parse().ok()).ok_or("Not an HTTP response")?;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(|e| e.to_string())? == 0 || line == "\r\n" {
+            return Ok(status);
+        }
+    }
+}
+
+fn download(app: &AppHandle, ep: &Endpoint) -> Result<(u64, Duration), String> {
+    let mut stream = connect(ep)?;
+    write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nUser-Agent: glassview\r\n\r\n", ep.path, ep.host)
+        .map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(stream);
+    let status = read_head(&mut reader)?;
+    if status != 200 {
+        return Err(format!("Download server answered HTTP {}", status));
+    }
+    let start = Instant::now();
+    let (mut bytes, mut last) = (0u64, start);
+    let mut buf = vec![0u8; CHUNK];
+    while start.elapsed() < PHASE_TIME {
+        let n = reader.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        bytes += n as u64;
+        if last.elapsed() >= PROGRESS_EVERY {
+            progress(app, "download", bytes, start.elapsed());
+            last = Instant::now();
+        }
+    }
+    Ok((bytes, start.elapsed()))
+}
+
+fn post_head(ep: &Endpoint, length: usize) -> String {
+    format!("POST {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nUser-Agent: glassview\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\n\r\n",
+        ep.path, ep.host, length)
+}
+
+fn check_status(status: u16) -> Result<(), String> {
+    if !(200..300).contains(&status) {
+        return Err(format!("Upload server answered HTTP {}", status));
+    }
+    Ok(())
+}
+
+// Bytes the peer has acknowledged, headers included; written bytes may still sit in our buffer
+fn bytes_acked(stream: &TcpStream) -> Option<u64> {
+    let mut info: libc::tcp_info = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of::<libc::tcp_info>() as libc::socklen_t;
+    let ok = unsafe {
+        libc::getsockopt(stream.as_raw_fd(), libc::IPPROTO_TCP, libc::TCP_INFO, (&mut info as *mut libc::tcp_info).cast(), &mut len)
+    } == 0;
+    // tcpi_bytes_acked arrived in 4.2; older kernels return a shorter struct
+    (ok && len as usize >= std::mem::size_of::<libc::tcp_info>()).then_some(info.tcpi_bytes_acked)
+}
+
+// Servers that refuse an upload answer before reading the body, then stop reading
+fn answered(stream: &TcpStream) -> bool {
+    let mut byte = 0u8;
+    unsafe { libc::recv(stream.as_raw_fd(), (&mut byte as *mut u8).cast(), 1, libc::MSG_PEEK | libc::MSG_DONTWAIT) > 0 }
+}
+
+fn upload(app: &AppHandle, ep: &Endpoint) -> Result<(u64, Duration), String> {
+    let mut probe = connect(ep)?;
+    probe.write_all(post_head(ep, PROBE_BYTES).as_bytes()).and_then(|_| probe.write_all(&[0u8; PROBE_BYTES]))
+        .map_err(|e| e.to_string())?;
+    check_status(read_head(&mut BufReader::new(probe))?)?;
+
+    let mut stream = connect(ep)?;
+    let head = post_head(ep, UPLOAD_BYTES);
+    stream.write_all(head.as_bytes()).map_err(|e| e.to_string())?;
+    let acked = |s: &TcpStream| bytes_acked(s).map(|a| a.saturating_sub(head.len() as u64));
+    let start = Instant::now();
+    let (mut sent, mut last) = (0u64, start);
+    let chunk = vec![0u8; CHUNK];
+    while (sent as usize) < UPLOAD_BYTES && start.elapsed() < PHASE_TIME {
+        let n = CHUNK.min(UPLOAD_BYTES - sent as usize);
+        // Servers may hang up once they have seen enough; what arrived still counts
+        if stream.write_all(&chunk[..n]).is_err() {
+            break;
+        }
+        sent += n as u64;
+        if last.elapsed() >= PROGRESS_EVERY {
+            if answered(&stream) {
+                break;
+            }
+            progress(app, "upload", acked(&stream).unwrap_or(sent), start.elapsed());
+            last = Instant::now();
+        }
+    }
+    // The clock stops once the server has the data, not when our send buffer took it
+    let complete = sent as usize == UPLOAD_BYTES;
+    let deadline = PHASE_TIME + IO_TIMEOUT;
+    while complete && acked(&stream).is_some_and(|a| a < sent) && start.elapsed() < deadline {
+        std::thread::sleep(ACK_POLL);
+    }
+    let elapsed = start.elapsed();
+    let bytes = acked(&stream).unwrap_or(sent).min(sent);
+    // A body cut short at the time limit gets no answer; one the server refused early does
+    if complete || answered(&stream) {
+        check_status(read_head(&mut BufReader::new(stream))?)?;
+    }
+    if bytes == 0 {
+        return Err(format!("Upload to {} failed", ep.host));
+    }
+    Ok((bytes, elapsed))
+}
+
+fn run(app: &AppHandle, download_url: &str, upload_url: &str) -> Result<SpeedtestResult, String> {
+    let down = endpoint(download_url)?;
+    let up = endpoint(upload_url)?;
+    let latency_ms = latency(&down)?;
+    let _ = app.emit_all("speedtest://progress", SpeedtestProgress { phase: "latency".to_string(), bytes: 0, mbps: 0.0 });
+    let (download_bytes, down_time) = download(app, &down)?;
+    let (upload_bytes, up_time) = upload(app, &up)?;
+    Ok(SpeedtestResult {
+        server: down.host,
+        latency_ms,
+        download_mbps: mbps(download_bytes, down_time),
+        upload_mbps: mbps(upload_bytes, up_time),
+        download_bytes,
+        upload_bytes,
+    })
+}
+
+// --- Commands ---
+
+/// Measures latency, then download and upload throughput against the endpoints in settings,
+/// emitting `speedtest://progress` as it goes.
+#[tauri::command]
+pub async fn run_speedtest(app: AppHandle, state: State<'_, AppState>) -> Result<SpeedtestResult, String> {
+    let (download_url, upload_url) = {
+        let settings = state.settings.lock().unwrap();
+        (settings.speedtest_download_url.clone(), settings.speedtest_upload_url.clone())
+    };
+    tauri::async_runtime::spawn_blocking(move || run(&app, &download_url, &upload_url))
+        .await
+        .map_err(|e| e.to_string())?
+}
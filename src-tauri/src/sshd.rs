@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SSHD_CONFIG: &str = "/etc/ssh/sshd_config";
+// Includes can nest; sshd itself gives up at 16 levels
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+#[derive(serde::Serialize)]
+pub struct SshdFinding {
+    setting: String,
+    /// Effective value; built-in defaults apply when nothing sets it
+    value: String,
+    /// high, medium or low
+    severity: String,
+    message: String,
+    /// File the value came from; None for a built-in default
+    source: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct SshdAudit {
+    running: bool,
+    /// Files that couldn't be read; some distros make sshd_config root-only
+    unreadable: Vec<String>,
+    findings: Vec<SshdFinding>,
+}
+
+/// Global settings in the order sshd reads them: the first value for a keyword wins.
+#[derive(Default)]
+struct Config {
+    settings: HashMap<String, (String, String)>,
+    unreadable: Vec<String>,
+    /// A Match block started; everything after it is conditional
+    done: bool,
+}
+
+// "Include sshd_config.d/*.conf": relative to /etc/ssh, with the glob in the file name only
+fn expand_include(pattern: &str) -> Vec<PathBuf> {
+    let path = if pattern.starts_with('/') { PathBuf::from(pattern) } else { Path::new("/etc/ssh").join(pattern) };
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let Some((prefix, suffix)) = name.split_once('*') else { return vec![path] };
+    let dir = path.parent().unwrap_or(Path::new("/"));
+    let mut matches: Vec<PathBuf> = fs::read_dir(dir).into_iter().flatten().flatten()
+        .filter(|e| {
+            let n = e.file_name().to_string_lossy().to_string();
+            n.len() >= prefix.len() + suffix.len() && n.starts_with(prefix) && n.ends_with(suffix)
+        })
+        .map(|e| e.path())
+        .collect();
+    // sshd expands includes with glob(3), which sorts
+    matches.sort();
+    matches
+}
+
+impl Config {
+    fn read(&mut self, path: &Path, depth: usize) {
+        let Ok(text) = fs::read_to_string(path) else {
+            self.unreadable.push(path.to_string_lossy().to_string());
+            return;
+        };
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // "Keyword value" or "Keyword=value"
+            let (keyword, value) = line.split_once(|c: char| c.is_whitespace() || c == '=').unwrap_or((line, ""));
+            let keyword = keyword.to_lowercase();
+            let value = value.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim().trim_matches('"');
+            match keyword.as_str() {
+                "match" => self.done = true,
+                "include" if depth < MAX_INCLUDE_DEPTH => {
+                    for pattern in value.split_whitespace() {
+                        for file in expand_include(pattern) {
+                            self.read(&file, depth + 1);
+                            if self.done {
+                                return;
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    self.settings.entry(keyword).or_insert_with(|| (value.to_string(), path.to_string_lossy().to_string()));
+                }
+            }
+            if self.done {
+                return;
+            }
+        }
+    }
+
+    fn get(&self, keyword: &str) -> Option<&(String, String)> {
+        self.settings.get(keyword)
+    }
+}
+
+/// None when no SSH server is installed.
+pub fn audit() -> Option<SshdAudit> {
+    if !Path::new(SSHD_CONFIG).exists() {
+        return None;
+    }
+    let mut config = Config::default();
+    config.read(Path::new(SSHD_CONFIG), 0);
+    let running = ["sshd", "ssh"].iter().any(|unit| {
+        Command::new("systemctl").args(["is-active", "--quiet", unit]).status().is_ok_and(|s| s.success())
+    });
+    // Judging by defaults alone would report settings the file may well override
+    if config.unreadable.iter().any(|f| f == SSHD_CONFIG) {
+        return Some(SshdAudit { running, unreadable: config.unreadable, findings: Vec::new() });
+    }
+
+    let mut findings = Vec::new();
+    let mut check = |setting: &str, default: &str, risky: &dyn Fn(&str) -> Option<(&'static str, &'static str)>| {
+        let (value, source) = match config.get(&setting.to_lowercase()) {
+            Some((v, s)) => (v.clone(), Some(s.clone())),
+            None => (default.to_string(), None),
+        };
+        if let Some((severity, message)) = risky(&value.to_lowercase()) {
+            findings.push(SshdFinding {
+                setting: setting.to_string(), value, severity: severity.to_string(), message: message.to_string(), source,
+            });
+        }
+    };
+    // Defaults are those of OpenSSH 7.0 and later
+    check("PermitRootLogin", "prohibit-password", &|v| {
+        (v == "yes").then_some(("high", "root can log in with a password"))
+    });
+    check("PermitEmptyPasswords", "no", &|v| {
+        (v == "yes").then_some(("high", "Accounts without a password can log in"))
+    });
+    check("PasswordAuthentication", "yes", &|v| {
+        (v == "yes").then_some(("medium", "Passwords are accepted, which allows brute-forcing; prefer keys"))
+    });
+    let restricted = ["allowusers", "allowgroups"].iter().any(|k| config.get(k).is_some_and(|(v, _)| !v.is_empty()));
+    if !restricted {
+        findings.push(SshdFinding {
+            setting: "AllowUsers".to_string(),
+            value: String::new(),
+            severity: "low".to_string(),
+            message: "Every account may log in; AllowUsers or AllowGroups limits who can".to_string(),
+            source: None,
+        });
+    }
+    Some(SshdAudit { running, unreadable: config.unreadable, findings })
+}
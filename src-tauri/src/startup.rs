@@ -0,0 +1,529 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::services::{self, Scope};
+use crate::startup_impact::{self, Impact};
+
+/// How an entry is launched at login, which decides how it is toggled.
+#[derive(Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupKind {
+    /// XDG autostart .desktop file; `path` is the file
+    #[default]
+    Autostart,
+    /// systemd --user unit wanted by default.target; `path` is the unit name
+    Systemd,
+    /// @reboot line in the user's crontab; `path` is the command
+    Cron,
+}
+
+#[derive(serde::Serialize)]
+pub struct StartupApp {
+    kind: StartupKind,
+    /// Desktop file name, e.g. "nextcloud.desktop"; a user entry overrides the system one
+    id: String,
+    name: String,
+    exec: Option<String>,
+    icon: Option<String>,
+    comment: Option<String>,
+    path: String,
+    enabled: bool,
+    /// Lives in /etc/xdg/autostart with no user override; toggling writes one
+    system: bool,
+    /// Measured at the last login glassview was autostarted for
+    impact: Option<Impact>,
+}
+
+/// What `remove_startup_app` took out, for the UI to confirm or undo by hand.
+#[derive(serde::Serialize)]
+pub struct RemovedStartupApp {
+    name: String,
+    exec: Option<String>,
+    path: String,
+    /// Where the file went in the desktop trash
+    trashed_to: String,
+}
+
+const SYSTEM_AUTOSTART: &str = "/etc/xdg/autostart";
+
+fn autostart_dir() -> PathBuf {
+    PathBuf::from(format!("{}/.config/autostart", std::env::var("HOME").unwrap_or_default()))
+}
+
+/// Keys of one group of a desktop entry or unit file, without localized variants like Name[de].
+struct DesktopEntry {
+    keys: Vec<(String, String)>,
+}
+
+impl DesktopEntry {
+    fn parse(text: &str) -> Self {
+        Self::group(text, "[Desktop Entry]")
+    }
+
+    fn group(text: &str, group: &str) -> Self {
+        let mut keys = Vec::new();
+        let mut in_entry = false;
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_entry = line == group;
+            } else if in_entry && !line.starts_with('#') && !line.starts_with(';') {
+                if let Some((k, v)) = line.split_once('=') {
+                    if !k.contains('[') {
+                        keys.push((k.trim().to_string(), v.trim().to_string()));
+                    }
+                }
+            }
+        }
+        DesktopEntry { keys }
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.keys.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    // Hidden=true means deleted per the autostart spec; GNOME also honours its own key
+    fn enabled(&self) -> bool {
+        self.get("Hidden").as_deref() != Some("true") && self.get("X-GNOME-Autostart-enabled").as_deref() != Some("false")
+    }
+}
+
+// Sets `key` in the [Desktop Entry] group, leaving every other line alone
+fn set_key(text: &str, key: &str, value: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let (mut in_entry, mut done) = (false, false);
+    let mut group_end = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            if in_entry && !done {
+                group_end = Some(out.len());
+            }
+            in_entry = trimmed == "[Desktop Entry]";
+        } else if in_entry && trimmed.split_once('=').is_some_and(|(k, _)| k.trim() == key) {
+            out.push(format!("{}={}", key, value));
+            done = true;
+            continue;
+        }
+        out.push(line.to_string());
+    }
+    if !done {
+        // Before the next group, or at the end; skip back over blank lines so the key stays
+        // with its group
+        let mut at = group_end.unwrap_or(out.len());
+        while at > 0 && out[at - 1].trim().is_empty() {
+            at -= 1;
+        }
+        if !out.iter().any(|l| l.trim() == "[Desktop Entry]") {
+            out.insert(0, "[Desktop Entry]".to_string());
+            at = out.len();
+        }
+        out.insert(at, format!("{}={}", key, value));
+    }
+    out.join("\n") + "\n"
+}
+
+/// Turns an autostart entry on or off in place. Both keys are written so every desktop
+/// agrees; enabling clears them rather than deleting, so the file stays recognisably edited.
+pub fn set_enabled(path: &Path, enabled: bool) -> Result<(), String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let text = set_key(&text, "Hidden", if enabled { "false" } else { "true" });
+    let text = set_key(&text, "X-GNOME-Autostart-enabled", if enabled { "true" } else { "false" });
+    fs::write(path, text).map_err(|e| e.to_string())
+}
+
+fn read_app(path: &Path, system: bool) -> Option<StartupApp> {
+    let id = path.file_name()?.to_string_lossy().trim_end_matches(".bak").to_string();
+    let entry = DesktopEntry::parse(&fs::read_to_string(path).ok()?);
+    // Older glassview versions disabled entries by renaming them to .desktop.bak
+    let legacy_disabled = path.to_string_lossy().ends_with(".desktop.bak");
+    Some(StartupApp {
+        kind: StartupKind::Autostart,
+        name: entry.get("Name").unwrap_or_else(|| id.trim_end_matches(".desktop").to_string()),
+        exec: entry.get("Exec"),
+        icon: entry.get("Icon"),
+        comment: entry.get("Comment"),
+        path: path.to_string_lossy().to_string(),
+        enabled: entry.enabled() && !legacy_disabled,
+        system,
+        impact: None,
+        id,
+    })
+}
+
+// "Firefox Nightly" -> "firefox-nightly"
+fn file_stem(name: &str) -> String {
+    let stem: String = name.to_lowercase().chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    stem.split('-').filter(|p| !p.is_empty()).collect::<Vec<_>>().join("-")
+}
+
+fn on_path(program: &str) -> bool {
+    if program.contains('/') {
+        return Path::new(program).is_file();
+    }
+    std::env::var("PATH").unwrap_or_default().split(':').any(|d| Path::new(d).join(program).is_file())
+}
+
+// Splits a command line the way a shell would, so quotes keep "/opt/My App/run" one word and
+// backslashes escape the next character
+fn shell_words(cmd: &str) -> Result<Vec<String>, String> {
+    let (mut words, mut word, mut in_word) = (Vec::new(), String::new(), false);
+    let mut quote = None;
+    let mut chars = cmd.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), c) => word.push(c),
+            (Some(_), '"') => quote = None,
+            (Some(_), '\\') => match chars.next() {
+                // Within double quotes a backslash only escapes these
+                Some(n @ ('"' | '\\' | '$' | '`')) => word.push(n),
+                Some(n) => word.extend(['\\', n]),
+                None => return Err("Unterminated quote in command".to_string()),
+            },
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => (quote, in_word) = (Some(c), true),
+            (None, '\\') => {
+                word.push(chars.next().ok_or("Command ends in a backslash")?);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if std::mem::take(&mut in_word) {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err("Unterminated quote in command".to_string());
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+// Exec= quoting from the desktop entry spec: arguments with reserved characters are double
+// quoted, '%' is doubled so it isn't read as a field code, and backslashes are escaped once
+// more because the whole value is itself an escaped string
+fn exec_value(words: &[String]) -> String {
+    const RESERVED: [char; 18] = [' ', '\t', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')', '`'];
+    words.iter().map(|w| {
+        let arg = if w.is_empty() || w.contains(RESERVED) {
+            let mut quoted = String::from('"');
+            for c in w.chars() {
+                if matches!(c, '"' | '`' | '$' | '\\') {
+                    quoted.push('\\');
+                }
+                quoted.push(c);
+            }
+            quoted.push('"');
+            quoted
+        } else {
+            w.clone()
+        };
+        arg.replace('%', "%%").replace('\\', "\\\\")
+    }).collect::<Vec<_>>().join(" ")
+}
+
+// Percent-encodes everything but unreserved characters and '/', as .trashinfo expects
+fn encode_path(path: &Path) -> String {
+    path.to_string_lossy().bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+// Local time as the trash spec wants it: 2024-05-01T13:45:00
+fn local_timestamp() -> String {
+    let now = unsafe { libc::time(std::ptr::null_mut()) };
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    unsafe { libc::localtime_r(&now, &mut tm) };
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}", tm.tm_year + 1900, tm.tm_mon + 1, tm.tm_mday, tm.tm_hour, tm.tm_min, tm.tm_sec)
+}
+
+// Moves `path` into the freedesktop.org trash in ~/.local/share/Trash, so file managers can
+// restore it
+fn trash(path: &Path) -> Result<PathBuf, String> {
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .unwrap_or_else(|_| format!("{}/.local/share", std::env::var("HOME").unwrap_or_default()));
+    let (files, info) = (PathBuf::from(&data_home).join("Trash/files"), PathBuf::from(&data_home).join("Trash/info"));
+    fs::create_dir_all(&files).map_err(|e| e.to_string())?;
+    fs::create_dir_all(&info).map_err(|e| e.to_string())?;
+    let base = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let name = (1..).map(|n| if n == 1 { base.clone() } else { format!("{}.{}", base, n) })
+        .find(|n| !files.join(n).exists() && !info.join(format!("{}.trashinfo", n)).exists())
+        .unwrap_or(base);
+    fs::write(
+        info.join(format!("{}.trashinfo", name)),
+        format!("[Trash Info]\nPath={}\nDeletionDate={}\n", encode_path(path), local_timestamp()),
+    ).map_err(|e| e.to_string())?;
+    let dest = files.join(&name);
+    fs::rename(path, &dest).map_err(|e| e.to_string())?;
+    Ok(dest)
+}
+
+// User unit search path, highest precedence first
+fn user_unit_dirs() -> Vec<PathBuf> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    [
+        format!("{}/.config/systemd/user", home),
+        "/etc/systemd/user".to_string(),
+        format!("{}/.local/share/systemd/user", home),
+        "/usr/local/lib/systemd/user".to_string(),
+        "/usr/lib/systemd/user".to_string(),
+    ].into_iter().map(PathBuf::from).collect()
+}
+
+// Services whose [Install] section hooks them to default.target, i.e. started at login
+// once enabled; only enabled and disabled ones, so static and masked units are left out
+fn systemd_apps() -> Vec<StartupApp> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let mut seen: Vec<String> = Vec::new();
+    let mut candidates = Vec::new();
+    for dir in user_unit_dirs() {
+        for path in fs::read_dir(&dir).into_iter().flatten().flatten().map(|e| e.path()) {
+            let Some(unit) = path.file_name().map(|n| n.to_string_lossy().to_string()) else { continue };
+            // Templates need an instance name to be enabled
+            if !unit.ends_with(".service") || unit.contains('@') || seen.contains(&unit) {
+                continue;
+            }
+            seen.push(unit.clone());
+            let Ok(text) = fs::read_to_string(&path) else { continue };
+            let install = DesktopEntry::group(&text, "[Install]");
+            let wanted = install.keys.iter()
+                .any(|(k, v)| k == "WantedBy" && v.split_whitespace().any(|t| t == "default.target"));
+            if wanted {
+                candidates.push((unit, path, text));
+            }
+        }
+    }
+    // One query over one connection for all of them
+    let states = services::unit_states(&candidates.iter().map(|(u, _, _)| u.as_str()).collect::<Vec<_>>(), Scope::User);
+    candidates.into_iter().filter_map(|(unit, path, text)| {
+        let (file_state, _) = states.get(&unit)?;
+        if file_state != "enabled" && file_state != "disabled" {
+            return None;
+        }
+        let service = DesktopEntry::group(&text, "[Service]");
+        let description = DesktopEntry::group(&text, "[Unit]").get("Description");
+        Some(StartupApp {
+            kind: StartupKind::Systemd,
+            name: unit.trim_end_matches(".service").to_string(),
+            // Prefixes like "-" or "@" change how systemd runs the command, not what it runs
+            exec: service.get("ExecStart").map(|e| e.trim_start_matches(['-', '@', '+', '!', ':']).to_string()),
+            icon: None,
+            comment: description,
+            path: unit.clone(),
+            enabled: file_state == "enabled",
+            system: !path.starts_with(&home),
+            impact: None,
+            id: unit,
+        })
+    }).collect()
+}
+
+fn read_crontab() -> Result<String, String> {
+    let output = Command::new("crontab").arg("-l").output().map_err(|_| "cron is not installed".to_string())?;
+    // Fails with "no crontab for <user>" when there isn't one yet
+    Ok(if output.status.success() { String::from_utf8_lossy(&output.stdout).to_string() } else { String::new() })
+}
+
+fn write_crontab(text: &str) -> Result<(), String> {
+    let mut child = Command::new("crontab").arg("-")
+        .stdin(Stdio::piped()).stderr(Stdio::piped())
+        .spawn().map_err(|e| e.to_string())?;
+    child.stdin.take().ok_or("crontab has no stdin")?.write_all(text.as_bytes()).map_err(|e| e.to_string())?;
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if output.status.success() { Ok(()) } else { Err(String::from_utf8_lossy(&output.stderr).trim().to_string()) }
+}
+
+// Splits a crontab line into (enabled, command) when it is an @reboot job; disabled jobs are
+// the same line commented out
+fn reboot_job(line: &str) -> Option<(bool, &str)> {
+    let trimmed = line.trim();
+    let (enabled, rest) = match trimmed.strip_prefix('#') {
+        Some(rest) => (false, rest.trim_start()),
+        None => (true, trimmed),
+    };
+    Some((enabled, rest.strip_prefix("@reboot")?.trim()))
+}
+
+fn cron_apps() -> Vec<StartupApp> {
+    read_crontab().unwrap_or_default().lines().filter_map(reboot_job).map(|(enabled, cmd)| {
+        let program = cmd.split_whitespace().next().unwrap_or(cmd);
+        StartupApp {
+            kind: StartupKind::Cron,
+            id: cmd.to_string(),
+            name: Path::new(program).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            exec: Some(cmd.to_string()),
+            icon: None,
+            comment: Some("crontab @reboot".to_string()),
+            path: cmd.to_string(),
+            enabled,
+            system: false,
+            impact: None,
+        }
+    }).collect()
+}
+
+fn toggle_cron(cmd: &str, enable: bool) -> Result<(), String> {
+    let text = read_crontab()?;
+    let mut found = false;
+    let lines: Vec<String> = text.lines().map(|line| match reboot_job(line) {
+        Some((_, c)) if c == cmd && !found => {
+            found = true;
+            format!("{}@reboot {}", if enable { "" } else { "#" }, c)
+        }
+        _ => line.to_string(),
+    }).collect();
+    if !found {
+        return Err(format!("No @reboot job runs {}", cmd));
+    }
+    write_crontab(&(lines.join("\n") + "\n"))
+}
+
+// The program an Exec= line starts: "env FOO=1 /usr/bin/app --flag" is "app"
+fn program(exec: &str) -> Option<String> {
+    let first = exec.split_whitespace()
+        .map(|t| t.trim_matches(['"', '\'']))
+        .find(|t| *t != "env" && !t.contains('=') && !t.starts_with('-'))?;
+    Path::new(first).file_name().map(|n| n.to_string_lossy().to_string())
+}
+
+/// (id, program name) of every enabled entry, for `startup_impact` to find their processes.
+pub fn startup_programs() -> Vec<(String, String)> {
+    startup_apps().into_iter()
+        .filter(|a| a.enabled)
+        .filter_map(|a| Some((a.id, program(a.exec.as_deref()?)?)))
+        .collect()
+}
+
+fn startup_apps() -> Vec<StartupApp> {
+    let mut apps: Vec<StartupApp> = fs::read_dir(autostart_dir()).into_iter().flatten().flatten()
+        .map(|e| e.path())
+        .filter(|p| p.to_string_lossy().ends_with(".desktop") || p.to_string_lossy().ends_with(".desktop.bak"))
+        .filter_map(|p| read_app(&p, false))
+        .collect();
+    let system: Vec<StartupApp> = fs::read_dir(SYSTEM_AUTOSTART).into_iter().flatten().flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|x| x == "desktop"))
+        .filter_map(|p| read_app(&p, true))
+        .filter(|s| !apps.iter().any(|a| a.id == s.id))
+        .collect();
+    apps.extend(system);
+    apps.extend(systemd_apps());
+    apps.extend(cron_apps());
+    let impacts = startup_impact::load();
+    for app in &mut apps {
+        app.impact = impacts.get(&app.id).cloned();
+    }
+    apps.sort_by_key(|a| a.name.to_lowercase());
+    apps
+}
+
+// --- Commands ---
+
+/// Everything that launches at login, sorted by name: user autostart entries plus system-wide
+/// ones the user hasn't overridden, systemd user services and crontab @reboot jobs.
+#[tauri::command]
+pub async fn get_startup_apps() -> Result<Vec<StartupApp>, String> {
+    // Reads unit files, asks the user bus and runs crontab
+    tauri::async_runtime::spawn_blocking(startup_apps).await.map_err(|e| e.to_string())
+}
+
+/// Enables or disables an entry. Autostart files get Hidden= and X-GNOME-Autostart-enabled=,
+/// with system entries copied to ~/.config/autostart, which overrides them; systemd units are
+/// enabled or disabled; cron jobs are commented out.
+#[tauri::command]
+pub fn toggle_startup(path: String, enable: bool, kind: Option<StartupKind>) -> Result<(), String> {
+    match kind.unwrap_or_default() {
+        StartupKind::Autostart => {}
+        StartupKind::Systemd => {
+            let unit = path;
+            if unit.contains('/') {
+                return Err(format!("Invalid unit name: {}", unit));
+            }
+            return services::enable_unit(&unit, enable, Scope::User);
+        }
+        StartupKind::Cron => return toggle_cron(&path, enable),
+    }
+    let mut path = PathBuf::from(path);
+    let user_dir = autostart_dir();
+    if path.parent() == Some(Path::new(SYSTEM_AUTOSTART)) {
+        let user = user_dir.join(path.file_name().ok_or("Invalid path")?);
+        fs::create_dir_all(&user_dir).map_err(|e| e.to_string())?;
+        fs::copy(&path, &user).map_err(|e| e.to_string())?;
+        path = user;
+    } else if path.parent() != Some(user_dir.as_path()) {
+        return Err(format!("{} is not an autostart entry", path.display()));
+    }
+    if path.to_string_lossy().ends_with(".desktop.bak") {
+        let renamed = path.with_extension("");
+        fs::rename(&path, &renamed).map_err(|e| e.to_string())?;
+        path = renamed;
+    }
+    set_enabled(&path, enable)
+}
+
+/// Writes an XDG autostart entry that runs `exec` at login and returns its path.
+#[tauri::command]
+pub fn add_startup_app(name: String, exec: String, icon: Option<String>) -> Result<String, String> {
+    let (name, exec) = (name.trim(), exec.trim());
+    let icon = icon.as_deref().map(str::trim).filter(|i| !i.is_empty());
+    if name.is_empty() || exec.is_empty() {
+        return Err("Name and command are required".to_string());
+    }
+    // A newline would start a new key in the entry
+    if [name, exec, icon.unwrap_or_default()].iter().any(|v| v.contains(['\n', '\r'])) {
+        return Err("Values must be on one line".to_string());
+    }
+    let words = shell_words(exec)?;
+    let program = words.first().ok_or("Name and command are required")?;
+    if !on_path(program) {
+        return Err(format!("{} was not found", program));
+    }
+    let stem = Some(file_stem(name)).filter(|s| !s.is_empty()).unwrap_or_else(|| "startup-app".to_string());
+
+    let dir = autostart_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    // Never overwrite an existing entry, enabled or not
+    let path = (1..).map(|n| if n == 1 { stem.clone() } else { format!("{}-{}", stem, n) })
+        .map(|s| dir.join(format!("{}.desktop", s)))
+        .find(|p| !p.exists() && !p.with_extension("desktop.bak").exists())
+        .unwrap_or_default();
+
+    let mut entry = format!("[Desktop Entry]\nType=Application\nName={}\nExec={}\n", name.replace('\\', "\\\\"), exec_value(&words));
+    if let Some(icon) = icon {
+        entry.push_str(&format!("Icon={}\n", icon));
+    }
+    entry.push_str("Terminal=false\nX-GNOME-Autostart-enabled=true\n");
+    fs::write(&path, entry).map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Deletes an autostart entry by moving it to the trash, and reports what it was.
+#[tauri::command]
+pub fn remove_startup_app(path: String) -> Result<RemovedStartupApp, String> {
+    let path = PathBuf::from(path);
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    // Only entries the list above shows; anything else would make this a general file deleter
+    if path.parent() != Some(autostart_dir().as_path()) || !(name.ends_with(".desktop") || name.ends_with(".desktop.bak")) {
+        return Err(format!("{} is not an autostart entry", path.display()));
+    }
+    let entry = DesktopEntry::parse(&fs::read_to_string(&path).map_err(|e| e.to_string())?);
+    let trashed_to = trash(&path)?;
+    Ok(RemovedStartupApp {
+        name: entry.get("Name").unwrap_or_else(|| name.trim_end_matches(".bak").trim_end_matches(".desktop").to_string()),
+        exec: entry.get("Exec"),
+        path: path.to_string_lossy().to_string(),
+        trashed_to: trashed_to.to_string_lossy().to_string(),
+    })
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Duration;
+use sysinfo::{PidExt, ProcessExt, SystemExt};
+use tauri::{AppHandle, Manager};
+
+use crate::history::unix_now;
+use crate::{config, startup, AppState};
+
+const IMPACT_FILE: &str = "startup_impact.toml";
+// Startup work is what a program does in its first minute and a half after login
+const WINDOW_SECS: u64 = 90;
+// Started later than this after login, glassview wasn't itself autostarted and the
+// programs' counters include far more than startup
+const MAX_LATE_SECS: u64 = 600;
+
+/// What one startup entry cost during the last measured login.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Impact {
+    /// low, medium or high, on the same thresholds Windows Task Manager uses
+    rating: String,
+    cpu_ms: u64,
+    /// Read plus written to storage
+    disk_bytes: u64,
+    /// Unix seconds of the login measured
+    measured: u64,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct ImpactStore {
+    entries: HashMap<String, Impact>,
+}
+
+/// Stored ratings keyed by `StartupApp::id`.
+pub fn load() -> HashMap<String, Impact> {
+    config::load::<ImpactStore>(IMPACT_FILE).entries
+}
+
+fn rating(cpu_ms: u64, disk_bytes: u64) -> &'static str {
+    if cpu_ms > 1000 || disk_bytes > 3_000_000 {
+        "high"
+    } else if cpu_ms > 300 || disk_bytes > 300_000 {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+// utime + stime from /proc/<pid>/stat; the name field can hold spaces, so count from its ')'
+fn cpu_ms(pid: u32) -> u64 {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap_or_default();
+    let Some((_, rest)) = stat.rsplit_once(')') else { return 0 };
+    let fields: Vec<u64> = rest.split_whitespace().skip(11).take(2).filter_map(|f| f.parse().ok()).collect();
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64;
+    fields.iter().sum::<u64>() * 1000 / ticks
+}
+
+fn measure(app: &AppHandle) {
+    let uid = unsafe { libc::getuid() };
+    let login = {
+        let state = app.state::<AppState>();
+        let sys = state.sys.lock().unwrap();
+        // The user's oldest process, normally systemd --user, started at login
+        sys.processes().values()
+            .filter(|p| p.user_id().is_some_and(|u| **u == uid))
+            .map(|p| p.start_time())
+            .min()
+    };
+    let Some(login) = login else { return };
+    if unix_now().saturating_sub(login) > MAX_LATE_SECS {
+        return;
+    }
+    std::thread::sleep(Duration::from_secs((login + WINDOW_SECS).saturating_sub(unix_now())));
+
+    let programs = startup::startup_programs();
+    let mut store: ImpactStore = config::load(IMPACT_FILE);
+    {
+        let state = app.state::<AppState>();
+        let sys = state.sys.lock().unwrap();
+        for (id, program) in programs {
+            // The kernel truncates process names to 15 bytes
+            let comm: String = program.chars().take(15).collect();
+            let procs: Vec<_> = sys.processes().values()
+                .filter(|p| p.user_id().is_some_and(|u| **u == uid))
+                .filter(|p| p.start_time() >= login && p.start_time() <= login + WINDOW_SECS)
+                .filter(|p| p.name() == comm || p.exe().file_name().is_some_and(|n| n.to_string_lossy() == program))
+                .collect();
+            if procs.is_empty() {
+                continue;
+            }
+            let cpu: u64 = procs.iter().map(|p| cpu_ms(p.pid().as_u32())).sum();
+            let disk: u64 = procs.iter().map(|p| p.disk_usage().total_read_bytes + p.disk_usage().total_written_bytes).sum();
+            store.entries.insert(id, Impact { rating: rating(cpu, disk).to_string(), cpu_ms: cpu, disk_bytes: disk, measured: login });
+        }
+    }
+    let _ = config::save(IMPACT_FILE, &store);
+}
+
+/// Measures startup entries once, when glassview itself runs at login.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || measure(&app));
+}
@@ -0,0 +1,431 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::{alerts, smart};
+
+const INODE_ALERT_PERCENT: f64 = 90.0;
+// Temperature changes slowly, and asking a drive often enough keeps it from ever spinning
+// down; smartctl is also too slow for every tick
+const DRIVE_TEMP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(serde::Serialize)]
+pub struct InodeUsage {
+    mount_point: String,
+    device: String,
+    fs_type: String,
+    inodes_total: u64,
+    inodes_used: u64,
+    inodes_free: u64,
+    percent_used: f64,
+    low: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct MountFinding {
+    mount_point: String,
+    device: String,
+    /// "warn" for security-relevant issues, "info" for performance hints
+    severity: String,
+    issue: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct DirFileCount {
+    path: String,
+    files: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct Partition {
+    name: String,
+    path: String,
+    size: u64,
+    fs_type: Option<String>,
+    label: Option<String>,
+    mount_point: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct StorageDevice {
+    /// Kernel name, e.g. "sda" or "nvme0n1"
+    name: String,
+    path: String,
+    model: Option<String>,
+    serial: Option<String>,
+    size: u64,
+    /// Spinning disk; false for SSDs and NVMe
+    rotational: bool,
+    removable: bool,
+    /// Bus the disk hangs off: sata, nvme, usb, ...
+    transport: Option<String>,
+    /// Filesystem written straight to the disk, without a partition table
+    fs_type: Option<String>,
+    mount_point: Option<String>,
+    partitions: Vec<Partition>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct DriveTemperature {
+    /// Kernel name, e.g. "nvme0n1"
+    device: String,
+    model: Option<String>,
+    celsius: f32,
+    /// hwmon or smartctl
+    source: String,
+}
+
+impl DriveTemperature {
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    pub fn celsius(&self) -> f32 {
+        self.celsius
+    }
+}
+
+struct CachedTemperature {
+    at: Instant,
+    reading: Option<(f32, &'static str)>,
+    /// Completed reads plus writes when it was taken
+    io: u64,
+}
+
+/// Reads drive temperatures at most once a `DRIVE_TEMP_INTERVAL`, remembering the answers.
+#[derive(Default)]
+pub struct DriveThermometer {
+    cache: HashMap<String, CachedTemperature>,
+}
+
+struct Mount {
+    device: String,
+    mount_point: String,
+    fs_type: String,
+    options: Vec<String>,
+}
+
+fn read_mounts() -> Vec<Mount> {
+    fs::read_to_string("/proc/self/mounts").unwrap_or_default()
+        .lines()
+        .filter_map(|l| {
+            let f: Vec<&str> = l.split_whitespace().collect();
+            if f.len() < 4 {
+                return None;
+            }
+            Some(Mount {
+                device: f[0].to_string(),
+                // Spaces in mount points are octal-escaped
+                mount_point: f[1].replace("\\040", " "),
+                fs_type: f[2].to_string(),
+                options: f[3].split(',').map(String::from).collect(),
+            })
+        })
+        .collect()
+}
+
+// Scratch space any user can write to; executing or setuid from here is a classic foothold
+const SHARED_TMP_DIRS: [&str; 3] = ["/tmp", "/var/tmp", "/dev/shm"];
+
+fn is_removable(device: &str) -> bool {
+    let Some(name) = device.strip_prefix("/dev/") else { return false };
+    // Partitions live under their parent disk in sysfs
+    let disk = fs::canonicalize(format!("/sys/class/block/{}", name)).ok()
+        .and_then(|p| {
+            let is_partition = p.join("partition").exists();
+            let p = if is_partition { p.parent()?.to_path_buf() } else { p };
+            Some(p.file_name()?.to_string_lossy().to_string())
+        });
+    disk.and_then(|d| fs::read_to_string(format!("/sys/block/{}/removable", d)).ok())
+        .is_some_and(|v| v.trim() == "1")
+}
+
+fn audit_mount(m: &Mount) -> Vec<MountFinding> {
+    let has = |opt: &str| m.options.iter().any(|o| o == opt);
+    let mut issues: Vec<(&str, String)> = Vec::new();
+
+    let shared_tmp = SHARED_TMP_DIRS.contains(&m.mount_point.as_str());
+    let removable = m.mount_point.starts_with("/media/") || m.mount_point.starts_with("/run/media/") || is_removable(&m.device);
+    if shared_tmp || removable {
+        let what = if shared_tmp { "world-writable temp dir" } else { "removable media" };
+        for opt in ["noexec", "nodev", "nosuid"] {
+            // noexec on removable media breaks too many workflows to insist on
+            if opt == "noexec" && !shared_tmp {
+                continue;
+            }
+            if !has(opt) {
+                issues.push(("warn", format!("{} mounted without {}", what, opt)));
+            }
+        }
+    }
+
+    if m.device.starts_with('/') {
+        if has("strictatime") || !(has("relatime") || has("noatime")) {
+            issues.push(("info", "atime updated on every read; relatime or noatime avoids needless writes".to_string()));
+        }
+        if has("discard") {
+            issues.push(("info", "continuous discard can slow deletes; periodic fstrim.timer is usually preferred".to_string()));
+        }
+    }
+
+    issues.into_iter().map(|(severity, issue)| MountFinding {
+        mount_point: m.mount_point.clone(),
+        device: m.device.clone(),
+        severity: severity.to_string(),
+        issue,
+    }).collect()
+}
+
+pub fn audit_mounts() -> Vec<MountFinding> {
+    read_mounts().iter().flat_map(audit_mount).collect()
+}
+
+/// Number of security-relevant mount findings, for the security audit summary.
+pub fn risky_mount_count() -> usize {
+    audit_mounts().iter().filter(|f| f.severity == "warn").count()
+}
+
+fn sys_value(path: String) -> Option<String> {
+    fs::read_to_string(path).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+// /sys/block sizes are in 512-byte sectors whatever the disk's real sector size
+fn sectors(path: String) -> u64 {
+    sys_value(path).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0) * 512
+}
+
+// Older lsblk prints every value as a string, newer ones use numbers and booleans
+fn lsblk_str(dev: &Value, key: &str) -> Option<String> {
+    match dev.get(key)? {
+        Value::String(s) if !s.is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// Every device lsblk knows, children included, by kernel name
+fn lsblk_devices() -> HashMap<String, Value> {
+    let output = Command::new("lsblk").args(["-J", "-b", "-o", "NAME,SERIAL,TRAN,FSTYPE,LABEL,MOUNTPOINT"]).output();
+    let json: Value = output.ok().and_then(|o| serde_json::from_slice(&o.stdout).ok()).unwrap_or_default();
+    let mut devices = HashMap::new();
+    let mut stack: Vec<Value> = json.get("blockdevices").and_then(Value::as_array).cloned().unwrap_or_default();
+    while let Some(mut dev) = stack.pop() {
+        if let Some(Value::Array(children)) = dev.get_mut("children").map(Value::take) {
+            stack.extend(children);
+        }
+        if let Some(name) = lsblk_str(&dev, "name") {
+            devices.insert(name, dev);
+        }
+    }
+    devices
+}
+
+/// Physical disks from /sys/block, with the filesystem details only lsblk has.
+fn storage_devices() -> Vec<StorageDevice> {
+    let lsblk = lsblk_devices();
+    let field = |name: &str, key: &str| lsblk.get(name).and_then(|d| lsblk_str(d, key));
+    let mut disks: Vec<StorageDevice> = fs::read_dir("/sys/block").into_iter().flatten().flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let sys = format!("/sys/block/{}", name);
+            // loop, zram, dm-* and other virtual devices have no backing device
+            if !PathBuf::from(format!("{}/device", sys)).exists() {
+                return None;
+            }
+            let mut partitions: Vec<Partition> = fs::read_dir(&sys).into_iter().flatten().flatten()
+                .filter(|p| p.path().join("partition").exists())
+                .map(|p| {
+                    let part = p.file_name().to_string_lossy().to_string();
+                    Partition {
+                        path: format!("/dev/{}", part),
+                        size: sectors(format!("{}/{}/size", sys, part)),
+                        fs_type: field(&part, "fstype"),
+                        label: field(&part, "label"),
+                        mount_point: field(&part, "mountpoint"),
+                        name: part,
+                    }
+                })
+                .collect();
+            partitions.sort_by_key(|p| sys_value(format!("{}/{}/partition", sys, p.name)).and_then(|n| n.parse::<u32>().ok()));
+            Some(StorageDevice {
+                path: format!("/dev/{}", name),
+                model: sys_value(format!("{}/device/model", sys)),
+                serial: field(&name, "serial").or_else(|| sys_value(format!("{}/device/serial", sys))),
+                size: sectors(format!("{}/size", sys)),
+                rotational: sys_value(format!("{}/queue/rotational", sys)).as_deref() == Some("1"),
+                removable: sys_value(format!("{}/removable", sys)).as_deref() == Some("1"),
+                transport: field(&name, "tran"),
+                fs_type: field(&name, "fstype"),
+                mount_point: field(&name, "mountpoint"),
+                partitions,
+                name,
+            })
+        })
+        .collect();
+    disks.sort_by(|a, b| a.name.cmp(&b.name));
+    disks
+}
+
+// NVMe registers its sensor on the controller (device/hwmonN), the drivetemp module for SATA
+// on the SCSI device (device/hwmon/hwmonN); both report millidegrees
+fn hwmon_temperature(sys: &str) -> Option<f32> {
+    let device = PathBuf::from(format!("{}/device", sys));
+    fs::read_dir(&device).into_iter().chain(fs::read_dir(device.join("hwmon"))).flatten().flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("hwmon"))
+        .find_map(|e| sys_value(e.path().join("temp1_input").to_string_lossy().to_string())?.parse::<f32>().ok())
+        .map(|milli| milli / 1000.0)
+}
+
+// /sys/block/<dev>/stat: reads completed is the first field, writes completed the fifth
+fn completed_io(sys: &str) -> u64 {
+    let stat = sys_value(format!("{}/stat", sys)).unwrap_or_default();
+    let fields: Vec<u64> = stat.split_whitespace().filter_map(|f| f.parse().ok()).collect();
+    fields.first().copied().unwrap_or(0) + fields.get(4).copied().unwrap_or(0)
+}
+
+fn read_temperature(name: &str, sys: &str) -> Option<(f32, &'static str)> {
+    hwmon_temperature(sys).map(|t| (t, "hwmon"))
+        .or_else(|| smart::temperature(&format!("/dev/{}", name)).map(|t| (t, "smartctl")))
+}
+
+impl DriveThermometer {
+    /// Every physical disk that reports a temperature.
+    pub fn read(&mut self) -> Vec<DriveTemperature> {
+        let mut names: Vec<String> = fs::read_dir("/sys/block").into_iter().flatten().flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| PathBuf::from(format!("/sys/block/{}/device", n)).exists())
+            .collect();
+        names.sort();
+        self.cache.retain(|n, _| names.contains(n));
+        names.into_iter().filter_map(|name| {
+            let sys = format!("/sys/block/{}", name);
+            let io = completed_io(&sys);
+            let suspended = sys_value(format!("{}/device/power/runtime_status", sys)).as_deref() == Some("suspended");
+            // A spinning disk that has seen no I/O since the last reading may be in standby, and
+            // asking it would wake it or restart its spin-down timer; it keeps the last reading
+            let idle_disk = sys_value(format!("{}/queue/rotational", sys)).as_deref() == Some("1")
+                && self.cache.get(&name).is_some_and(|c| c.io == io);
+            let fresh = self.cache.get(&name).is_some_and(|c| c.at.elapsed() < DRIVE_TEMP_INTERVAL);
+            if !fresh && !idle_disk && !suspended {
+                let reading = read_temperature(&name, &sys);
+                self.cache.insert(name.clone(), CachedTemperature { at: Instant::now(), reading, io });
+            }
+            let (celsius, source) = self.cache.get(&name)?.reading?;
+            Some(DriveTemperature {
+                model: sys_value(format!("{}/device/model", sys)),
+                celsius,
+                source: source.to_string(),
+                device: name,
+            })
+        }).collect()
+    }
+}
+
+fn statvfs(path: &str) -> Option<libc::statvfs> {
+    let c_path = CString::new(path).ok()?;
+    let mut st: libc::statvfs = unsafe { std::mem::zeroed() };
+    (unsafe { libc::statvfs(c_path.as_ptr(), &mut st) } == 0).then_some(st)
+}
+
+fn inode_usage() -> Vec<InodeUsage> {
+    let mut seen = HashSet::new();
+    read_mounts().into_iter()
+        .filter(|m| m.device.starts_with('/') || m.fs_type == "tmpfs")
+        .filter(|m| seen.insert(m.device.clone()))
+        .filter_map(|m| {
+            let st = statvfs(&m.mount_point)?;
+            // Filesystems like btrfs allocate inodes dynamically and report zero
+            if st.f_files == 0 {
+                return None;
+            }
+            let used = st.f_files - st.f_ffree;
+            let percent_used = used as f64 / st.f_files as f64 * 100.0;
+            Some(InodeUsage {
+                mount_point: m.mount_point,
+                device: m.device,
+                fs_type: m.fs_type,
+                inodes_total: st.f_files,
+                inodes_used: used,
+                inodes_free: st.f_ffree,
+                percent_used,
+                low: percent_used >= INODE_ALERT_PERCENT,
+            })
+        })
+        .collect()
+}
+
+/// Called periodically by the sampler.
+pub fn check_inodes(app: &AppHandle) {
+    for fs in inode_usage() {
+        let key = format!("inodes:{}", fs.mount_point);
+        if fs.low {
+            alerts::raise(app, &key, "Inodes nearly exhausted",
+                &format!("{} has used {:.0}% of its inodes; new files may fail even with free space", fs.mount_point, fs.percent_used));
+        } else {
+            alerts::clear(app, &key);
+        }
+    }
+}
+
+fn count_files(root: PathBuf, limit: usize) -> Vec<DirFileCount> {
+    let root_dev = fs::metadata(&root).map(|m| m.dev()).unwrap_or(0);
+    let mut counts: HashMap<PathBuf, u64> = HashMap::new();
+    let mut stack = vec![root];
+
+    while let Some(dir) = stack.pop() {
+        let mut n = 0;
+        for entry in fs::read_dir(&dir).into_iter().flatten().flatten() {
+            n += 1;
+            if let Ok(meta) = entry.path().symlink_metadata() {
+                if meta.is_dir() && meta.dev() == root_dev {
+                    stack.push(entry.path());
+                }
+            }
+        }
+        counts.insert(dir, n);
+    }
+
+    let mut dirs: Vec<DirFileCount> = counts.into_iter()
+        .map(|(p, files)| DirFileCount { path: p.to_string_lossy().to_string(), files })
+        .collect();
+    dirs.sort_by_key(|d| std::cmp::Reverse(d.files));
+    dirs.truncate(limit);
+    dirs
+}
+
+// --- Commands ---
+
+#[tauri::command]
+pub fn get_inode_usage() -> Vec<InodeUsage> {
+    inode_usage()
+}
+
+/// Physical disks and their partitions.
+#[tauri::command]
+pub fn get_storage_devices() -> Vec<StorageDevice> {
+    storage_devices()
+}
+
+#[tauri::command]
+pub fn get_mount_audit() -> Vec<MountFinding> {
+    audit_mounts()
+}
+
+/// Directories under `path` with the most direct entries, the usual inode hogs.
+#[tauri::command]
+pub async fn find_inode_hogs(path: String, limit: usize) -> Result<Vec<DirFileCount>, String> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(format!("{} is not a directory", path));
+    }
+    tauri::async_runtime::spawn_blocking(move || count_files(root, limit))
+        .await
+        .map_err(|e| e.to_string())
+}
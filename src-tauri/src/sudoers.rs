@@ -0,0 +1,134 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SUDOERS: &str = "/etc/sudoers";
+// Granted sudo by the stock sudoers of Debian/Ubuntu, Arch/Fedora and older Ubuntu
+const DEFAULT_SUDO_GROUPS: [&str; 3] = ["sudo", "wheel", "admin"];
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+#[derive(serde::Serialize)]
+pub struct SudoRule {
+    /// User, %group or alias the rule applies to
+    principal: String,
+    rule: String,
+    /// Runs commands without asking for a password
+    nopasswd: bool,
+    source: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct PrivilegeAudit {
+    /// sudoers is root-only on most systems; without it rules are empty and sudo_users is
+    /// inferred from the usual admin groups
+    sudoers_readable: bool,
+    rules: Vec<SudoRule>,
+    /// Accounts that can use sudo, directly or through a group
+    sudo_users: Vec<String>,
+    /// Accounts besides root with UID 0
+    uid0_accounts: Vec<String>,
+}
+
+struct Account {
+    name: String,
+    uid: u32,
+    gid: u32,
+}
+
+fn accounts() -> Vec<Account> {
+    fs::read_to_string("/etc/passwd").unwrap_or_default().lines().filter_map(|l| {
+        let f: Vec<&str> = l.split(':').collect();
+        Some(Account { name: f.first()?.to_string(), uid: f.get(2)?.parse().ok()?, gid: f.get(3)?.parse().ok()? })
+    }).collect()
+}
+
+// Supplementary members from /etc/group plus accounts with it as their primary group
+fn group_members(group: &str, accounts: &[Account]) -> Vec<String> {
+    let groups = fs::read_to_string("/etc/group").unwrap_or_default();
+    let Some(f) = groups.lines().map(|l| l.split(':').collect::<Vec<_>>()).find(|f| f.first() == Some(&group)) else {
+        return Vec::new();
+    };
+    let mut members: Vec<String> = f.get(3).unwrap_or(&"").split(',').filter(|m| !m.is_empty()).map(String::from).collect();
+    if let Some(gid) = f.get(2).and_then(|g| g.parse::<u32>().ok()) {
+        members.extend(accounts.iter().filter(|a| a.gid == gid).map(|a| a.name.clone()));
+    }
+    members
+}
+
+fn read_rules(path: &Path, depth: usize, rules: &mut Vec<SudoRule>) -> bool {
+    let Ok(text) = fs::read_to_string(path) else { return false };
+    let source = path.to_string_lossy().to_string();
+    // Backslash continues a line
+    let joined = text.replace("\\\n", " ");
+    for line in joined.lines() {
+        let line = line.trim();
+        // "#include" is a directive, not a comment; sudo 1.9 spells it "@include"
+        let directive = line.strip_prefix('#').or_else(|| line.strip_prefix('@')).unwrap_or(line);
+        if let Some(dir) = directive.strip_prefix("includedir ") {
+            if depth < MAX_INCLUDE_DEPTH {
+                let mut files: Vec<PathBuf> = fs::read_dir(dir.trim()).into_iter().flatten().flatten()
+                    // sudo skips editor backups and files with a dot, like README or foo.rpmsave
+                    .filter(|e| { let n = e.file_name().to_string_lossy().to_string(); !n.contains('.') && !n.ends_with('~') })
+                    .map(|e| e.path())
+                    .collect();
+                files.sort();
+                for file in files {
+                    read_rules(&file, depth + 1, rules);
+                }
+            }
+            continue;
+        }
+        if let Some(file) = directive.strip_prefix("include ") {
+            if depth < MAX_INCLUDE_DEPTH {
+                read_rules(Path::new(file.trim()), depth + 1, rules);
+            }
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Defaults") || line.contains("_Alias") {
+            continue;
+        }
+        // "%wheel ALL=(ALL:ALL) NOPASSWD: ALL"
+        let Some((who, spec)) = line.split_once('=') else { continue };
+        let Some(principals) = who.split_whitespace().next() else { continue };
+        for principal in principals.split(',') {
+            rules.push(SudoRule {
+                principal: principal.to_string(),
+                rule: line.to_string(),
+                nopasswd: spec.contains("NOPASSWD:"),
+                source: source.clone(),
+            });
+        }
+    }
+    true
+}
+
+pub fn audit() -> PrivilegeAudit {
+    let accounts = accounts();
+    let mut rules = Vec::new();
+    let sudoers_readable = read_rules(Path::new(SUDOERS), 0, &mut rules);
+
+    let mut sudo_users = BTreeSet::new();
+    if sudoers_readable {
+        for rule in &rules {
+            match rule.principal.strip_prefix('%') {
+                Some(group) => sudo_users.extend(group_members(group, &accounts)),
+                // Aliases are upper case; users and groups by number can't be told apart from them cheaply
+                None if rule.principal != "root" && rule.principal.chars().any(|c| c.is_ascii_lowercase()) => {
+                    sudo_users.insert(rule.principal.clone());
+                }
+                None => {}
+            }
+        }
+    } else {
+        for group in DEFAULT_SUDO_GROUPS {
+            sudo_users.extend(group_members(group, &accounts));
+        }
+    }
+
+    PrivilegeAudit {
+        sudoers_readable,
+        rules,
+        sudo_users: sudo_users.into_iter().collect(),
+        uid0_accounts: accounts.iter().filter(|a| a.uid == 0 && a.name != "root").map(|a| a.name.clone()).collect(),
+    }
+}
@@ -0,0 +1,288 @@
+use serde::Serialize;
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, Manager, State};
+
+use crate::history::unix_now;
+use crate::{config, AppState};
+
+// Characters previewed per file; the archive always gets the full text
+const PREVIEW_CHARS: usize = 2000;
+
+#[derive(serde::Serialize)]
+pub struct BundleItem {
+    /// Path inside the archive
+    name: String,
+    bytes: usize,
+    /// How many hostnames, user names, addresses and the like were masked
+    redactions: usize,
+    preview: String,
+}
+
+// Fields whose values identify the machine or where it is, with what they are masked as.
+// Longest first, so "id_serial_short" wins over "serial".
+const FIELDS: [(&str, &str); 9] = [
+    ("id_serial_short", "<serial>"),
+    ("wireless network", "<ssid>"),
+    ("serial number", "<serial>"),
+    ("serial_number", "<serial>"),
+    ("serialnumber", "<serial>"),
+    ("id_serial", "<serial>"),
+    ("serial", "<serial>"),
+    ("ssid", "<ssid>"),
+    // netfilter's "MAC=<dst>:<src>:<ethertype>" in the kernel log
+    ("mac", "<mac>"),
+];
+
+/// Identifying strings masked in every file of the bundle.
+struct Redactor {
+    literals: Vec<(String, &'static str)>,
+}
+
+impl Redactor {
+    fn new() -> Self {
+        let mut literals = Vec::new();
+        // Longest first, so the home directory goes before the user name inside it
+        if let Ok(home) = std::env::var("HOME") {
+            literals.push((home, "~"));
+        }
+        if let Ok(host) = fs::read_to_string("/proc/sys/kernel/hostname") {
+            literals.push((host.trim().to_string(), "<hostname>"));
+        }
+        if let Ok(user) = std::env::var("USER") {
+            literals.push((user, "<user>"));
+        }
+        literals.retain(|(s, _)| s.len() > 1);
+        Redactor { literals }
+    }
+
+    fn redact(&self, text: &str) -> (String, usize) {
+        let mut count = 0;
+        let mut text = text.to_string();
+        for (literal, mask) in &self.literals {
+            let (masked, n) = replace_words(&text, literal, mask);
+            text = masked;
+            count += n;
+        }
+        let (text, fields) = redact_fields(&text);
+        let (text, n) = redact_addresses(&text);
+        (text, count + fields + n)
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+// Whole words only, so a user called "al" leaves "also" and /home/alice alone
+fn replace_words(text: &str, literal: &str, mask: &str) -> (String, usize) {
+    let mut out = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut last = 0;
+    for (start, _) in text.match_indices(literal) {
+        let end = start + literal.len();
+        let before = text[..start].chars().next_back().is_some_and(is_word_char);
+        let after = text[end..].chars().next().is_some_and(is_word_char);
+        if start < last || before || after {
+            continue;
+        }
+        out.push_str(&text[last..start]);
+        out.push_str(mask);
+        last = end;
+        count += 1;
+    }
+    out.push_str(&text[last..]);
+    (out, count)
+}
+
+// The value after a field name: `KEY=value`, `Key: value`, `"key": "value"` or `network 'value'`.
+// Returns where the value starts and ends; quotes stay outside it.
+fn field_value(rest: &str) -> Option<(usize, usize)> {
+    let trimmed = rest.strip_prefix('"').unwrap_or(rest).trim_start();
+    let value = match trimmed.strip_prefix(['=', ':']) {
+        Some(v) => v.trim_start(),
+        None if trimmed.starts_with(['\'', '"']) => trimmed,
+        None => return None,
+    };
+    let mut start = rest.len() - value.len();
+    let len = match value.chars().next() {
+        Some(q @ ('\'' | '"')) => {
+            start += 1;
+            value[1..].find(q).unwrap_or(value.len() - 1)
+        }
+        _ => value.find(|c: char| c.is_whitespace() || ",;)]}".contains(c)).unwrap_or(value.len()),
+    };
+    (len > 0 && &rest[start..start + len] != "null").then_some((start, start + len))
+}
+
+/// Masks disk and USB serial numbers, Wi-Fi network names and MAC headers by their field names.
+fn redact_fields(text: &str) -> (String, usize) {
+    let lower = text.to_ascii_lowercase();
+    let mut out = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut last = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let boundary = text.is_char_boundary(i) && !text[..i].chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let found = boundary.then(|| FIELDS.iter().find_map(|(key, mask)| {
+            if !lower[i..].starts_with(key) {
+                return None;
+            }
+            let (start, end) = field_value(&text[i + key.len()..])?;
+            Some((i + key.len() + start, i + key.len() + end, *mask))
+        })).flatten();
+        match found {
+            Some((start, end, mask)) => {
+                out.push_str(&text[last..start]);
+                out.push_str(mask);
+                last = end;
+                i = end;
+                count += 1;
+            }
+            None => i += 1,
+        }
+    }
+    out.push_str(&text[last..]);
+    (out, count)
+}
+
+// 00:1a:2b:3c:4d:5e, or a longer run of such pairs like a whole Ethernet header
+fn is_mac(token: &str) -> bool {
+    token.split(':').count() >= 6
+        && token.split(':').all(|p| p.len() == 2 && p.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// Loopback and unspecified addresses say nothing about the reporter and help reading the logs
+fn is_identifying_ip(token: &str) -> bool {
+    let ip = token.parse::<IpAddr>().ok()
+        .or_else(|| token.parse::<SocketAddr>().ok().map(|s| s.ip()))
+        .or_else(|| token.split_once('/').and_then(|(ip, _)| ip.parse().ok()));
+    ip.is_some_and(|ip| !ip.is_loopback() && !ip.is_unspecified())
+}
+
+/// Masks IP and MAC addresses, treating runs of address characters as candidate tokens.
+fn redact_addresses(text: &str) -> (String, usize) {
+    let is_addr_char = |c: char| c.is_ascii_hexdigit() || ".:[]/".contains(c);
+    let mut out = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut rest = text;
+    while let Some(start) = rest.find(is_addr_char) {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_addr_char(c)).unwrap_or(rest.len());
+        let raw = &rest[..end];
+        // Trailing punctuation belongs to the sentence, not the address
+        let token = raw.trim_end_matches(['.', ':']);
+        // Words like "deadbeef" or "cafe" made of hex letters only run into the next non-hex char
+        let glued = out.chars().last().is_some_and(|c| c.is_alphanumeric() || c == '_')
+            || rest[end..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        if !glued && (is_mac(token) || is_identifying_ip(token)) {
+            out.push_str(if is_mac(token) { "<mac>" } else { "<ip>" });
+            out.push_str(&raw[token.len()..]);
+            count += 1;
+        } else {
+            out.push_str(raw);
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    (out, count)
+}
+
+fn json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|e| e.to_string())
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> String {
+    match Command::new(cmd).args(args).output() {
+        Ok(o) => String::from_utf8_lossy(&o.stdout).to_string() + &String::from_utf8_lossy(&o.stderr),
+        Err(_) => format!("{} is not installed\n", cmd),
+    }
+}
+
+// Every file of the bundle, before redaction
+fn collect(state: &State<AppState>) -> Vec<(String, String)> {
+    let mut files = vec![
+        ("system/stats.json".to_string(), json(&crate::get_system_stats(state.clone()))),
+        ("system/hardware.json".to_string(), json(&crate::get_hardware_info(state.clone()))),
+        ("system/os-release".to_string(), fs::read_to_string("/etc/os-release").unwrap_or_default()),
+        ("system/kernel-cmdline".to_string(), fs::read_to_string("/proc/cmdline").unwrap_or_default()),
+        ("system/lspci.txt".to_string(), command_output("lspci", &["-nn"])),
+        ("system/lsusb.txt".to_string(), command_output("lsusb", &[])),
+        ("security/audit.json".to_string(), json(&crate::security_audit(state))),
+        ("security/kernel-integrity.json".to_string(), json(&crate::security::get_kernel_integrity())),
+        ("security/mounts.json".to_string(), json(&crate::storage::get_mount_audit())),
+        ("alerts.json".to_string(), json(&crate::alerts::get_alerts(state.clone()))),
+        ("collector-health.json".to_string(), json(&crate::health::get_collector_health(state.clone()))),
+        ("settings.json".to_string(), json(&crate::settings::get_settings(state.clone()))),
+        ("logs/journal-warnings.txt".to_string(),
+            command_output("journalctl", &["-b", "-p", "warning", "-n", "2000", "--no-pager", "-o", "short-iso"])),
+        ("logs/kernel.txt".to_string(), command_output("journalctl", &["-k", "-b", "-n", "1000", "--no-pager", "-o", "short-iso"])),
+        ("logs/failed-units.txt".to_string(), command_output("systemctl", &["--failed", "--no-pager"])),
+    ];
+    // Our own saved state, e.g. rules and profiles
+    let mut configs: Vec<PathBuf> = fs::read_dir(config::config_dir()).into_iter().flatten().flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "toml"))
+        .collect();
+    configs.sort();
+    for path in configs {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        files.push((format!("config/{}", name), fs::read_to_string(&path).unwrap_or_default()));
+    }
+    files
+}
+
+fn write_bundle(files: Vec<(String, String)>, exclude: &[String], staging: &Path, path: &str) -> Result<(), String> {
+    let redactor = Redactor::new();
+    let root = staging.join("glassview-support");
+    for (name, text) in files.into_iter().filter(|(n, _)| !exclude.contains(n)) {
+        let file = root.join(&name);
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        fs::write(file, redactor.redact(&text).0).map_err(|e| e.to_string())?;
+    }
+    let output = Command::new("tar").arg("-czf").arg(path).arg("-C").arg(staging).arg("glassview-support")
+        .output().map_err(|_| "tar is not installed".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+// --- Commands ---
+
+/// What `generate_support_bundle` would put in the archive, already redacted.
+#[tauri::command]
+pub async fn preview_support_bundle(app: AppHandle) -> Result<Vec<BundleItem>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let redactor = Redactor::new();
+        collect(&app.state()).into_iter().map(|(name, text)| {
+            let (text, redactions) = redactor.redact(&text);
+            BundleItem { name, bytes: text.len(), redactions, preview: text.chars().take(PREVIEW_CHARS).collect() }
+        }).collect()
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Writes logs, hardware inventory, alerts, configuration and the security audit into a
+/// redacted .tar.gz, leaving out the archive paths in `exclude`. Returns the archive's path.
+#[tauri::command]
+pub async fn generate_support_bundle(path: Option<String>, exclude: Option<Vec<String>>, app: AppHandle) -> Result<String, String> {
+    let exclude = exclude.unwrap_or_default();
+    let path = path.filter(|p| !p.is_empty()).unwrap_or_else(|| {
+        format!("{}/glassview-support-{}.tar.gz", std::env::var("HOME").unwrap_or_default(), unix_now())
+    });
+    tauri::async_runtime::spawn_blocking(move || {
+        let staging = config::private_temp_dir("glassview-support")?;
+        let result = write_bundle(collect(&app.state()), &exclude, &staging, &path).map(|_| path);
+        let _ = fs::remove_dir_all(&staging);
+        result
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+use sysinfo::{NetworkExt, System, SystemExt};
+use tauri::AppHandle;
+
+use crate::SystemStats;
+
+/// Metric keys accepted in `Settings::tray_metrics`.
+pub const TRAY_METRICS: [&str; 8] = ["cpu", "mem", "temp", "gpu", "vram", "net_down", "net_up", "disk_busy"];
+
+/// Id of the disabled tray menu entry that shows the metrics line.
+pub const TRAY_METRICS_ITEM: &str = "metrics";
+
+/// Renders the selected metrics into the tray tooltip and the top tray menu entry.
+/// Keeps the previous counters needed to turn totals into rates.
+pub struct TrayMeter {
+    last_at: Instant,
+    /// Milliseconds spent doing I/O, summed over whole disks
+    last_io_ms: u64,
+}
+
+impl Default for TrayMeter {
+    fn default() -> Self {
+        TrayMeter { last_at: Instant::now(), last_io_ms: read_io_ms() }
+    }
+}
+
+// Field 13 of /proc/diskstats is time spent doing I/O, in ms
+fn read_io_ms() -> u64 {
+    fs::read_to_string("/proc/diskstats").unwrap_or_default().lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>())
+        .filter(|f| f.len() > 12 && Path::new(&format!("/sys/block/{}/device", f[2])).exists())
+        .map(|f| f[12].parse::<u64>().unwrap_or(0))
+        .sum()
+}
+
+fn mb_per_sec(bytes: u64, secs: f64) -> String {
+    format!("{:.1} MB/s", bytes as f64 / 1024.0 / 1024.0 / secs)
+}
+
+impl TrayMeter {
+    pub fn update(&mut self, app: &AppHandle, metrics: &[String], sys: &System, stats: &SystemStats) {
+        let secs = self.last_at.elapsed().as_secs_f64().max(0.001);
+        let io_ms = read_io_ms();
+        // Network counters are deltas since the last refresh, which the sampler just did
+        let (down, up) = sys.networks().into_iter()
+            .fold((0, 0), |(r, t), (_, d)| (r + d.received(), t + d.transmitted()));
+
+        let parts: Vec<String> = metrics.iter().filter_map(|m| Some(match m.as_str() {
+            "cpu" => format!("CPU {:.0}%", stats.cpu_util),
+            "mem" => format!("MEM {:.1}/{:.0} GB", gib(stats.mem_used), gib(stats.mem_total)),
+            "temp" => format!("{:.0}°C", stats.cpu_temp),
+            "gpu" => format!("GPU {:.0}%", stats.gpu_busy?),
+            "vram" => format!("VRAM {:.1}/{:.0} GB", gib(stats.vram_used?), gib(stats.vram_total?)),
+            "net_down" => format!("↓ {}", mb_per_sec(down, secs)),
+            "net_up" => format!("↑ {}", mb_per_sec(up, secs)),
+            "disk_busy" => {
+                // Summed over every disk, so several busy disks are clamped to 100%
+                let busy = io_ms.saturating_sub(self.last_io_ms) as f64 / (secs * 1000.0) * 100.0;
+                format!("DISK {:.0}%", busy.min(100.0))
+            }
+            _ => return None,
+        })).collect();
+
+        self.last_at = Instant::now();
+        self.last_io_ms = io_ms;
+
+        let tray = app.tray_handle();
+        // Tooltips are unsupported on Linux, so the same line also heads the tray menu
+        let _ = tray.set_tooltip(&parts.join("\n"));
+        let _ = tray.get_item(TRAY_METRICS_ITEM).set_title(parts.join("  "));
+    }
+}
+
+fn gib(bytes: u64) -> f64 {
+    bytes as f64 / 1024.0 / 1024.0 / 1024.0
+}
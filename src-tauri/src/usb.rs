@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const USB_DEVICES: &str = "/sys/bus/usb/devices";
+// Where distros install the usbutils name database
+const USB_IDS: [&str; 4] = ["/usr/share/hwdata/usb.ids", "/usr/share/misc/usb.ids", "/var/lib/usbutils/usb.ids", "/usr/share/usb.ids"];
+
+#[derive(serde::Serialize)]
+pub struct UsbDevice {
+    /// sysfs name and USBGuard's via-port, e.g. "1-2.4"
+    port: String,
+    vendor_id: String,
+    product_id: String,
+    manufacturer: Option<String>,
+    product: Option<String>,
+    serial: Option<String>,
+    /// The kernel lets drivers bind to it
+    authorized: bool,
+    /// USBGuard's target for the device: allow, block or reject; None without USBGuard
+    policy: Option<String>,
+    /// USBGuard's device number, which its commands take
+    guard_id: Option<u32>,
+}
+
+/// One device in the bus topology; root hubs are the roots.
+#[derive(serde::Serialize)]
+pub struct UsbNode {
+    /// "usb1" for root hubs, "1-2.4" below them
+    port: String,
+    bus: u32,
+    /// Device number on the bus, as lsusb prints it
+    address: u32,
+    vendor_id: String,
+    product_id: String,
+    /// From usb.ids like lsusb, falling back to the strings the device reports
+    vendor: Option<String>,
+    product: Option<String>,
+    /// Negotiated speed in Mbit/s, e.g. "480"
+    speed: Option<String>,
+    hub: bool,
+    children: Vec<UsbNode>,
+}
+
+#[derive(serde::Serialize)]
+pub struct UsbGuardRule {
+    id: u32,
+    rule: String,
+}
+
+fn attr(dir: &Path, name: &str) -> Option<String> {
+    fs::read_to_string(dir.join(name)).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn valid_port(port: &str) -> bool {
+    !port.is_empty() && port.chars().all(|c| c.is_ascii_digit() || c == '-' || c == '.')
+}
+
+// "3: allow id 046d:c52b serial "" name "USB Receiver" ... via-port "1-2" with-interface ..."
+fn parse_guard_device(line: &str) -> Option<(String, u32, String)> {
+    let (id, rest) = line.split_once(": ")?;
+    let target = rest.split_whitespace().next()?;
+    let port = rest.split_once("via-port \"")?.1.split_once('"')?.0;
+    Some((port.to_string(), id.trim().parse().ok()?, target.to_string()))
+}
+
+// Listing works without a prompt only when the user is in USBGuard's IPCAllowedUsers
+fn guard_devices() -> HashMap<String, (u32, String)> {
+    let Ok(output) = Command::new("usbguard").arg("list-devices").output() else { return HashMap::new() };
+    if !output.status.success() {
+        return HashMap::new();
+    }
+    String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(parse_guard_device)
+        .map(|(port, id, target)| (port, (id, target)))
+        .collect()
+}
+
+// Tries without privileges first, then through pkexec
+fn usbguard(args: &[&str]) -> Result<String, String> {
+    let direct = Command::new("usbguard").args(args).output().map_err(|_| "USBGuard is not installed".to_string())?;
+    let output = if direct.status.success() || unsafe { libc::geteuid() } == 0 {
+        direct
+    } else {
+        Command::new("pkexec").arg("usbguard").args(args).output().map_err(|e| e.to_string())?
+    };
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn guard_running() -> bool {
+    Command::new("systemctl").args(["is-active", "--quiet", "usbguard"]).status().is_ok_and(|s| s.success())
+}
+
+fn set_authorized(port: &str, allow: bool, permanent: bool) -> Result<(), String> {
+    if !valid_port(port) {
+        return Err(format!("Invalid USB port: {}", port));
+    }
+    if guard_running() {
+        let guard_id = guard_devices().get(port).map(|(id, _)| id.to_string())
+            .ok_or_else(|| format!("USBGuard doesn't know the device on {}", port))?;
+        let cmd = if allow { "allow-device" } else { "block-device" };
+        let mut args = vec![cmd];
+        if permanent {
+            // Adds a rule to the policy instead of only applying to this connection
+            args.push("-p");
+        }
+        args.push(&guard_id);
+        return usbguard(&args).map(|_| ());
+    }
+    // Without USBGuard the kernel's own switch applies until the device is replugged
+    let path = format!("{}/{}/authorized", USB_DEVICES, port);
+    let value = if allow { "1" } else { "0" };
+    let mut cmd = if unsafe { libc::geteuid() } == 0 {
+        Command::new("sh")
+    } else {
+        let mut c = Command::new("pkexec");
+        c.arg("sh");
+        c
+    };
+    let status = cmd.args(["-c", "echo \"$1\" > \"$2\"", "sh", value, &path]).status().map_err(|e| e.to_string())?;
+    if status.success() { Ok(()) } else { Err(format!("Could not write {}", path)) }
+}
+
+// Vendor name and product names for the given vendor ids, from the usb.ids database:
+// "046d  Logitech, Inc." followed by tab-indented "\tc52b  Unifying Receiver" lines
+fn usb_names(vendors: &[String]) -> HashMap<String, (String, HashMap<String, String>)> {
+    let Some(text) = USB_IDS.iter().find_map(|p| fs::read(p).ok()) else { return HashMap::new() };
+    let text = String::from_utf8_lossy(&text);
+    let mut names: HashMap<String, (String, HashMap<String, String>)> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in text.lines() {
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('\t') {
+            // Two tabs are interfaces, which we don't show
+            if rest.starts_with('\t') {
+                continue;
+            }
+            if let (Some(vendor), Some((id, name))) = (&current, rest.split_once("  ")) {
+                if let Some(entry) = names.get_mut(vendor) {
+                    entry.1.insert(id.to_lowercase(), name.trim().to_string());
+                }
+            }
+            continue;
+        }
+        let Some((id, name)) = line.split_once("  ") else { current = None; continue };
+        // Device classes ("C 09  Hub") and the other tables follow the vendors
+        if id.len() != 4 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+            if id.contains(' ') {
+                break;
+            }
+            continue;
+        }
+        let id = id.to_lowercase();
+        current = vendors.contains(&id).then(|| id.clone());
+        if current.is_some() {
+            names.insert(id, (name.trim().to_string(), HashMap::new()));
+        }
+    }
+    names
+}
+
+// "1-2.4" hangs off "1-2", "1-2" off root hub "usb1"
+fn parent_port(port: &str) -> Option<String> {
+    if port.starts_with("usb") {
+        return None;
+    }
+    match port.rsplit_once('.') {
+        Some((parent, _)) => Some(parent.to_string()),
+        None => Some(format!("usb{}", port.split('-').next()?)),
+    }
+}
+
+fn sort_tree(nodes: &mut [UsbNode]) {
+    nodes.sort_by(|a, b| (a.bus, &a.port).cmp(&(b.bus, &b.port)));
+    nodes.iter_mut().for_each(|n| sort_tree(&mut n.children));
+}
+
+// --- Commands ---
+
+/// Connected USB devices, excluding root hubs, with their USBGuard policy where available.
+#[tauri::command]
+pub fn get_usb_devices() -> Vec<UsbDevice> {
+    let guard = guard_devices();
+    let mut devices: Vec<UsbDevice> = fs::read_dir(USB_DEVICES).into_iter().flatten().flatten()
+        .filter_map(|entry| {
+            let port = entry.file_name().to_string_lossy().to_string();
+            // Interfaces are "1-2:1.0" and root hubs "usb1"; neither can be blocked on its own
+            if port.contains(':') || port.starts_with("usb") {
+                return None;
+            }
+            let dir = entry.path();
+            let (guard_id, policy) = guard.get(&port).cloned().unzip();
+            Some(UsbDevice {
+                vendor_id: attr(&dir, "idVendor")?,
+                product_id: attr(&dir, "idProduct").unwrap_or_default(),
+                manufacturer: attr(&dir, "manufacturer"),
+                product: attr(&dir, "product"),
+                serial: attr(&dir, "serial"),
+                authorized: attr(&dir, "authorized").is_none_or(|a| a == "1"),
+                policy,
+                guard_id,
+                port,
+            })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.port.cmp(&b.port));
+    devices
+}
+
+/// Rules in the USBGuard policy, in the order they are matched.
+#[tauri::command]
+pub async fn get_usbguard_policy() -> Result<Vec<UsbGuardRule>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        if !guard_running() {
+            return Err("USBGuard is not running".to_string());
+        }
+        // "1: allow id 1d6b:0002 serial "0000:00:14.0" name "xHCI Host Controller" ..."
+        Ok(usbguard(&["list-rules"])?.lines().filter_map(|l| {
+            let (id, rule) = l.split_once(": ")?;
+            Some(UsbGuardRule { id: id.trim().parse().ok()?, rule: rule.to_string() })
+        }).collect())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Lets the device on `port` bind drivers again. With `permanent`, USBGuard adds a policy rule.
+#[tauri::command]
+pub async fn allow_usb_device(port: String, permanent: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || set_authorized(&port, true, permanent))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn block_usb_device(port: String, permanent: bool) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || set_authorized(&port, false, permanent))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Every USB device including hubs, nested under the hub it is plugged into.
+#[tauri::command]
+pub fn get_usb_tree() -> Vec<UsbNode> {
+    let dirs: Vec<(String, PathBuf)> = fs::read_dir(USB_DEVICES).into_iter().flatten().flatten()
+        .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+        .filter(|(port, _)| !port.contains(':'))
+        .collect();
+    let vendors: Vec<String> = dirs.iter().filter_map(|(_, d)| attr(d, "idVendor")).collect();
+    let names = usb_names(&vendors);
+
+    let mut nodes: Vec<(Option<String>, UsbNode)> = dirs.iter().filter_map(|(port, dir)| {
+        let vendor_id = attr(dir, "idVendor")?;
+        let product_id = attr(dir, "idProduct").unwrap_or_default();
+        let known = names.get(&vendor_id);
+        Some((parent_port(port), UsbNode {
+            port: port.clone(),
+            bus: attr(dir, "busnum")?.parse().ok()?,
+            address: attr(dir, "devnum")?.parse().ok()?,
+            vendor: known.map(|v| v.0.clone()).or_else(|| attr(dir, "manufacturer")),
+            product: known.and_then(|v| v.1.get(&product_id).cloned()).or_else(|| attr(dir, "product")),
+            speed: attr(dir, "speed"),
+            hub: attr(dir, "bDeviceClass").as_deref() == Some("09"),
+            vendor_id,
+            product_id,
+            children: Vec::new(),
+        }))
+    }).collect();
+
+    // Attach the deepest devices first so each hub already holds its subtree when it moves
+    nodes.sort_by_key(|(_, n)| std::cmp::Reverse(n.port.matches(['-', '.']).count()));
+    let mut roots = Vec::new();
+    while !nodes.is_empty() {
+        let (parent, node) = nodes.remove(0);
+        match parent.and_then(|p| nodes.iter_mut().find(|(_, n)| n.port == p)) {
+            Some((_, hub)) => hub.children.push(node),
+            None => roots.push(node),
+        }
+    }
+    sort_tree(&mut roots);
+    roots
+}
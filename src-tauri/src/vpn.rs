@@ -0,0 +1,194 @@
+use serde_json::Value;
+use std::fs;
+use std::process::Command;
+
+const SYS_NET: &str = "/sys/class/net";
+// Asked of the routing policy, so wg-quick's separate table counts too
+const ROUTE_PROBE: &str = "1.1.1.1";
+// ARPHRD_PPP
+const TYPE_PPP: &str = "512";
+// tun_flags bits
+const IFF_TUN: u32 = 0x1;
+const IFF_TAP: u32 = 0x2;
+
+#[derive(serde::Serialize)]
+pub struct VpnInterface {
+    name: String,
+    /// wireguard, tun, tap or ppp
+    kind: String,
+    /// Kernel operstate; tun devices report "unknown" while passing traffic
+    state: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct WireguardPeer {
+    interface: String,
+    public_key: String,
+    endpoint: Option<String>,
+    allowed_ips: Vec<String>,
+    /// Unix seconds; None if no handshake has happened yet
+    latest_handshake: Option<u64>,
+    rx_bytes: u64,
+    tx_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct Proxy {
+    /// environment, gnome or kde
+    source: String,
+    /// http, https, socks, ftp, all or pac
+    scheme: String,
+    url: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct VpnStatus {
+    interfaces: Vec<VpnInterface>,
+    /// Interface internet-bound traffic leaves through
+    route_interface: Option<String>,
+    /// The route goes through one of `interfaces`
+    tunneled: bool,
+    /// None when `wg` is missing or needs root to read the peers
+    wireguard_peers: Option<Vec<WireguardPeer>>,
+    proxies: Vec<Proxy>,
+}
+
+fn attr(name: &str, file: &str) -> Option<String> {
+    fs::read_to_string(format!("{}/{}/{}", SYS_NET, name, file)).ok().map(|v| v.trim().to_string())
+}
+
+fn vpn_kind(name: &str) -> Option<&'static str> {
+    if attr(name, "uevent").is_some_and(|u| u.lines().any(|l| l == "DEVTYPE=wireguard")) {
+        return Some("wireguard");
+    }
+    // "0x1001" is a tun device with IFF_NO_PI
+    let flags = attr(name, "tun_flags").and_then(|f| u32::from_str_radix(f.trim_start_matches("0x"), 16).ok());
+    match flags {
+        Some(f) if f & IFF_TUN != 0 => Some("tun"),
+        Some(f) if f & IFF_TAP != 0 => Some("tap"),
+        _ if attr(name, "type").as_deref() == Some(TYPE_PPP) => Some("ppp"),
+        _ => None,
+    }
+}
+
+fn vpn_interfaces() -> Vec<VpnInterface> {
+    let mut interfaces: Vec<VpnInterface> = fs::read_dir(SYS_NET).into_iter().flatten().flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            Some(VpnInterface {
+                kind: vpn_kind(&name)?.to_string(),
+                state: attr(&name, "operstate").unwrap_or_else(|| "unknown".to_string()),
+                name,
+            })
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+fn route_interface() -> Option<String> {
+    let output = Command::new("ip").args(["-j", "route", "get", ROUTE_PROBE]).output().ok()?;
+    let routes: Vec<Value> = serde_json::from_slice(&output.stdout).ok()?;
+    routes.first()?.get("dev")?.as_str().map(String::from)
+}
+
+// `wg show all dump` prints an interface line with 5 tab-separated fields, then a 9-field line
+// per peer: interface, key, preshared key, endpoint, allowed ips, handshake, rx, tx, keepalive
+fn wireguard_peers() -> Option<Vec<WireguardPeer>> {
+    let output = Command::new("wg").args(["show", "all", "dump"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let none = |v: &str| (v != "(none)" && v != "off").then(|| v.to_string());
+    Some(String::from_utf8_lossy(&output.stdout).lines().filter_map(|l| {
+        let f: Vec<&str> = l.split('\t').collect();
+        if f.len() != 9 {
+            return None;
+        }
+        Some(WireguardPeer {
+            interface: f[0].to_string(),
+            public_key: f[1].to_string(),
+            endpoint: none(f[3]),
+            allowed_ips: none(f[4]).map(|ips| ips.split(',').map(String::from).collect()).unwrap_or_default(),
+            latest_handshake: f[5].parse().ok().filter(|t| *t != 0),
+            rx_bytes: f[6].parse().unwrap_or(0),
+            tx_bytes: f[7].parse().unwrap_or(0),
+        })
+    }).collect())
+}
+
+fn proxy(source: &str, scheme: &str, url: String) -> Proxy {
+    Proxy { source: source.to_string(), scheme: scheme.to_string(), url }
+}
+
+// As inherited from the session; tools like curl and apt honour these
+fn env_proxies() -> Vec<Proxy> {
+    ["http", "https", "ftp", "all"].iter().filter_map(|scheme| {
+        let key = format!("{}_proxy", scheme);
+        let url = std::env::var(&key).or_else(|_| std::env::var(key.to_uppercase())).ok().filter(|u| !u.is_empty())?;
+        Some(proxy("environment", scheme, url))
+    }).collect()
+}
+
+fn gsettings(schema: &str, key: &str) -> Option<String> {
+    let output = Command::new("gsettings").args(["get", schema, key]).output().ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().trim_matches('\'').to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+fn gnome_proxies() -> Vec<Proxy> {
+    match gsettings("org.gnome.system.proxy", "mode").as_deref() {
+        Some("manual") => ["http", "https", "socks", "ftp"].iter().filter_map(|scheme| {
+            let schema = format!("org.gnome.system.proxy.{}", scheme);
+            let host = gsettings(&schema, "host")?;
+            let port = gsettings(&schema, "port").unwrap_or_default();
+            Some(proxy("gnome", scheme, format!("{}:{}", host, port)))
+        }).collect(),
+        Some("auto") => gsettings("org.gnome.system.proxy", "autoconfig-url")
+            .map(|url| vec![proxy("gnome", "pac", url)])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+// kioslaverc [Proxy Settings]: ProxyType 1 is manual with "host port" values, 2 is a PAC script
+fn kde_proxies() -> Vec<Proxy> {
+    let path = format!("{}/.config/kioslaverc", std::env::var("HOME").unwrap_or_default());
+    let text = fs::read_to_string(path).unwrap_or_default();
+    let section: Vec<(&str, &str)> = text.lines()
+        .skip_while(|l| l.trim() != "[Proxy Settings]").skip(1)
+        .take_while(|l| !l.starts_with('['))
+        .filter_map(|l| l.split_once('='))
+        .collect();
+    let get = |key: &str| section.iter().find(|(k, _)| *k == key).map(|(_, v)| v.trim()).filter(|v| !v.is_empty());
+    match get("ProxyType") {
+        Some("1") => [("http", "httpProxy"), ("https", "httpsProxy"), ("socks", "socksProxy"), ("ftp", "ftpProxy")].iter()
+            .filter_map(|(scheme, key)| Some(proxy("kde", scheme, get(key)?.replacen(' ', ":", 1))))
+            .collect(),
+        Some("2") => get("Proxy Config Script").map(|url| vec![proxy("kde", "pac", url.to_string())]).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+// --- Commands ---
+
+/// VPN interfaces, WireGuard peers, configured proxies and whether the route out is tunneled.
+#[tauri::command]
+pub async fn get_vpn_status() -> VpnStatus {
+    tauri::async_runtime::spawn_blocking(|| {
+        let interfaces = vpn_interfaces();
+        let route_interface = route_interface();
+        let mut proxies = env_proxies();
+        proxies.extend(gnome_proxies());
+        proxies.extend(kde_proxies());
+        VpnStatus {
+            tunneled: route_interface.as_ref().is_some_and(|r| interfaces.iter().any(|i| &i.name == r)),
+            wireguard_peers: interfaces.iter().any(|i| i.kind == "wireguard").then(wireguard_peers).flatten(),
+            interfaces,
+            route_interface,
+            proxies,
+        }
+    })
+    .await
+    .unwrap_or(VpnStatus { interfaces: Vec::new(), route_interface: None, tunneled: false, wireguard_peers: None, proxies: Vec::new() })
+}
@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+use sysinfo::{NetworkExt, System, SystemExt};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{alerts, config, AppState, SystemStats};
+
+const WATCHES_FILE: &str = "watches.toml";
+
+/// Names usable in expressions. Sizes are bytes, rates bytes (or pages) per second,
+/// percentages 0-100 and times seconds.
+pub const VARIABLES: [&str; 18] = [
+    "cpu", "cpu_temp", "drive_temp", "load1", "load5", "load15",
+    "mem_used", "mem_total", "swap_used", "swap_total", "swap_rate",
+    "net_in_rate", "net_out_rate", "gpu_busy", "vram_used", "vram_total",
+    "proc_count", "uptime",
+];
+
+/// A user-defined derived metric, e.g. `mem_used/mem_total > 0.9 && swap_rate > 10MB/s`.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct Watch {
+    name: String,
+    expression: String,
+    /// Show the value on the dashboard
+    #[serde(default)]
+    tile: bool,
+    /// Raise an alert while the expression is non-zero
+    #[serde(default)]
+    alert: bool,
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct WatchFile {
+    watches: Vec<Watch>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct WatchValue {
+    #[serde(flatten)]
+    watch: Watch,
+    /// None while a metric it reads is unavailable, e.g. `gpu_busy` without a GPU
+    value: Option<f64>,
+    /// Result of a comparison or logical operator, so the UI can show TRUE/FALSE
+    boolean: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+#[derive(Debug)]
+enum Expr {
+    Num(f64),
+    Var(String),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+// Longest first so ">=" isn't read as ">" followed by "="
+const OPERATORS: [&str; 14] = ["&&", "||", ">=", "<=", "==", "!=", ">", "<", "+", "-", "*", "/", "!", "%"];
+
+fn unit_factor(unit: &str) -> Option<f64> {
+    // Rates are per second already, so "/s" only documents intent
+    let unit = unit.strip_suffix("/s").unwrap_or(unit);
+    Some(match unit {
+        "" | "%" | "B" | "s" => 1.0,
+        "ms" => 0.001,
+        "min" => 60.0,
+        "h" => 3600.0,
+        "KB" | "K" | "KiB" => 1024.0,
+        "MB" | "M" | "MiB" => 1024.0 * 1024.0,
+        "GB" | "G" | "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TB" | "T" | "TiB" => 1024.0_f64.powi(4),
+        _ => return None,
+    })
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = src.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let num: f64 = chars[start..i].iter().collect::<String>().parse()
+                .map_err(|_| format!("Bad number at {}", start))?;
+            // A unit glued to the number: 10MB/s, 90%, 500ms
+            let unit_start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphabetic() || chars[i] == '%') {
+                i += 1;
+            }
+            // "/s" ends the unit only on its own; in "10%/sys_load" it starts a division
+            let name_char = |c: Option<&char>| c.is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_');
+            if i > unit_start && chars.get(i) == Some(&'/') && chars.get(i + 1) == Some(&'s') && !name_char(chars.get(i + 2)) {
+                i += 2;
+            }
+            let unit: String = chars[unit_start..i].iter().collect();
+            let factor = unit_factor(&unit).ok_or_else(|| format!("Unknown unit: {}", unit))?;
+            tokens.push(Token::Num(num * factor));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            tokens.push(match name.as_str() {
+                "and" => Token::Op("&&"),
+                "or" => Token::Op("||"),
+                "not" => Token::Op("!"),
+                _ => Token::Ident(name),
+            });
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            i += 1;
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let op = OPERATORS.iter().find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format!("Unexpected '{}' at {}", c, i))?;
+            tokens.push(Token::Op(op));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+// Binding strength of each binary operator, loosest first
+fn precedence(op: &str) -> Option<u8> {
+    Some(match op {
+        "||" => 1,
+        "&&" => 2,
+        "==" | "!=" => 3,
+        ">" | ">=" | "<" | "<=" => 4,
+        "+" | "-" => 5,
+        "*" | "/" | "%" => 6,
+        _ => return None,
+    })
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn expr(&mut self, min_prec: u8) -> Result<Expr, String> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Op(op)) = self.peek() {
+            let op = *op;
+            let Some(prec) = precedence(op).filter(|p| *p >= min_prec) else { break };
+            self.pos += 1;
+            let rhs = self.expr(prec + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Num(n)) => Ok(Expr::Num(n)),
+            Some(Token::Ident(name)) => {
+                if !VARIABLES.contains(&name.as_str()) {
+                    return Err(format!("Unknown metric: {}", name));
+                }
+                Ok(Expr::Var(name))
+            }
+            Some(Token::Op("!")) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Op("-")) => Ok(Expr::Neg(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let inner = self.expr(1)?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("Missing ')'".to_string()),
+                }
+            }
+            Some(t) => Err(format!("Unexpected {:?}", t)),
+            None => Err("Expression ends early".to_string()),
+        }
+    }
+}
+
+fn parse(src: &str) -> Result<Expr, String> {
+    let mut parser = Parser { tokens: tokenize(src)?, pos: 0 };
+    let expr = parser.expr(1)?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(t) => Err(format!("Unexpected {:?}", t)),
+    }
+}
+
+fn is_boolean(expr: &Expr) -> bool {
+    match expr {
+        Expr::Not(_) => true,
+        Expr::Binary(op, _, _) => precedence(op).is_some_and(|p| p <= 4),
+        _ => false,
+    }
+}
+
+fn truth(b: bool) -> f64 {
+    if b { 1.0 } else { 0.0 }
+}
+
+fn eval(expr: &Expr, vars: &HashMap<&str, f64>) -> Option<f64> {
+    Some(match expr {
+        Expr::Num(n) => *n,
+        Expr::Var(name) => *vars.get(name.as_str())?,
+        Expr::Not(e) => truth(eval(e, vars)? == 0.0),
+        Expr::Neg(e) => -eval(e, vars)?,
+        Expr::Binary(op, l, r) => {
+            let l = eval(l, vars)?;
+            // Short-circuit, so a missing GPU on the right doesn't hide a true left side
+            match *op {
+                "&&" if l == 0.0 => return Some(0.0),
+                "||" if l != 0.0 => return Some(1.0),
+                _ => {}
+            }
+            let r = eval(r, vars)?;
+            match *op {
+                "&&" | "||" => truth(r != 0.0),
+                ">" => truth(l > r),
+                ">=" => truth(l >= r),
+                "<" => truth(l < r),
+                "<=" => truth(l <= r),
+                "==" => truth(l == r),
+                "!=" => truth(l != r),
+                "+" => l + r,
+                "-" => l - r,
+                "*" => l * r,
+                "/" if r == 0.0 => return None,
+                "/" => l / r,
+                "%" if r == 0.0 => return None,
+                "%" => l % r,
+                _ => return None,
+            }
+        }
+    })
+}
+
+fn reads(expr: &Expr, var: &str) -> bool {
+    match expr {
+        Expr::Num(_) => false,
+        Expr::Var(name) => name == var,
+        Expr::Not(e) | Expr::Neg(e) => reads(e, var),
+        Expr::Binary(_, l, r) => reads(l, var) || reads(r, var),
+    }
+}
+
+struct Compiled {
+    watch: Watch,
+    expr: Result<Expr, String>,
+}
+
+/// Compiled watches plus the counters needed to turn totals into rates.
+pub struct WatchEngine {
+    watches: Vec<Compiled>,
+    last_at: Instant,
+    last_swap_pages: u64,
+    last_net: (u64, u64),
+    values: Vec<WatchValue>,
+}
+
+// pswpin + pswpout from /proc/vmstat
+fn swap_pages() -> u64 {
+    fs::read_to_string("/proc/vmstat").unwrap_or_default().lines()
+        .filter_map(|l| l.strip_prefix("pswpin ").or_else(|| l.strip_prefix("pswpout ")))
+        .filter_map(|v| v.trim().parse::<u64>().ok())
+        .sum()
+}
+
+fn net_totals(sys: &System) -> (u64, u64) {
+    sys.networks().into_iter()
+        .fold((0, 0), |(r, t), (_, d)| (r + d.total_received(), t + d.total_transmitted()))
+}
+
+impl WatchEngine {
+    pub fn load() -> Self {
+        let file: WatchFile = config::load(WATCHES_FILE);
+        let mut engine = WatchEngine {
+            watches: Vec::new(), last_at: Instant::now(), last_swap_pages: swap_pages(), last_net: (0, 0), values: Vec::new(),
+        };
+        engine.set(file.watches);
+        engine
+    }
+
+    fn set(&mut self, watches: Vec<Watch>) {
+        self.watches = watches.into_iter().map(|w| Compiled { expr: parse(&w.expression), watch: w }).collect();
+        self.values.clear();
+    }
+
+    fn variables(&mut self, sys: &System, stats: &SystemStats) -> HashMap<&'static str, f64> {
+        let secs = self.last_at.elapsed().as_secs_f64().max(0.001);
+        let swap = swap_pages();
+        let net = net_totals(sys);
+        // The first call has no previous network totals
+        let (net_in, net_out) = if self.last_net == (0, 0) { (0, 0) } else {
+            (net.0.saturating_sub(self.last_net.0), net.1.saturating_sub(self.last_net.1))
+        };
+        let load = sys.load_average();
+        let mut vars = HashMap::from([
+            ("cpu", stats.cpu_util as f64),
+            ("cpu_temp", stats.cpu_temp as f64),
+            ("load1", load.one),
+            ("load5", load.five),
+            ("load15", load.fifteen),
+            ("mem_used", stats.mem_used as f64),
+            ("mem_total", stats.mem_total as f64),
+            ("swap_used", sys.used_swap() as f64),
+            ("swap_total", sys.total_swap() as f64),
+            // Pages are 4 KiB on every platform we run on
+            ("swap_rate", swap.saturating_sub(self.last_swap_pages) as f64 * 4096.0 / secs),
+            ("net_in_rate", net_in as f64 / secs),
+            ("net_out_rate", net_out as f64 / secs),
+            ("proc_count", stats.proc_count as f64),
+            ("uptime", stats.uptime as f64),
+        ]);
+        // The hottest drive
+        if let Some(t) = stats.drive_temps.iter().map(|d| d.celsius()).reduce(f32::max) {
+            vars.insert("drive_temp", t as f64);
+        }
+        if let Some(busy) = stats.gpu_busy {
+            vars.insert("gpu_busy", busy as f64);
+        }
+        if let (Some(used), Some(total)) = (stats.vram_used, stats.vram_total) {
+            vars.insert("vram_used", used as f64);
+            vars.insert("vram_total", total as f64);
+        }
+        self.last_at = Instant::now();
+        self.last_swap_pages = swap;
+        self.last_net = net;
+        vars
+    }
+
+    /// Whether any watch reads one of `vars`, so the sampler can skip collecting the rest.
+    pub fn uses(&self, vars: &[&str]) -> bool {
+        self.watches.iter().any(|c| c.expr.as_ref().is_ok_and(|e| vars.iter().any(|v| reads(e, v))))
+    }
+
+    /// Evaluates every watch against fresh stats. Called by the sampler after each stats refresh.
+    pub fn evaluate(&mut self, sys: &System, stats: &SystemStats) -> Vec<WatchValue> {
+        let vars = self.variables(sys, stats);
+        self.values = self.watches.iter().map(|c| match &c.expr {
+            Ok(expr) => WatchValue { watch: c.watch.clone(), value: eval(expr, &vars), boolean: is_boolean(expr), error: None },
+            Err(e) => WatchValue { watch: c.watch.clone(), value: None, boolean: false, error: Some(e.clone()) },
+        }).collect();
+        self.values.clone()
+    }
+}
+
+/// Raises or clears the alert of each alerting watch and pushes the values to the window.
+pub fn publish(app: &AppHandle, values: &[WatchValue]) {
+    for v in values.iter().filter(|v| v.watch.alert) {
+        let key = format!("watch:{}", v.watch.name);
+        match v.value {
+            Some(x) if x != 0.0 => alerts::raise(app, &key, &v.watch.name, &format!("Watch condition met: {}", v.watch.expression)),
+            _ => alerts::clear(app, &key),
+        }
+    }
+    if !values.is_empty() {
+        let _ = app.emit_all("watches://update", values);
+    }
+}
+
+// --- Commands ---
+
+/// Watches with their latest values.
+#[tauri::command]
+pub fn get_watches(state: State<AppState>) -> Vec<WatchValue> {
+    let engine = state.watches.lock().unwrap();
+    if !engine.values.is_empty() {
+        return engine.values.clone();
+    }
+    // Edited since the last sample; values arrive with the next stats refresh
+    engine.watches.iter().map(|c| WatchValue {
+        watch: c.watch.clone(), value: None, boolean: c.expr.as_ref().is_ok_and(is_boolean), error: c.expr.as_ref().err().cloned(),
+    }).collect()
+}
+
+#[tauri::command]
+pub fn get_watch_variables() -> Vec<&'static str> {
+    VARIABLES.to_vec()
+}
+
+/// Adds a watch, or replaces the one with the same name. Rejects expressions that don't parse.
+#[tauri::command]
+pub fn save_watch(watch: Watch, state: State<AppState>) -> Result<(), String> {
+    if watch.name.trim().is_empty() {
+        return Err("Watch needs a name".to_string());
+    }
+    parse(&watch.expression)?;
+    let mut engine = state.watches.lock().unwrap();
+    let mut watches: Vec<Watch> = engine.watches.iter().map(|c| c.watch.clone()).filter(|w| w.name != watch.name).collect();
+    watches.push(watch);
+    config::save(WATCHES_FILE, &WatchFile { watches: watches.clone() })?;
+    engine.set(watches);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_watch(name: String, app: AppHandle, state: State<AppState>) -> Result<(), String> {
+    let mut engine = state.watches.lock().unwrap();
+    let watches: Vec<Watch> = engine.watches.iter().map(|c| c.watch.clone()).filter(|w| w.name != name).collect();
+    config::save(WATCHES_FILE, &WatchFile { watches: watches.clone() })?;
+    engine.set(watches);
+    alerts::clear(&app, &format!("watch:{}", name));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_second_suffix_is_not_taken_from_a_name() {
+        let ident = |name: &str| Token::Ident(name.to_string());
+        assert_eq!(tokenize("10MB/s").unwrap(), vec![Token::Num(10.0 * 1024.0 * 1024.0)]);
+        assert_eq!(tokenize("10/sys_load").unwrap(), vec![Token::Num(10.0), Token::Op("/"), ident("sys_load")]);
+        assert_eq!(tokenize("50%/swap_total").unwrap(), vec![Token::Num(50.0), Token::Op("/"), ident("swap_total")]);
+        assert_eq!(tokenize("1K/s2").unwrap(), vec![Token::Num(1024.0), Token::Op("/"), ident("s2")]);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use zbus::blocking::Connection;
+use zbus::dbus_proxy;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, Value};
+use zbus::CacheProperties;
+
+use crate::network::NetworkManagerProxyBlocking;
+
+// NM_DEVICE_TYPE_WIFI
+const DEVICE_TYPE_WIFI: u32 = 2;
+// Scans take a few seconds; NetworkManager refuses new ones for a while after the last
+const SCAN_TIMEOUT: Duration = Duration::from_secs(10);
+const SCAN_POLL: Duration = Duration::from_millis(500);
+// NM_802_11_AP_FLAGS_PRIVACY and NM_802_11_AP_SEC_* key management bits
+const AP_PRIVACY: u32 = 0x1;
+const KEY_MGMT_PSK: u32 = 0x100;
+const KEY_MGMT_8021X: u32 = 0x200;
+const KEY_MGMT_SAE: u32 = 0x400;
+
+#[dbus_proxy(interface = "org.freedesktop.NetworkManager.Device", default_service = "org.freedesktop.NetworkManager")]
+trait Device {
+    #[dbus_proxy(property)]
+    fn device_type(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn interface(&self) -> zbus::Result<String>;
+}
+
+#[dbus_proxy(interface = "org.freedesktop.NetworkManager.Device.Wireless", default_service = "org.freedesktop.NetworkManager")]
+trait Wireless {
+    fn request_scan(&self, options: HashMap<&str, Value<'_>>) -> zbus::Result<()>;
+    fn get_all_access_points(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+    /// CLOCK_BOOTTIME milliseconds of the last finished scan
+    #[dbus_proxy(property)]
+    fn last_scan(&self) -> zbus::Result<i64>;
+    #[dbus_proxy(property)]
+    fn active_access_point(&self) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[dbus_proxy(interface = "org.freedesktop.NetworkManager.AccessPoint", default_service = "org.freedesktop.NetworkManager")]
+trait AccessPoint {
+    #[dbus_proxy(property)]
+    fn ssid(&self) -> zbus::Result<Vec<u8>>;
+    #[dbus_proxy(property)]
+    fn hw_address(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn strength(&self) -> zbus::Result<u8>;
+    #[dbus_proxy(property)]
+    fn frequency(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn flags(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn wpa_flags(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn rsn_flags(&self) -> zbus::Result<u32>;
+}
+
+#[derive(serde::Serialize)]
+pub struct WifiNetwork {
+    ssid: String,
+    /// The strongest access point seen for the SSID
+    bssid: String,
+    /// Percent
+    strength: u8,
+    frequency_mhz: u32,
+    /// open, wep, wpa, wpa2, wpa2/wpa3 (transition mode), wpa3 or enterprise
+    security: String,
+    active: bool,
+    /// Interface that saw it, e.g. "wlan0"
+    device: String,
+}
+
+struct WifiDevice {
+    path: OwnedObjectPath,
+    interface: String,
+    wireless: WirelessProxyBlocking<'static>,
+}
+
+fn system() -> Result<Connection, String> {
+    Connection::system().map_err(|e| e.to_string())
+}
+
+// Uncached, so properties are read fresh while waiting for a scan
+fn wifi_devices(conn: &Connection) -> Result<Vec<WifiDevice>, String> {
+    let nm = NetworkManagerProxyBlocking::new(conn).map_err(|_| "NetworkManager is not running".to_string())?;
+    let devices = nm.get_devices().map_err(|_| "NetworkManager is not running".to_string())?;
+    let wifi: Vec<WifiDevice> = devices.into_iter().filter_map(|path| {
+        let device = DeviceProxyBlocking::builder(conn).path(path.clone()).ok()?.build().ok()?;
+        if device.device_type().ok()? != DEVICE_TYPE_WIFI {
+            return None;
+        }
+        let wireless = WirelessProxyBlocking::builder(conn)
+            .path(path.clone()).ok()?
+            .cache_properties(CacheProperties::No)
+            .build().ok()?;
+        Some(WifiDevice { interface: device.interface().unwrap_or_default(), path, wireless })
+    }).collect();
+    if wifi.is_empty() {
+        return Err("No Wi-Fi device managed by NetworkManager".to_string());
+    }
+    Ok(wifi)
+}
+
+fn security(flags: u32, wpa: u32, rsn: u32) -> &'static str {
+    if (wpa | rsn) & KEY_MGMT_8021X != 0 {
+        "enterprise"
+    } else if rsn & KEY_MGMT_SAE != 0 && rsn & KEY_MGMT_PSK != 0 {
+        // Transition mode takes either; plenty of adapters and drivers can't do SAE
+        "wpa2/wpa3"
+    } else if rsn & KEY_MGMT_SAE != 0 {
+        "wpa3"
+    } else if rsn & KEY_MGMT_PSK != 0 {
+        "wpa2"
+    } else if wpa & KEY_MGMT_PSK != 0 {
+        "wpa"
+    } else if flags & AP_PRIVACY != 0 {
+        "wep"
+    } else {
+        "open"
+    }
+}
+
+fn access_point(conn: &Connection, path: OwnedObjectPath) -> Option<(OwnedObjectPath, AccessPointProxyBlocking<'static>)> {
+    let ap = AccessPointProxyBlocking::builder(conn).path(path.clone()).ok()?.build().ok()?;
+    Some((path, ap))
+}
+
+// Asks every device to scan, then waits until each has finished or the timeout passes
+fn scan(devices: &[WifiDevice]) {
+    let before: Vec<i64> = devices.iter().map(|d| d.wireless.last_scan().unwrap_or(0)).collect();
+    // Refused while a scan is running or one has just finished; the results then are fresh anyway
+    let requested: Vec<bool> = devices.iter().map(|d| d.wireless.request_scan(HashMap::new()).is_ok()).collect();
+    let start = Instant::now();
+    while start.elapsed() < SCAN_TIMEOUT {
+        let pending = devices.iter().zip(&before).zip(&requested)
+            .any(|((d, b), r)| *r && d.wireless.last_scan().unwrap_or(0) == *b);
+        if !pending {
+            break;
+        }
+        std::thread::sleep(SCAN_POLL);
+    }
+}
+
+fn networks(conn: &Connection, devices: &[WifiDevice]) -> Vec<WifiNetwork> {
+    let mut best: HashMap<String, WifiNetwork> = HashMap::new();
+    for device in devices {
+        let active = device.wireless.active_access_point().ok();
+        for (path, ap) in device.wireless.get_all_access_points().unwrap_or_default().into_iter().filter_map(|p| access_point(conn, p)) {
+            let ssid = String::from_utf8_lossy(&ap.ssid().unwrap_or_default()).to_string();
+            // Hidden networks can't be picked without knowing their name
+            if ssid.is_empty() {
+                continue;
+            }
+            let network = WifiNetwork {
+                bssid: ap.hw_address().unwrap_or_default(),
+                strength: ap.strength().unwrap_or(0),
+                frequency_mhz: ap.frequency().unwrap_or(0),
+                security: security(ap.flags().unwrap_or(0), ap.wpa_flags().unwrap_or(0), ap.rsn_flags().unwrap_or(0)).to_string(),
+                active: active.as_ref() == Some(&path),
+                device: device.interface.clone(),
+                ssid: ssid.clone(),
+            };
+            let keep = best.get(&ssid).is_none_or(|b| !b.active && (network.active || network.strength > b.strength));
+            if keep {
+                best.insert(ssid, network);
+            }
+        }
+    }
+    let mut networks: Vec<WifiNetwork> = best.into_values().collect();
+    networks.sort_by_key(|n| (!n.active, std::cmp::Reverse(n.strength)));
+    networks
+}
+
+// WPA passphrases are 8 to 63 printable characters, or the 64-digit hex key itself
+fn valid_psk(psk: &str) -> bool {
+    (8..=63).contains(&psk.len()) && psk.chars().all(|c| (' '..='~').contains(&c))
+        || psk.len() == 64 && psk.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn connect(ssid: &str, psk: Option<&str>) -> Result<(), String> {
+    let conn = system()?;
+    let devices = wifi_devices(&conn)?;
+    // The strongest access point of the network, on whichever device sees it
+    let (device, (ap_path, ap)) = devices.iter()
+        .flat_map(|d| d.wireless.get_all_access_points().unwrap_or_default().into_iter().filter_map(|p| access_point(&conn, p)).map(move |ap| (d, ap)))
+        .filter(|(_, (_, ap))| ap.ssid().is_ok_and(|s| s == ssid.as_bytes()))
+        .max_by_key(|(_, (_, ap))| ap.strength().unwrap_or(0))
+        .ok_or_else(|| format!("{} is not in range", ssid))?;
+    let security = security(ap.flags().unwrap_or(0), ap.wpa_flags().unwrap_or(0), ap.rsn_flags().unwrap_or(0));
+    let nm = NetworkManagerProxyBlocking::new(&conn).map_err(|e| e.to_string())?;
+    let device_path = ObjectPath::from(&device.path);
+    let ap_path = ObjectPath::from(&ap_path);
+    match psk {
+        // Reuses a saved profile for the network, with its stored secrets
+        None if security != "open" => {
+            let root = ObjectPath::try_from("/").map_err(|e| e.to_string())?;
+            nm.activate_connection(&root, &device_path, &ap_path)
+                .map(|_| ())
+                .map_err(|_| format!("{} needs a password", ssid))
+        }
+        _ if security == "enterprise" || security == "wep" => Err(format!("{} security is not supported here", security.to_uppercase())),
+        _ => {
+            let mut settings: HashMap<&str, HashMap<&str, Value>> = HashMap::new();
+            if let Some(psk) = psk {
+                if !valid_psk(psk) {
+                    return Err("A WPA password is 8 to 63 characters".to_string());
+                }
+                // Only SAE-only networks need it; transition mode connects with a plain PSK
+                let key_mgmt = if security == "wpa3" { "sae" } else { "wpa-psk" };
+                settings.insert("802-11-wireless-security", HashMap::from([("key-mgmt", Value::from(key_mgmt)), ("psk", Value::from(psk))]));
+            }
+            // NetworkManager fills in the SSID and the rest from the access point
+            nm.add_and_activate_connection(settings, &device_path, &ap_path)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+// --- Commands ---
+
+/// Scans on every Wi-Fi device and lists the networks in range, one entry per SSID.
+#[tauri::command]
+pub async fn scan_wifi() -> Result<Vec<WifiNetwork>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let conn = system()?;
+        let devices = wifi_devices(&conn)?;
+        scan(&devices);
+        Ok(networks(&conn, &devices))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Connects to `ssid`, saving a new profile when `psk` is given and reusing a saved one otherwise.
+#[tauri::command]
+pub async fn connect_wifi(ssid: String, psk: Option<String>) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || connect(&ssid, psk.as_deref().filter(|p| !p.is_empty())))
+        .await
+        .map_err(|e| e.to_string())?
+}